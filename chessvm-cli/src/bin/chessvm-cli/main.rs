@@ -1,7 +1,11 @@
-use std::{env, str::FromStr};
+use std::{env, fs, str::FromStr};
 
-use alloy_primitives::Address;
-use chessvm::{api::chain_handlers::MoveEnum, client};
+use avalanche_types::{ids, key::secp256k1::private_key};
+use chessvm::{
+    api::chain_handlers::{ImportGameArgs, MoveEnum, TxAuth},
+    client, pgn,
+    state::{puzzle, rating::TimeCategory, variant::GameVariant},
+};
 use clap::{command, Arg, ArgMatches, Command};
 
 #[tokio::main]
@@ -26,14 +30,15 @@ async fn main() {
             )
             .subcommand(
                 Command::new("create-game")
-                    .about("Creates a new Chess Game")
-                    .arg(
-                        Arg::new("white").required(true).help(
-                            "The address of the white player; must be a valid Ethereum address.",
-                        ),
-                    )
+                    .about("Creates a new Chess Game, in which you play white")
                     .arg(Arg::new("black").required(true).help(
-                        "The address of the black player; must be a valid Ethereum address.",
+                        "The address of the black player; a valid Ethereum address or a registered @name.",
+                    ))
+                    .arg(Arg::new("variant").long("variant").help(
+                        "The chess variant to play, e.g. chess960, atomic or crazyhouse; defaults to standard chess",
+                    ))
+                    .arg(Arg::new("fen").long("fen").help(
+                        "The position to start from, in FEN; defaults to the standard starting position",
                     )),
             )
             .subcommand(
@@ -41,15 +46,335 @@ async fn main() {
                     .about("Returns FEN representation of the associated game if it exists")
                     .arg(Arg::new("game-id").help("The game ID of the chess game")),
             )
+            .subcommand(
+                Command::new("end-game")
+                    .about("Aborts a game before any move, or ends any game as the arbiter")
+                    .arg(
+                        Arg::new("game-id")
+                            .required(true)
+                            .help("The game ID of the chess game"),
+                    ),
+            )
+            .subcommand(
+                Command::new("get-receipt")
+                    .about("Returns whether an accepted transaction succeeded")
+                    .arg(
+                        Arg::new("tx-id")
+                            .required(true)
+                            .help("The transaction ID returned when submitting it"),
+                    ),
+            )
+            .subcommand(
+                Command::new("export-pgn")
+                    .about("Exports a game, or every game of a player, in PGN")
+                    .arg(Arg::new("game-id").help("The game ID of the chess game"))
+                    .arg(
+                        Arg::new("player")
+                            .long("player")
+                            .conflicts_with("game-id")
+                            .help("Exports every game of this address instead"),
+                    ),
+            )
+            .subcommand(
+                Command::new("sign-import")
+                    .about("Signs the import of a game played outside of ChessVM")
+                    .arg(
+                        Arg::new("file")
+                            .required(true)
+                            .help("The PGN file holding the game's movetext"),
+                    )
+                    .arg(
+                        Arg::new("white")
+                            .long("white")
+                            .required(true)
+                            .help("The address of the white player"),
+                    )
+                    .arg(
+                        Arg::new("black")
+                            .long("black")
+                            .required(true)
+                            .help("The address of the black player"),
+                    )
+                    .arg(Arg::new("variant").long("variant").help(
+                        "The chess variant that was played; defaults to standard chess",
+                    ))
+                    .arg(Arg::new("fen").long("fen").help(
+                        "The position the game started from, in FEN; defaults to the standard starting position",
+                    ))
+                    .arg(
+                        Arg::new("private-key")
+                            .long("private-key")
+                            .required(true)
+                            .help("The hex encoded private key of either player"),
+                    ),
+            )
+            .subcommand(
+                Command::new("import-pgn")
+                    .about("Records a game played outside of ChessVM, signed by both players")
+                    .arg(
+                        Arg::new("file")
+                            .required(true)
+                            .help("The PGN file holding the game's movetext"),
+                    )
+                    .arg(
+                        Arg::new("white")
+                            .long("white")
+                            .required(true)
+                            .help("The address of the white player"),
+                    )
+                    .arg(
+                        Arg::new("black")
+                            .long("black")
+                            .required(true)
+                            .help("The address of the black player"),
+                    )
+                    .arg(Arg::new("variant").long("variant").help(
+                        "The chess variant that was played; defaults to standard chess",
+                    ))
+                    .arg(Arg::new("fen").long("fen").help(
+                        "The position the game started from, in FEN; defaults to the standard starting position",
+                    ))
+                    .arg(
+                        Arg::new("white-signature")
+                            .long("white-signature")
+                            .required(true)
+                            .help("The white player's signature, from sign-import"),
+                    )
+                    .arg(
+                        Arg::new("black-signature")
+                            .long("black-signature")
+                            .required(true)
+                            .help("The black player's signature, from sign-import"),
+                    ),
+            )
+            .subcommand(
+                Command::new("rating")
+                    .about("Returns the ratings of a player in each variant and time control category")
+                    .arg(
+                        Arg::new("player")
+                            .required(true)
+                            .help("The address of the player"),
+                    )
+                    .arg(Arg::new("variant").long("variant").help(
+                        "Only shows ratings in this chess variant",
+                    ))
+                    .arg(Arg::new("category").long("category").help(
+                        "Only shows ratings in this time control category: bullet, blitz, rapid, classical or correspondence",
+                    )),
+            )
+            .subcommand(
+                Command::new("leaderboard")
+                    .about("Returns the best rated players of a variant and time control category")
+                    .arg(Arg::new("category").required(true).help(
+                        "The time control category: bullet, blitz, rapid, classical or correspondence",
+                    ))
+                    .arg(Arg::new("variant").long("variant").help(
+                        "The chess variant; defaults to standard chess",
+                    ))
+                    .arg(
+                        Arg::new("offset")
+                            .long("offset")
+                            .help("Skips this many players; defaults to 0"),
+                    )
+                    .arg(
+                        Arg::new("limit")
+                            .long("limit")
+                            .help("The number of players to show; at most 100"),
+                    ),
+            )
+            .subcommand(
+                Command::new("player-stats")
+                    .about("Returns the record, streak and head-to-head results of a player")
+                    .arg(
+                        Arg::new("player")
+                            .required(true)
+                            .help("The address of the player"),
+                    ),
+            )
+            .subcommand(
+                Command::new("balance")
+                    .about("Returns the balance of an account")
+                    .arg(
+                        Arg::new("address")
+                            .required(true)
+                            .help("The address of the account"),
+                    ),
+            )
+            .subcommand(
+                Command::new("transfer")
+                    .about("Moves funds from your account to another")
+                    .arg(
+                        Arg::new("to")
+                            .required(true)
+                            .help("The address of the account receiving the funds"),
+                    )
+                    .arg(
+                        Arg::new("amount")
+                            .required(true)
+                            .help("The amount to transfer"),
+                    )
+                    .arg(
+                        Arg::new("tip")
+                            .long("tip")
+                            .help("Paid on top of the fee for the transfer to be included sooner"),
+                    ),
+            )
+            .subcommand(
+                Command::new("post-puzzle")
+                    .about("Offers a reward for the solution of a position")
+                    .arg(
+                        Arg::new("fen")
+                            .required(true)
+                            .help("The position to solve, in FEN"),
+                    )
+                    .arg(
+                        Arg::new("reward")
+                            .required(true)
+                            .help("The amount paid to the first solver"),
+                    )
+                    .arg(
+                        Arg::new("deadline")
+                            .required(true)
+                            .help("The block timestamp after which no solution can be committed"),
+                    )
+                    .arg(
+                        Arg::new("moves")
+                            .required(true)
+                            .num_args(1..)
+                            .help("The moves of the solution in UCI notation; only their hash is sent"),
+                    ),
+            )
+            .subcommand(
+                Command::new("commit-solution")
+                    .about("Commits to the solution of a puzzle without revealing it")
+                    .arg(Arg::new("puzzle-id").required(true).help("The ID of the puzzle"))
+                    .arg(
+                        Arg::new("salt")
+                            .required(true)
+                            .help("A secret kept until the solution is revealed"),
+                    )
+                    .arg(
+                        Arg::new("moves")
+                            .required(true)
+                            .num_args(1..)
+                            .help("The moves of the solution in UCI notation; only their hash is sent"),
+                    ),
+            )
+            .subcommand(
+                Command::new("reveal-solution")
+                    .about("Reveals the solution committed to")
+                    .arg(Arg::new("puzzle-id").required(true).help("The ID of the puzzle"))
+                    .arg(
+                        Arg::new("salt")
+                            .required(true)
+                            .help("The secret used when committing"),
+                    )
+                    .arg(
+                        Arg::new("moves")
+                            .required(true)
+                            .num_args(1..)
+                            .help("The moves of the solution in UCI notation"),
+                    ),
+            )
+            .subcommand(
+                Command::new("get-puzzle")
+                    .about("Returns a puzzle, its commitments and its winner")
+                    .arg(Arg::new("puzzle-id").required(true).help("The ID of the puzzle")),
+            )
+            .subcommand(
+                Command::new("create-simul")
+                    .about("Opens boards against you, playing white on each")
+                    .arg(
+                        Arg::new("boards")
+                            .required(true)
+                            .help("The maximum number of opponents"),
+                    ),
+            )
+            .subcommand(
+                Command::new("register-for-simul")
+                    .about("Takes a board of a simul")
+                    .arg(Arg::new("simul-id").required(true).help("The ID of the simul")),
+            )
+            .subcommand(
+                Command::new("withdraw-from-simul")
+                    .about("Gives a board of a simul back before it starts")
+                    .arg(Arg::new("simul-id").required(true).help("The ID of the simul")),
+            )
+            .subcommand(
+                Command::new("start-simul")
+                    .about("Closes registration and creates the games of your simul")
+                    .arg(Arg::new("simul-id").required(true).help("The ID of the simul")),
+            )
+            .subcommand(
+                Command::new("get-simul")
+                    .about("Returns the boards of a simul, whose turn it is on each and the score")
+                    .arg(Arg::new("simul-id").required(true).help("The ID of the simul")),
+            )
+            .subcommand(
+                Command::new("create-team")
+                    .about("Creates a team, managed by you, voting together in consultation games"),
+            )
+            .subcommand(
+                Command::new("add-team-member")
+                    .about("Adds a member to a team you own")
+                    .arg(Arg::new("team-id").required(true).help("The ID of the team"))
+                    .arg(Arg::new("member").required(true).help("The address of the new member")),
+            )
+            .subcommand(
+                Command::new("remove-team-member")
+                    .about("Removes a member from a team you own, or leaves a team")
+                    .arg(Arg::new("team-id").required(true).help("The ID of the team"))
+                    .arg(Arg::new("member").required(true).help("The address of the member")),
+            )
+            .subcommand(
+                Command::new("get-team")
+                    .about("Returns the owner and members of a team")
+                    .arg(Arg::new("team-id").required(true).help("The ID of the team")),
+            )
+            .subcommand(
+                Command::new("create-consultation-game")
+                    .about("Starts a game between two teams voting on their moves, one of them yours")
+                    .arg(Arg::new("white-team").required(true).help("The ID of the team playing white"))
+                    .arg(Arg::new("black-team").required(true).help("The ID of the team playing black"))
+                    .arg(
+                        Arg::new("voting-window")
+                            .required(true)
+                            .help("The seconds each side votes before its most voted move is played"),
+                    ),
+            )
+            .subcommand(
+                Command::new("vote-move")
+                    .about("Votes for the next move of your team in a consultation game")
+                    .arg(Arg::new("game-id").required(true).help("The ID of the game"))
+                    .arg(Arg::new("uci").required(true).help("The move in UCI notation, e.g. e2e4")),
+            )
+            .subcommand(
+                Command::new("estimate-fee")
+                    .about("Returns the fee of an action and the highest tip waiting in the mempool")
+                    .arg(
+                        Arg::new("action")
+                            .required(true)
+                            .help("The name of the action, e.g. MakeMove or Transfer"),
+                    ),
+            )
             .subcommand(
                 Command::new("make-move")
                     .about("Creates a transaction for the move")
-                    .subcommand(Command::new("normal").about(
-                        "A regular chess move which is neither an En Passant nor Castling move",
+                    .subcommand(Command::new("uci").about("A move in UCI notation, e.g. e2e4 or e7e8q")
+                        .arg(
+                            Arg::new("game-id").help("The ID of the game to perform the move on").required(true)
+                        )
+                        .arg(Arg::new("move").help("The move in UCI notation").required(true))
                     )
+                    .subcommand(Command::new("san").about("A move in Standard Algebraic Notation, e.g. Nxf3+ or O-O")
                         .arg(
-                            Arg::new("player-address").help("The Ethereum address of the player making the move").required(true)
+                            Arg::new("game-id").help("The ID of the game to perform the move on").required(true)
                         )
+                        .arg(Arg::new("move").help("The move in SAN").required(true))
+                    )
+                    .subcommand(Command::new("normal").about(
+                        "A regular chess move which is neither an En Passant nor Castling move",
+                    )
                         .arg(
                             Arg::new("game-id").help("The ID of the game to perform the move on").required(true)
                         )
@@ -60,9 +385,6 @@ async fn main() {
                         .arg(Arg::new("promotion-piece").help("The piece you want your pawn to promote to; in FEN notation"))
                     )
                     .subcommand(Command::new("en-passant").about("The En Passant chess move")
-                        .arg(
-                            Arg::new("player-address").help("The Ethereum address of the player making the move").required(true)
-                        )
                         .arg(
                             Arg::new("game-id").help("The ID of the game to perform the move on").required(true)
                         )
@@ -71,9 +393,6 @@ async fn main() {
                     .subcommand(
                         Command::new("castle")
                             .about("The castling move")
-                            .arg(
-                                Arg::new("player-address").help("The Ethereum address of the player making the move").required(true)
-                            )
                             .arg(
                                 Arg::new("game-id").help("The ID of the game to perform the move on").required(true)
                             )
@@ -91,6 +410,9 @@ async fn main() {
             )
             .arg(Arg::new("http-rpc").short('h').required(!is_http_rpc_set))
             .arg(Arg::new("url-path").short('u').required(!is_url_path_set))
+            .arg(Arg::new("private-key").short('k').help(
+                "The hex encoded private key signing the transactions you send; defaults to PRIVATE_KEY",
+            ))
             .get_matches();
 
     let http_rpc: String;
@@ -108,16 +430,91 @@ async fn main() {
         url_path = env::var("URL_PATH").unwrap();
     }
 
+    // Only commands sending a transaction need the key
+    let key = || {
+        let v = match matches.get_one::<String>("private-key") {
+            Some(v) => v.clone(),
+            None => env::var("PRIVATE_KEY").unwrap(),
+        };
+        private_key::Key::from_hex(v).unwrap()
+    };
+
     match matches.subcommand() {
         Some(("ping", _)) => execute_ping(&http_rpc, &url_path).await,
         Some(("does-game-exist", sub_args)) => {
             execute_does_game_exist(&http_rpc, &url_path, sub_args).await
         }
         Some(("create-game", sub_args)) => {
-            execute_create_game(&http_rpc, &url_path, sub_args).await
+            execute_create_game(&http_rpc, &url_path, &key(), sub_args).await
         }
         Some(("get-game", sub_args)) => execute_get_game(&http_rpc, &url_path, sub_args).await,
-        Some(("make-move", sub_args)) => execute_make_move(&http_rpc, &url_path, sub_args).await,
+        Some(("make-move", sub_args)) => {
+            execute_make_move(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("end-game", sub_args)) => {
+            execute_end_game(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("get-receipt", sub_args)) => {
+            execute_get_receipt(&http_rpc, &url_path, sub_args).await
+        }
+        Some(("export-pgn", sub_args)) => execute_export_pgn(&http_rpc, &url_path, sub_args).await,
+        Some(("sign-import", sub_args)) => {
+            execute_sign_import(&http_rpc, &url_path, sub_args).await
+        }
+        Some(("import-pgn", sub_args)) => {
+            execute_import_pgn(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("rating", sub_args)) => execute_rating(&http_rpc, &url_path, sub_args).await,
+        Some(("leaderboard", sub_args)) => {
+            execute_leaderboard(&http_rpc, &url_path, sub_args).await
+        }
+        Some(("player-stats", sub_args)) => {
+            execute_player_stats(&http_rpc, &url_path, sub_args).await
+        }
+        Some(("balance", sub_args)) => execute_balance(&http_rpc, &url_path, sub_args).await,
+        Some(("transfer", sub_args)) => {
+            execute_transfer(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("post-puzzle", sub_args)) => {
+            execute_post_puzzle(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("commit-solution", sub_args)) => {
+            execute_commit_solution(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("reveal-solution", sub_args)) => {
+            execute_reveal_solution(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("get-puzzle", sub_args)) => execute_get_puzzle(&http_rpc, &url_path, sub_args).await,
+        Some(("create-simul", sub_args)) => {
+            execute_create_simul(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("register-for-simul", sub_args)) => {
+            execute_simul_action(&http_rpc, &url_path, &key(), sub_args, "registerForSimul").await
+        }
+        Some(("withdraw-from-simul", sub_args)) => {
+            execute_simul_action(&http_rpc, &url_path, &key(), sub_args, "withdrawFromSimul").await
+        }
+        Some(("start-simul", sub_args)) => {
+            execute_simul_action(&http_rpc, &url_path, &key(), sub_args, "startSimul").await
+        }
+        Some(("get-simul", sub_args)) => execute_get_simul(&http_rpc, &url_path, sub_args).await,
+        Some(("create-team", _)) => execute_create_team(&http_rpc, &url_path, &key()).await,
+        Some(("add-team-member", sub_args)) => {
+            execute_update_team(&http_rpc, &url_path, &key(), sub_args, true).await
+        }
+        Some(("remove-team-member", sub_args)) => {
+            execute_update_team(&http_rpc, &url_path, &key(), sub_args, false).await
+        }
+        Some(("get-team", sub_args)) => execute_get_team(&http_rpc, &url_path, sub_args).await,
+        Some(("create-consultation-game", sub_args)) => {
+            execute_create_consultation_game(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("vote-move", sub_args)) => {
+            execute_vote_move(&http_rpc, &url_path, &key(), sub_args).await
+        }
+        Some(("estimate-fee", sub_args)) => {
+            execute_estimate_fee(&http_rpc, &url_path, sub_args).await
+        }
         _ => panic!("Unknown subcommand!"),
     };
 }
@@ -145,18 +542,28 @@ async fn execute_does_game_exist(http_rpc: &str, url_path: &str, sub_args: &ArgM
 
     println!("Calling exist failed!");
 }
-async fn execute_create_game(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+async fn execute_create_game(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
     // Parse out arguments
-    let white = sub_args.get_one::<String>("white").unwrap().as_str();
-    let white_addr = Address::from_str(white).unwrap();
     let black = sub_args.get_one::<String>("black").unwrap().as_str();
-    let black_addr = Address::from_str(black).unwrap();
+    let black_addr = client::parse_address(http_rpc, url_path, black)
+        .await
+        .unwrap();
+    let variant = match sub_args.get_one::<String>("variant") {
+        Some(v) => GameVariant::from_str(v).unwrap(),
+        None => GameVariant::Standard,
+    };
+    let fen = sub_args.get_one::<String>("fen").cloned();
 
-    if let Ok(resp) = client::create_game(http_rpc, url_path, white_addr, black_addr).await {
-        println!(
-            "Created Chess Game with ID: {}",
-            resp.result.unwrap().game_id
-        );
+    if let Ok(resp) = client::create_game(http_rpc, url_path, key, black_addr, variant, fen).await {
+        match resp.result {
+            Some(v) => println!("Created Chess Game with ID: {}", v.game_id),
+            None => println!("Failed to create game: {}", resp.error.unwrap().message),
+        }
         return;
     }
 
@@ -175,14 +582,762 @@ async fn execute_get_game(http_rpc: &str, url_path: &str, sub_args: &ArgMatches)
         //     "Current game board is the following: {}",
         //     resp.result.unwrap().game
         // );
+        let game = resp.result.unwrap();
+        println!("Variant: {}", game.variant);
+        if let Some(outcome) = game.outcome {
+            println!(
+                "Result: {:?} ({:?}) after {} half-moves",
+                outcome.result, outcome.termination, game.move_count
+            );
+        }
+        if game.stake > 0 {
+            match game.escrow {
+                Some(_) => println!("Each player has {} locked on this game", game.stake),
+                None => println!("Each player wagered {} on this game", game.stake),
+            }
+        }
+        if let Some(simul_id) = game.simul {
+            println!("Board of simul {simul_id}");
+        }
+        if let Some(consultation) = game.consultation {
+            println!(
+                "Consultation game between teams {} and {}",
+                consultation.white_team, consultation.black_team
+            );
+            if let Some(deadline) = game.voting_deadline {
+                println!("Voting until {deadline}");
+            }
+            for (mv, votes) in game.votes {
+                println!("{mv}: {votes} vote(s)");
+            }
+        }
+        if game.archived {
+            println!("This game has ended and is archived");
+        }
         println!("Current game board is the following: ");
-        print_chess_board_from_fen(&resp.result.unwrap().game);
+        print_chess_board_from_fen(&game.game);
         return;
     }
 
     println!("Failed to call get_game!");
 }
 
+async fn execute_end_game(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let game_id = sub_args
+        .get_one::<String>("game-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+
+    if let Ok(resp) = client::end_game(http_rpc, url_path, key, game_id).await {
+        match resp.result {
+            Some(v) => println!("Submitted end game transaction with ID: {}", v.tx_id),
+            None => println!("Failed to end game: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call end_game!");
+}
+
+async fn execute_get_receipt(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let tx_id = sub_args.get_one::<String>("tx-id").unwrap();
+    let tx_id = ids::Id::from_str(tx_id).unwrap();
+
+    if let Ok(resp) = client::get_receipt(http_rpc, url_path, tx_id).await {
+        match resp.result {
+            Some(v) => match v.receipt.error {
+                Some(error) => println!(
+                    "Transaction failed in block {}: {error}",
+                    v.receipt.block_height
+                ),
+                None => println!("Transaction succeeded in block {}", v.receipt.block_height),
+            },
+            None => println!("Failed to get receipt: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_receipt!");
+}
+
+async fn execute_export_pgn(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let game_id = sub_args
+        .get_one::<String>("game-id")
+        .map(|id| id.parse::<u64>().unwrap());
+    let player = match sub_args.get_one::<String>("player") {
+        Some(p) => Some(client::parse_address(http_rpc, url_path, p).await.unwrap()),
+        None => None,
+    };
+
+    if let Ok(resp) = client::get_game_pgn(http_rpc, url_path, game_id, player).await {
+        match resp.result {
+            Some(v) => print!("{}", v.pgn),
+            None => println!("Failed to export PGN: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_game_pgn!");
+}
+
+/// Reads the arguments shared by `sign-import` and `import-pgn`
+async fn parse_import_args(
+    http_rpc: &str,
+    url_path: &str,
+    sub_args: &ArgMatches,
+) -> ImportGameArgs {
+    let file = sub_args.get_one::<String>("file").unwrap();
+    let white = sub_args.get_one::<String>("white").unwrap();
+    let black = sub_args.get_one::<String>("black").unwrap();
+    let variant = match sub_args.get_one::<String>("variant") {
+        Some(v) => GameVariant::from_str(v).unwrap(),
+        None => GameVariant::Standard,
+    };
+
+    ImportGameArgs {
+        white: client::parse_address(http_rpc, url_path, white)
+            .await
+            .unwrap(),
+        black: client::parse_address(http_rpc, url_path, black)
+            .await
+            .unwrap(),
+        variant,
+        fen: sub_args.get_one::<String>("fen").cloned(),
+        movetext: fs::read_to_string(file).unwrap(),
+        white_signature: sub_args
+            .get_one::<String>("white-signature")
+            .cloned()
+            .unwrap_or_default(),
+        black_signature: sub_args
+            .get_one::<String>("black-signature")
+            .cloned()
+            .unwrap_or_default(),
+        // Filled in when the import is sent
+        auth: TxAuth::default(),
+    }
+}
+
+async fn execute_sign_import(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    let args = parse_import_args(http_rpc, url_path, sub_args).await;
+    let key =
+        private_key::Key::from_hex(sub_args.get_one::<String>("private-key").unwrap()).unwrap();
+
+    let message = pgn::import_message(
+        args.white,
+        args.black,
+        args.variant,
+        args.fen.as_deref(),
+        &args.movetext,
+    );
+    match pgn::sign_message(&key, &message) {
+        Ok(signature) => println!("{signature}"),
+        Err(e) => println!("Failed to sign import: {e}"),
+    }
+}
+
+async fn execute_import_pgn(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    let args = parse_import_args(http_rpc, url_path, sub_args).await;
+
+    if let Ok(resp) = client::import_game(http_rpc, url_path, key, &args).await {
+        match resp.result {
+            Some(v) => println!("Imported Chess Game with ID: {}", v.game_id),
+            None => println!("Failed to import game: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Calling import_game failed!");
+}
+
+async fn execute_rating(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let player = sub_args.get_one::<String>("player").unwrap();
+    let player = client::parse_address(http_rpc, url_path, player)
+        .await
+        .unwrap();
+    let variant = sub_args
+        .get_one::<String>("variant")
+        .map(|v| GameVariant::from_str(v).unwrap());
+    let category = sub_args
+        .get_one::<String>("category")
+        .map(|c| TimeCategory::from_str(c).unwrap());
+
+    if let Ok(resp) = client::get_rating(http_rpc, url_path, player, variant, category).await {
+        match resp.result {
+            Some(v) if v.ratings.is_empty() => println!("{player} has not played rated games"),
+            Some(v) => {
+                for r in v.ratings {
+                    println!(
+                        "{} {}: {} ({} games)",
+                        r.variant, r.category, r.rating, r.games
+                    );
+                }
+            }
+            None => println!("Failed to get rating: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_rating!");
+}
+
+async fn execute_leaderboard(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let category = sub_args.get_one::<String>("category").unwrap();
+    let category = TimeCategory::from_str(category).unwrap();
+    let variant = match sub_args.get_one::<String>("variant") {
+        Some(v) => GameVariant::from_str(v).unwrap(),
+        None => GameVariant::Standard,
+    };
+    let offset = sub_args
+        .get_one::<String>("offset")
+        .map_or(0, |o| o.parse::<usize>().unwrap());
+    let limit = sub_args
+        .get_one::<String>("limit")
+        .map(|l| l.parse::<usize>().unwrap());
+
+    if let Ok(resp) =
+        client::get_leaderboard(http_rpc, url_path, variant, category, offset, limit).await
+    {
+        match resp.result {
+            Some(v) => {
+                println!("{variant} {category} leaderboard ({} players)", v.total);
+                for e in v.entries {
+                    println!(
+                        "{:>4}. {} {} ({} games)",
+                        e.rank, e.player, e.rating, e.games
+                    );
+                }
+            }
+            None => println!("Failed to get leaderboard: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_leaderboard!");
+}
+
+async fn execute_player_stats(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let player = sub_args.get_one::<String>("player").unwrap();
+    let player = client::parse_address(http_rpc, url_path, player)
+        .await
+        .unwrap();
+
+    if let Ok(resp) = client::get_player_stats(http_rpc, url_path, player).await {
+        match resp.result {
+            Some(v) => {
+                let record = v.stats.record;
+                println!(
+                    "Played {}: {} won, {} lost, {} drawn",
+                    record.played, record.won, record.lost, record.drawn
+                );
+                if let Some(streak) = v.stats.streak {
+                    println!("Current streak: {} x {:?}", streak.length, streak.score);
+                }
+                if let Some(average) = v.average_length {
+                    println!("Average game length: {average:.1} half-moves");
+                }
+                for (opponent, r) in v.stats.head_to_head {
+                    println!(
+                        "Against {opponent}: {} won, {} lost, {} drawn",
+                        r.won, r.lost, r.drawn
+                    );
+                }
+            }
+            None => println!(
+                "Failed to get player stats: {}",
+                resp.error.unwrap().message
+            ),
+        }
+        return;
+    }
+
+    println!("Failed to call get_player_stats!");
+}
+
+async fn execute_balance(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let address = sub_args.get_one::<String>("address").unwrap();
+    let address = client::parse_address(http_rpc, url_path, address)
+        .await
+        .unwrap();
+
+    if let Ok(resp) = client::get_balance(http_rpc, url_path, address).await {
+        match resp.result {
+            Some(v) => {
+                println!("Balance of {address}: {}", v.balance);
+                if v.locked > 0 {
+                    println!("Locked in wagered games: {}", v.locked);
+                }
+            }
+            None => println!("Failed to get balance: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_balance!");
+}
+
+async fn execute_transfer(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let to = sub_args.get_one::<String>("to").unwrap();
+    let to = client::parse_address(http_rpc, url_path, to).await.unwrap();
+    let amount = sub_args
+        .get_one::<String>("amount")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let tip = sub_args
+        .get_one::<String>("tip")
+        .map(|t| t.parse::<u64>().unwrap())
+        .unwrap_or_default();
+
+    if let Ok(resp) = client::transfer(http_rpc, url_path, key, to, amount, tip).await {
+        match resp.result {
+            Some(v) => println!("Submitted transfer transaction with ID: {}", v.tx_id),
+            None => println!("Failed to transfer: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call transfer!");
+}
+
+async fn execute_post_puzzle(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let fen = sub_args.get_one::<String>("fen").unwrap();
+    let reward = sub_args
+        .get_one::<String>("reward")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let deadline = sub_args
+        .get_one::<String>("deadline")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let moves: Vec<String> = sub_args
+        .get_many::<String>("moves")
+        .unwrap()
+        .cloned()
+        .collect();
+
+    // A solution that cannot be played would never be found
+    if let Err(e) = puzzle::check_line(fen, &moves) {
+        println!("Invalid solution: {e}");
+        return;
+    }
+
+    if let Ok(resp) = client::post_puzzle(
+        http_rpc,
+        url_path,
+        key,
+        fen,
+        puzzle::solution_hash(&moves),
+        reward,
+        deadline,
+    )
+    .await
+    {
+        match resp.result {
+            Some(v) => println!(
+                "Posted puzzle with ID: {} in transaction {}",
+                v.puzzle_id, v.tx_id
+            ),
+            None => println!("Failed to post puzzle: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call post_puzzle!");
+}
+
+/// Parses the arguments shared by `commit-solution` and `reveal-solution`
+fn solution_args(sub_args: &ArgMatches) -> (u64, String, Vec<String>) {
+    let puzzle_id = sub_args
+        .get_one::<String>("puzzle-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let salt = sub_args.get_one::<String>("salt").unwrap().clone();
+    let moves = sub_args
+        .get_many::<String>("moves")
+        .unwrap()
+        .cloned()
+        .collect();
+
+    (puzzle_id, salt, moves)
+}
+
+async fn execute_commit_solution(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let (puzzle_id, salt, moves) = solution_args(sub_args);
+
+    if let Ok(resp) =
+        client::commit_solution(http_rpc, url_path, key, puzzle_id, &moves, &salt).await
+    {
+        match resp.result {
+            Some(v) => println!("Submitted commitment transaction with ID: {}", v.tx_id),
+            None => println!("Failed to commit solution: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call commit_solution!");
+}
+
+async fn execute_reveal_solution(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let (puzzle_id, salt, moves) = solution_args(sub_args);
+
+    if let Ok(resp) =
+        client::reveal_solution(http_rpc, url_path, key, puzzle_id, &moves, &salt).await
+    {
+        match resp.result {
+            Some(v) => println!("Submitted reveal transaction with ID: {}", v.tx_id),
+            None => println!("Failed to reveal solution: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call reveal_solution!");
+}
+
+async fn execute_get_puzzle(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let puzzle_id = sub_args
+        .get_one::<String>("puzzle-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+
+    if let Ok(resp) = client::get_puzzle(http_rpc, url_path, puzzle_id).await {
+        match resp.result {
+            Some(v) => {
+                let puzzle = v.puzzle;
+                println!("Position: {}", puzzle.fen);
+                println!("Reward: {}", puzzle.reward);
+                println!(
+                    "Commitments until {}, reveals until {}",
+                    puzzle.deadline, v.reveal_deadline
+                );
+                for (i, c) in puzzle.commitments.iter().enumerate() {
+                    let status = if c.solved { "solved" } else { "not revealed" };
+                    println!("{}. {} ({status})", i + 1, c.solver);
+                }
+                match (puzzle.closed, puzzle.winner) {
+                    (true, Some(winner)) => println!("Solved by {winner}"),
+                    (true, None) => println!("Closed unsolved, the reward was refunded"),
+                    (false, _) => println!("Open"),
+                }
+            }
+            None => println!("Failed to get puzzle: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_puzzle!");
+}
+
+async fn execute_create_simul(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let boards = sub_args
+        .get_one::<String>("boards")
+        .unwrap()
+        .parse::<usize>()
+        .unwrap();
+
+    if let Ok(resp) = client::create_simul(http_rpc, url_path, key, boards).await {
+        match resp.result {
+            Some(v) => println!(
+                "Created simul with ID: {} in transaction {}",
+                v.simul_id, v.tx_id
+            ),
+            None => println!("Failed to create simul: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call create_simul!");
+}
+
+/// Runs `register-for-simul`, `withdraw-from-simul` or `start-simul`
+async fn execute_simul_action(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+    method: &str,
+) {
+    // Parse out arguments
+    let simul_id = sub_args
+        .get_one::<String>("simul-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+
+    if let Ok(resp) = client::simul_action(http_rpc, url_path, method, key, simul_id).await {
+        match resp.result {
+            Some(v) => println!("Submitted simul transaction with ID: {}", v.tx_id),
+            None => println!("Failed to call {method}: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call {method}!");
+}
+
+async fn execute_get_simul(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let simul_id = sub_args
+        .get_one::<String>("simul-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+
+    if let Ok(resp) = client::get_simul(http_rpc, url_path, simul_id).await {
+        match resp.result {
+            Some(v) => {
+                println!("Host: {}", v.simul.host);
+                if !v.simul.started() {
+                    println!(
+                        "Registration open, {} of {} boards taken:",
+                        v.simul.players.len(),
+                        v.simul.boards
+                    );
+                    for player in v.simul.players {
+                        println!("- {player}");
+                    }
+                    return;
+                }
+                for board in v.boards {
+                    let status = match (board.outcome, board.to_move) {
+                        (Some(outcome), _) if outcome.termination.is_void() => {
+                            format!("{:?}", outcome.termination)
+                        }
+                        (Some(outcome), _) => format!("{:?}", outcome.result),
+                        (None, Some(player)) if player == v.simul.host => {
+                            "host to move".to_string()
+                        }
+                        (None, _) => "opponent to move".to_string(),
+                    };
+                    println!(
+                        "{}. {} (game {}, {} half-moves): {status}",
+                        board.board, board.opponent, board.game_id, board.move_count
+                    );
+                }
+                let score = v.score;
+                println!(
+                    "Score: host {} - {} opponents, {} board(s) in progress, {} void",
+                    score.host_points, score.opponent_points, score.ongoing, score.void
+                );
+            }
+            None => println!("Failed to get simul: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_simul!");
+}
+
+async fn execute_create_team(http_rpc: &str, url_path: &str, key: &private_key::Key) {
+    if let Ok(resp) = client::create_team(http_rpc, url_path, key).await {
+        match resp.result {
+            Some(v) => println!(
+                "Created team with ID: {} in transaction {}",
+                v.team_id, v.tx_id
+            ),
+            None => println!("Failed to create team: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call create_team!");
+}
+
+/// Runs `add-team-member`, or `remove-team-member` if `add` is false
+async fn execute_update_team(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+    add: bool,
+) {
+    // Parse out arguments
+    let team_id = sub_args
+        .get_one::<String>("team-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let member = sub_args.get_one::<String>("member").unwrap();
+    let member = client::parse_address(http_rpc, url_path, member)
+        .await
+        .unwrap();
+
+    if let Ok(resp) = client::update_team(http_rpc, url_path, key, team_id, member, add).await {
+        match resp.result {
+            Some(v) => println!("Submitted team transaction with ID: {}", v.tx_id),
+            None => println!("Failed to update team: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call update_team!");
+}
+
+async fn execute_get_team(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let team_id = sub_args
+        .get_one::<String>("team-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+
+    if let Ok(resp) = client::get_team(http_rpc, url_path, team_id).await {
+        match resp.result {
+            Some(v) => {
+                println!("Owner: {}", v.team.owner);
+                for member in v.team.members {
+                    println!("- {member}");
+                }
+            }
+            None => println!("Failed to get team: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call get_team!");
+}
+
+async fn execute_create_consultation_game(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let [white_team, black_team, voting_window] = ["white-team", "black-team", "voting-window"]
+        .map(|arg| {
+            sub_args
+                .get_one::<String>(arg)
+                .unwrap()
+                .parse::<u64>()
+                .unwrap()
+        });
+
+    if let Ok(resp) = client::create_consultation_game(
+        http_rpc,
+        url_path,
+        key,
+        white_team,
+        black_team,
+        voting_window,
+    )
+    .await
+    {
+        match resp.result {
+            Some(v) => println!(
+                "Created consultation game with ID: {} in transaction {}",
+                v.game_id, v.tx_id
+            ),
+            None => println!(
+                "Failed to create consultation game: {}",
+                resp.error.unwrap().message
+            ),
+        }
+        return;
+    }
+
+    println!("Failed to call create_consultation_game!");
+}
+
+async fn execute_vote_move(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    // Parse out arguments
+    let game_id = sub_args
+        .get_one::<String>("game-id")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let uci = sub_args.get_one::<String>("uci").unwrap();
+
+    if let Ok(resp) = client::vote_move(http_rpc, url_path, key, game_id, uci).await {
+        match resp.result {
+            Some(v) => println!("Submitted vote transaction with ID: {}", v.tx_id),
+            None => println!("Failed to vote: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call vote_move!");
+}
+
+async fn execute_estimate_fee(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    // Parse out arguments
+    let action = sub_args.get_one::<String>("action").unwrap();
+
+    if let Ok(resp) = client::estimate_fee(http_rpc, url_path, action).await {
+        match resp.result {
+            Some(v) => {
+                println!("Fee of {action}: {}", v.fee);
+                println!("Highest tip waiting in the mempool: {}", v.pending_tip);
+                match v.treasury {
+                    Some(treasury) => println!("Fees are collected by {treasury}"),
+                    None => println!("Fees are burned"),
+                }
+            }
+            None => println!("Failed to estimate fee: {}", resp.error.unwrap().message),
+        }
+        return;
+    }
+
+    println!("Failed to call estimate_fee!");
+}
+
 fn print_chess_board_from_fen(fen: &str) {
     // Directly use the FEN string as the board layout
     let board_layout = fen;
@@ -214,16 +1369,19 @@ fn print_chess_board_from_fen(fen: &str) {
     println!("    a b c d e f g h  "); // Print file letters below
 }
 
-async fn execute_make_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
-    async fn execute_en_passant_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+async fn execute_make_move(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    sub_args: &ArgMatches,
+) {
+    async fn execute_en_passant_move(
+        http_rpc: &str,
+        url_path: &str,
+        key: &private_key::Key,
+        sub_args: &ArgMatches,
+    ) {
         // Extract args
-        let player = Address::from_str(
-            sub_args
-                .get_one::<String>("player-address")
-                .unwrap()
-                .as_str(),
-        )
-        .unwrap();
         let game_id = sub_args
             .get_one::<String>("game-id")
             .unwrap()
@@ -239,7 +1397,7 @@ async fn execute_make_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches
         if let Ok(resp) = client::make_move(
             http_rpc,
             url_path,
-            player,
+            key,
             game_id,
             MoveEnum::EnPassant {
                 from: from_square,
@@ -258,15 +1416,13 @@ async fn execute_make_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches
         println!("Failed to submit En Passant Transaction!");
     }
 
-    async fn execute_normal_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    async fn execute_normal_move(
+        http_rpc: &str,
+        url_path: &str,
+        key: &private_key::Key,
+        sub_args: &ArgMatches,
+    ) {
         // Extract args
-        let player = Address::from_str(
-            sub_args
-                .get_one::<String>("player-address")
-                .unwrap()
-                .as_str(),
-        )
-        .unwrap();
         let game_id = sub_args
             .get_one::<String>("game-id")
             .unwrap()
@@ -290,7 +1446,7 @@ async fn execute_make_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches
         if let Ok(resp) = client::make_move(
             http_rpc,
             url_path,
-            player,
+            key,
             game_id,
             MoveEnum::Normal {
                 role,
@@ -312,15 +1468,13 @@ async fn execute_make_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches
         println!("Failed to make normal move transaction!");
     }
 
-    async fn execute_castle_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches) {
+    async fn execute_castle_move(
+        http_rpc: &str,
+        url_path: &str,
+        key: &private_key::Key,
+        sub_args: &ArgMatches,
+    ) {
         // Extract args
-        let player = Address::from_str(
-            sub_args
-                .get_one::<String>("player-address")
-                .unwrap()
-                .as_str(),
-        )
-        .unwrap();
         let game_id = sub_args
             .get_one::<String>("game-id")
             .unwrap()
@@ -339,7 +1493,7 @@ async fn execute_make_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches
         if let Ok(resp) = client::make_move(
             http_rpc,
             url_path,
-            player,
+            key,
             game_id,
             MoveEnum::Castle {
                 king: king_square,
@@ -358,12 +1512,53 @@ async fn execute_make_move(http_rpc: &str, url_path: &str, sub_args: &ArgMatches
         println!("Failed to make Castling Transaction!");
     }
 
+    async fn execute_notation_move(
+        http_rpc: &str,
+        url_path: &str,
+        key: &private_key::Key,
+        sub_args: &ArgMatches,
+        notation: &str,
+    ) {
+        // Extract args
+        let game_id = sub_args
+            .get_one::<String>("game-id")
+            .unwrap()
+            .parse::<u64>()
+            .unwrap();
+        let mv = sub_args.get_one::<String>("move").unwrap().to_owned();
+        let mv = match notation {
+            "uci" => MoveEnum::Uci(mv),
+            _ => MoveEnum::San(mv),
+        };
+
+        // Make call
+        if let Ok(resp) = client::make_move(http_rpc, url_path, key, game_id, mv).await {
+            match resp.result {
+                Some(v) => println!("Move Transaction Submission Status: {}", v.status),
+                None => println!("Failed to make move: {}", resp.error.unwrap().message),
+            }
+            return;
+        }
+
+        println!("Failed to make move transaction!");
+    }
+
     match sub_args.subcommand() {
-        Some(("normal", ssub_args)) => execute_normal_move(http_rpc, url_path, ssub_args).await,
+        Some(("uci", ssub_args)) => {
+            execute_notation_move(http_rpc, url_path, key, ssub_args, "uci").await
+        }
+        Some(("san", ssub_args)) => {
+            execute_notation_move(http_rpc, url_path, key, ssub_args, "san").await
+        }
+        Some(("normal", ssub_args)) => {
+            execute_normal_move(http_rpc, url_path, key, ssub_args).await
+        }
         Some(("en-passant", ssub_args)) => {
-            execute_en_passant_move(http_rpc, url_path, ssub_args).await
+            execute_en_passant_move(http_rpc, url_path, key, ssub_args).await
+        }
+        Some(("castle", ssub_args)) => {
+            execute_castle_move(http_rpc, url_path, key, ssub_args).await
         }
-        Some(("castle", ssub_args)) => execute_castle_move(http_rpc, url_path, ssub_args).await,
         _ => panic!("not a valid move subcommand!"),
     }
 }
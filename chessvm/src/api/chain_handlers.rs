@@ -3,21 +3,40 @@
 
 use crate::{
     block::{
-        tx::{self, ActionType, Transaction, TransactionContext},
+        tx::{self, ActionType, Transaction},
         Block,
     },
-    state::calculate_game_id,
+    pgn,
+    state::{
+        calculate_challenge_id, calculate_consultation_game_id, calculate_game_id,
+        calculate_import_id, calculate_puzzle_id, calculate_seek_id, calculate_simul_id,
+        calculate_team_id, calculate_tournament_id,
+        clock::TimeControl,
+        ledger::Escrow,
+        puzzle::Puzzle,
+        rating::{RatingPool, TimeCategory},
+        simul::{self, Simul, SimulBoard, SimulScore},
+        stats::PlayerStats,
+        team::{Consultation, Team},
+        tournament::{BracketMatch, Standing, Tiebreak, TournamentFormat},
+        variant::{starting_position, GameVariant},
+        Challenge, ColorPreference, ConditionalMoves, GameOutcome, GameResult, MoveRecord, Pause,
+        RatingRange, Receipt, Rematch, Seek, TakebackRequest,
+    },
     vm::Vm,
 };
 use avalanche_types::{ids, proto::http::Element, subnet::rpc::http::handle::Handle};
 use bytes::Bytes;
+use chrono::Utc;
 use jsonrpc_core::{BoxFuture, Error, ErrorCode, IoHandler, Result};
 use jsonrpc_derive::rpc;
 use serde::{Deserialize, Serialize};
-use shakmaty::{Chess, Position};
-use std::{borrow::Borrow, fmt::Debug, io, marker::PhantomData, str::FromStr};
+use shakmaty::{san::SanPlus, uci::Uci};
+use std::{
+    borrow::Borrow, collections::BTreeMap, fmt::Debug, io, marker::PhantomData, str::FromStr,
+};
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 
 use super::de_request;
 
@@ -56,6 +75,277 @@ pub trait Rpc {
     /// Check if a game exists
     #[rpc(name = "exists", alias("chessvm.exists"))]
     fn exists(&self, args: ExistsArgs) -> BoxFuture<Result<ExistsResponse>>;
+
+    /// Get the moves played in a Chess game
+    #[rpc(name = "getGameHistory", alias("chessvm.getGameHistory"))]
+    fn get_game_history(
+        &self,
+        args: GetGameHistoryArgs,
+    ) -> BoxFuture<Result<GetGameHistoryResponse>>;
+
+    /// Export Chess games in PGN
+    #[rpc(name = "getGamePgn", alias("chessvm.getGamePgn"))]
+    fn get_game_pgn(&self, args: GetGamePgnArgs) -> BoxFuture<Result<GetGamePgnResponse>>;
+
+    /// Get the outcome of an accepted transaction
+    #[rpc(name = "getReceipt", alias("chessvm.getReceipt"))]
+    fn get_receipt(&self, args: GetReceiptArgs) -> BoxFuture<Result<GetReceiptResponse>>;
+
+    /// Records a game played outside of ChessVM
+    #[rpc(name = "importGame", alias("chessvm.importGame"))]
+    fn import_game(&self, args: ImportGameArgs) -> BoxFuture<Result<ImportGameResponse>>;
+
+    /// Offers a game to a specific opponent or to anyone
+    #[rpc(name = "createChallenge", alias("chessvm.createChallenge"))]
+    fn create_challenge(
+        &self,
+        args: CreateChallengeArgs,
+    ) -> BoxFuture<Result<CreateChallengeResponse>>;
+
+    /// Accepts a challenge, starting the game
+    #[rpc(name = "acceptChallenge", alias("chessvm.acceptChallenge"))]
+    fn accept_challenge(
+        &self,
+        args: AcceptChallengeArgs,
+    ) -> BoxFuture<Result<AcceptChallengeResponse>>;
+
+    /// Withdraws a challenge
+    #[rpc(name = "cancelChallenge", alias("chessvm.cancelChallenge"))]
+    fn cancel_challenge(
+        &self,
+        args: CancelChallengeArgs,
+    ) -> BoxFuture<Result<CancelChallengeResponse>>;
+
+    /// Lists challenges waiting for an opponent
+    #[rpc(name = "listOpenChallenges", alias("chessvm.listOpenChallenges"))]
+    fn list_open_challenges(
+        &self,
+        args: ListOpenChallengesArgs,
+    ) -> BoxFuture<Result<ListOpenChallengesResponse>>;
+
+    /// Asks the opponent to undo the last moves of a game
+    #[rpc(name = "requestTakeback", alias("chessvm.requestTakeback"))]
+    fn request_takeback(&self, args: RequestTakebackArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Accepts the opponent's takeback request
+    #[rpc(name = "acceptTakeback", alias("chessvm.acceptTakeback"))]
+    fn accept_takeback(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Asks the opponent to freeze the clocks of a game
+    #[rpc(name = "requestPause", alias("chessvm.requestPause"))]
+    fn request_pause(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Accepts the opponent's pause request
+    #[rpc(name = "acceptPause", alias("chessvm.acceptPause"))]
+    fn accept_pause(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Restarts the clocks of a paused game
+    #[rpc(name = "resume", alias("chessvm.resume"))]
+    fn resume(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Sets up replies to the opponent's next moves
+    #[rpc(name = "setConditionalMoves", alias("chessvm.setConditionalMoves"))]
+    fn set_conditional_moves(&self, args: SetConditionalMovesArgs)
+        -> BoxFuture<Result<TxResponse>>;
+
+    /// Offers to play a finished game again with colors swapped
+    #[rpc(name = "offerRematch", alias("chessvm.offerRematch"))]
+    fn offer_rematch(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Accepts the opponent's rematch offer
+    #[rpc(name = "acceptRematch", alias("chessvm.acceptRematch"))]
+    fn accept_rematch(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Get the ratings of a player
+    #[rpc(name = "getRating", alias("chessvm.getRating"))]
+    fn get_rating(&self, args: GetRatingArgs) -> BoxFuture<Result<GetRatingResponse>>;
+
+    /// Get the best rated players of a pool
+    #[rpc(name = "getLeaderboard", alias("chessvm.getLeaderboard"))]
+    fn get_leaderboard(
+        &self,
+        args: GetLeaderboardArgs,
+    ) -> BoxFuture<Result<GetLeaderboardResponse>>;
+
+    /// Get the statistics of a player over their finished games
+    #[rpc(name = "getPlayerStats", alias("chessvm.getPlayerStats"))]
+    fn get_player_stats(
+        &self,
+        args: GetPlayerStatsArgs,
+    ) -> BoxFuture<Result<GetPlayerStatsResponse>>;
+
+    /// Registers a human readable name for an address
+    #[rpc(name = "registerName", alias("chessvm.registerName"))]
+    fn register_name(&self, args: NameArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Gives a name to another address
+    #[rpc(name = "transferName", alias("chessvm.transferName"))]
+    fn transfer_name(&self, args: TransferNameArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Frees a name so that anyone can register it
+    #[rpc(name = "releaseName", alias("chessvm.releaseName"))]
+    fn release_name(&self, args: NameArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Get the address owning a name
+    #[rpc(name = "resolveName", alias("chessvm.resolveName"))]
+    fn resolve_name(&self, args: ResolveNameArgs) -> BoxFuture<Result<ResolveNameResponse>>;
+
+    /// Get the name owned by an address
+    #[rpc(name = "reverseResolve", alias("chessvm.reverseResolve"))]
+    fn reverse_resolve(
+        &self,
+        args: ReverseResolveArgs,
+    ) -> BoxFuture<Result<ReverseResolveResponse>>;
+
+    /// Creates a round-robin or Swiss tournament
+    #[rpc(name = "createTournament", alias("chessvm.createTournament"))]
+    fn create_tournament(
+        &self,
+        args: CreateTournamentArgs,
+    ) -> BoxFuture<Result<CreateTournamentResponse>>;
+
+    /// Registers a player for a tournament
+    #[rpc(name = "registerForTournament", alias("chessvm.registerForTournament"))]
+    fn register_for_tournament(&self, args: TournamentActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Withdraws a player from a tournament
+    #[rpc(
+        name = "withdrawFromTournament",
+        alias("chessvm.withdrawFromTournament")
+    )]
+    fn withdraw_from_tournament(&self, args: TournamentActionArgs)
+        -> BoxFuture<Result<TxResponse>>;
+
+    /// Pairs the next round of a tournament and creates its games
+    #[rpc(name = "startRound", alias("chessvm.startRound"))]
+    fn start_round(&self, args: TournamentActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Get a tournament and the pairings of its rounds
+    #[rpc(name = "getTournament", alias("chessvm.getTournament"))]
+    fn get_tournament(&self, args: GetTournamentArgs) -> BoxFuture<Result<GetTournamentResponse>>;
+
+    /// Get the standings of a tournament
+    #[rpc(name = "getStandings", alias("chessvm.getStandings"))]
+    fn get_standings(&self, args: GetTournamentArgs) -> BoxFuture<Result<GetStandingsResponse>>;
+
+    /// Get the bracket of a knockout
+    #[rpc(name = "getBracket", alias("chessvm.getBracket"))]
+    fn get_bracket(&self, args: GetTournamentArgs) -> BoxFuture<Result<GetBracketResponse>>;
+
+    /// Looks for any opponent with matching settings
+    #[rpc(name = "postSeek", alias("chessvm.postSeek"))]
+    fn post_seek(&self, args: PostSeekArgs) -> BoxFuture<Result<PostSeekResponse>>;
+
+    /// Withdraws a seek
+    #[rpc(name = "cancelSeek", alias("chessvm.cancelSeek"))]
+    fn cancel_seek(&self, args: CancelSeekArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Lists the seeks waiting for an opponent
+    #[rpc(name = "listSeeks", alias("chessvm.listSeeks"))]
+    fn list_seeks(&self) -> BoxFuture<Result<ListSeeksResponse>>;
+
+    /// Moves funds to another account
+    #[rpc(name = "transfer", alias("chessvm.transfer"))]
+    fn transfer(&self, args: TransferArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Get the balance of an account
+    #[rpc(name = "getBalance", alias("chessvm.getBalance"))]
+    fn get_balance(&self, args: GetBalanceArgs) -> BoxFuture<Result<GetBalanceResponse>>;
+
+    /// Get the fee of an action and the tips pending in the mempool
+    #[rpc(name = "estimateFee", alias("chessvm.estimateFee"))]
+    fn estimate_fee(&self, args: EstimateFeeArgs) -> BoxFuture<Result<EstimateFeeResponse>>;
+
+    /// Offers a reward for the solution of a position
+    #[rpc(name = "postPuzzle", alias("chessvm.postPuzzle"))]
+    fn post_puzzle(&self, args: PostPuzzleArgs) -> BoxFuture<Result<PostPuzzleResponse>>;
+
+    /// Commits to the solution of a puzzle without revealing it
+    #[rpc(name = "commitSolution", alias("chessvm.commitSolution"))]
+    fn commit_solution(&self, args: CommitSolutionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Reveals the solution committed to
+    #[rpc(name = "revealSolution", alias("chessvm.revealSolution"))]
+    fn reveal_solution(&self, args: RevealSolutionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Get a puzzle and its commitments
+    #[rpc(name = "getPuzzle", alias("chessvm.getPuzzle"))]
+    fn get_puzzle(&self, args: GetPuzzleArgs) -> BoxFuture<Result<GetPuzzleResponse>>;
+
+    /// Opens boards against a host for opponents to register
+    #[rpc(name = "createSimul", alias("chessvm.createSimul"))]
+    fn create_simul(&self, args: CreateSimulArgs) -> BoxFuture<Result<CreateSimulResponse>>;
+
+    /// Registers a player for a board of a simul
+    #[rpc(name = "registerForSimul", alias("chessvm.registerForSimul"))]
+    fn register_for_simul(&self, args: SimulActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Withdraws a player from a simul before it starts
+    #[rpc(name = "withdrawFromSimul", alias("chessvm.withdrawFromSimul"))]
+    fn withdraw_from_simul(&self, args: SimulActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Closes registration and creates the games of a simul
+    #[rpc(name = "startSimul", alias("chessvm.startSimul"))]
+    fn start_simul(&self, args: SimulActionArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Get a simul, its boards and the host's score
+    #[rpc(name = "getSimul", alias("chessvm.getSimul"))]
+    fn get_simul(&self, args: GetSimulArgs) -> BoxFuture<Result<GetSimulResponse>>;
+
+    /// Creates a team owned by the caller
+    #[rpc(name = "createTeam", alias("chessvm.createTeam"))]
+    fn create_team(&self, args: CreateTeamArgs) -> BoxFuture<Result<CreateTeamResponse>>;
+
+    /// Adds a member to a team
+    #[rpc(name = "addTeamMember", alias("chessvm.addTeamMember"))]
+    fn add_team_member(&self, args: TeamMemberArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Removes a member from a team, or leaves it
+    #[rpc(name = "removeTeamMember", alias("chessvm.removeTeamMember"))]
+    fn remove_team_member(&self, args: TeamMemberArgs) -> BoxFuture<Result<TxResponse>>;
+
+    /// Get the owner and members of a team
+    #[rpc(name = "getTeam", alias("chessvm.getTeam"))]
+    fn get_team(&self, args: GetTeamArgs) -> BoxFuture<Result<GetTeamResponse>>;
+
+    /// Starts a game between two teams voting on their moves
+    #[rpc(
+        name = "createConsultationGame",
+        alias("chessvm.createConsultationGame")
+    )]
+    fn create_consultation_game(
+        &self,
+        args: CreateConsultationGameArgs,
+    ) -> BoxFuture<Result<CreateConsultationGameResponse>>;
+
+    /// Votes for the next move of a team in a consultation game
+    #[rpc(name = "voteMove", alias("chessvm.voteMove"))]
+    fn vote_move(&self, args: VoteMoveArgs) -> BoxFuture<Result<TxResponse>>;
+}
+
+/// Nonce, tip and signature of the transaction submitted by a write method,
+/// given along with its other arguments
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct TxAuth {
+    /// Chosen by the sender to tell apart transactions with the same action.
+    /// Objects created by the transaction, e.g. challenges, take their ID
+    /// from it.
+    #[serde(default)]
+    pub nonce: u64,
+    /// Paid on top of the fee for the transaction to be included sooner
+    #[serde(default)]
+    pub tip: u64,
+    /// Sender's signature of the transaction's signing message, see
+    /// [`Transaction::sign`](crate::block::tx::Transaction::sign)
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// Response of write methods that only submit a transaction
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TxResponse {
+    /// Look up the receipt of this transaction to know if it succeeded
+    pub tx_id: ids::Id,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -81,6 +371,13 @@ pub struct GetBlockResponse {
 pub struct CreateGameArgs {
     white: Address,
     black: Address,
+    #[serde(default)]
+    variant: GameVariant,
+    /// Starting position in FEN; the variant's initial one if not given
+    #[serde(default)]
+    fen: Option<String>,
+    #[serde(flatten)]
+    pub auth: TxAuth,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -90,8 +387,14 @@ pub struct CreateGameResponse {
 
 /// We need to implement this since the Move enum from the chess package cannot
 /// be serialized :(
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// `Uci` and `San` hold a move in the respective notation (e.g. `e7e8q` or
+/// `Nxf3+`), which is resolved against the current position of the game. The
+/// remaining variants are the legacy, fully spelled out encoding.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum MoveEnum {
+    Uci(String),
+    San(String),
     Normal {
         role: String,
         from: String,
@@ -113,7 +416,51 @@ pub enum MoveEnum {
 pub struct MakeMoveArgs {
     player: Address,
     game_id: String,
-    mv: MoveEnum,
+    /// Exactly one of `mv`, `uci` and `san` must be given
+    #[serde(default)]
+    mv: Option<MoveEnum>,
+    #[serde(default)]
+    uci: Option<String>,
+    #[serde(default)]
+    san: Option<String>,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+impl MakeMoveArgs {
+    /// Returns the move, in whichever notation it was given
+    fn into_move(self) -> Result<MoveEnum> {
+        parse_move(self.mv, self.uci, self.san)
+    }
+}
+
+/// Returns the move given in exactly one of the notations
+fn parse_move(mv: Option<MoveEnum>, uci: Option<String>, san: Option<String>) -> Result<MoveEnum> {
+    let mv = match (mv, uci, san) {
+        (Some(mv), None, None) => mv,
+        (None, Some(uci), None) => MoveEnum::Uci(uci),
+        (None, None, Some(san)) => MoveEnum::San(san),
+        _ => {
+            return Err(Error::invalid_params(
+                "exactly one of mv, uci and san must be given",
+            ))
+        }
+    };
+
+    // Catch typos before the move reaches the mempool
+    match &mv {
+        MoveEnum::Uci(uci) => {
+            Uci::from_ascii(uci.as_bytes())
+                .map_err(|e| Error::invalid_params(format!("invalid UCI move: {e}")))?;
+        }
+        MoveEnum::San(san) => {
+            SanPlus::from_ascii(san.as_bytes())
+                .map_err(|e| Error::invalid_params(format!("invalid SAN move: {e}")))?;
+        }
+        _ => {}
+    }
+
+    Ok(mv)
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -124,11 +471,15 @@ pub struct MakeMoveResponse {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct EndGameArgs {
     game_id: u64,
+    #[serde(flatten)]
+    pub auth: TxAuth,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct EndGameResponse {
-    status: bool,
+    pub status: bool,
+    /// Look up the receipt of this transaction to know if the game was ended
+    pub tx_id: ids::Id,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -139,6 +490,40 @@ pub struct GetGameArgs {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GetGameResponse {
     pub game: String,
+    pub variant: GameVariant,
+    /// FEN of the position the game started from
+    pub starting_fen: String,
+    /// Whether the game was played outside of ChessVM and imported
+    pub imported: bool,
+    /// Whether the game counts towards ratings and statistics
+    pub rated: bool,
+    /// Whether the game has ended and was moved to the archive
+    pub archived: bool,
+    pub outcome: Option<GameOutcome>,
+    /// Number of half-moves played
+    pub move_count: usize,
+    /// Takeback waiting for the opponent's approval
+    pub takeback: Option<TakebackRequest>,
+    /// Pause requested or in effect
+    pub pause: Option<Pause>,
+    /// Rematch offered after the game, if any
+    pub rematch: Option<Rematch>,
+    /// IDs of the games linked by rematches, from the first to the last
+    pub rematch_chain: Vec<u64>,
+    /// Tournament the game is played in, if any
+    pub tournament: Option<u64>,
+    /// Simul the game is a board of, if any
+    pub simul: Option<u64>,
+    /// Amount each player wagered on the game
+    pub stake: u64,
+    /// Stakes still locked, until the game ends and they are paid out
+    pub escrow: Option<Escrow>,
+    /// Teams voting on the moves, if it is a consultation game
+    pub consultation: Option<Consultation>,
+    /// Number of votes for each move of the side to move, in UCI notation
+    pub votes: BTreeMap<String, usize>,
+    /// Block timestamp after which the most voted move is played
+    pub voting_deadline: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -151,171 +536,796 @@ pub struct ExistsResponse {
     pub exists: bool,
 }
 
-/// Implements API services for the chain-specific handlers.
-#[derive(Clone)]
-pub struct ChainService<A> {
-    pub vm: Vm<A>,
+/// Maximum number of items returned by paginated read methods
+const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetGameHistoryArgs {
+    pub game_id: String,
+    /// Index of the first move to return
+    #[serde(default)]
+    pub offset: usize,
+    /// Number of moves to return; at most (and by default) `MAX_PAGE_SIZE`
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
-impl<A> ChainService<A> {
-    pub fn new(vm: Vm<A>) -> Self {
-        Self { vm }
-    }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetGameHistoryResponse {
+    pub moves: Vec<MoveRecord>,
+    /// Number of moves played in the game
+    pub total: usize,
 }
 
-impl<A> Rpc for ChainService<A>
-where
-    A: Send + Sync + Clone + 'static,
-{
-    #[doc = r" Pings the VM."]
-    fn ping(&self) -> BoxFuture<Result<crate::api::PingResponse>> {
-        log::debug!("ping called");
-        Box::pin(async move { Ok(crate::api::PingResponse { success: true }) })
-    }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetGamePgnArgs {
+    /// Exports a single game
+    #[serde(default)]
+    pub game_id: Option<String>,
+    /// Exports every game of a player
+    #[serde(default)]
+    pub player: Option<Address>,
+}
 
-    #[doc = r" Fetches the last accepted block."]
-    fn last_accepted(&self) -> BoxFuture<Result<LastAcceptedResponse>> {
-        log::debug!("last accept method called!");
-        let vm = self.vm.clone();
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetGamePgnResponse {
+    pub pgn: String,
+    /// Number of games in `pgn`
+    pub games: usize,
+}
 
-        Box::pin(async move {
-            let vm_state = vm.vm_state.read().await;
-            if let Some(state) = &vm_state.state {
-                let last_accepted = state
-                    .get_last_accepted_block_id()
-                    .await
-                    .map_err(create_jsonrpc_error)?;
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetReceiptArgs {
+    pub tx_id: String,
+}
 
-                return Ok(LastAcceptedResponse { id: last_accepted });
-            }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetReceiptResponse {
+    pub receipt: Receipt,
+}
 
-            Err(Error {
-                code: ErrorCode::InternalError,
-                message: String::from("No state manager found"),
-                data: None,
-            })
-        })
-    }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetRatingArgs {
+    pub player: Address,
+    /// Only returns ratings in this variant
+    #[serde(default)]
+    pub variant: Option<GameVariant>,
+    /// Only returns ratings in this time control category
+    #[serde(default)]
+    pub category: Option<TimeCategory>,
+}
 
-    #[doc = r" Fetches the block."]
-    fn get_block(&self, args: GetBlockArgs) -> BoxFuture<Result<GetBlockResponse>> {
-        let blk_id = ids::Id::from_str(&args.id).unwrap();
-        log::info!("get_block called for {}", blk_id);
+/// A player's rating in a pool
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PoolRating {
+    pub variant: GameVariant,
+    pub category: TimeCategory,
+    pub rating: u32,
+    /// Number of rated games played in the pool
+    pub games: u32,
+}
 
-        let vm = self.vm.clone();
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetRatingResponse {
+    pub ratings: Vec<PoolRating>,
+}
 
-        Box::pin(async move {
-            let vm_state = vm.vm_state.read().await;
-            if let Some(state) = &vm_state.state {
-                let block = state
-                    .get_block(&blk_id)
-                    .await
-                    .map_err(create_jsonrpc_error)?;
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetLeaderboardArgs {
+    #[serde(default)]
+    pub variant: GameVariant,
+    pub category: TimeCategory,
+    /// Rank of the first player to return, starting at 0
+    #[serde(default)]
+    pub offset: usize,
+    /// Number of players to return; at most (and by default) `MAX_PAGE_SIZE`
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
 
-                return Ok(GetBlockResponse { block });
-            }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LeaderboardEntry {
+    /// Position in the leaderboard, starting at 1
+    pub rank: usize,
+    pub player: Address,
+    pub rating: u32,
+    pub games: u32,
+}
 
-            Err(Error {
-                code: ErrorCode::InternalError,
-                message: String::from("no state manager found"),
-                data: None,
-            })
-        })
-    }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetLeaderboardResponse {
+    pub entries: Vec<LeaderboardEntry>,
+    /// Number of rated players in the pool
+    pub total: usize,
+}
 
-    #[doc = r" Creates new Chess game"]
-    /// Write method
-    fn create_game(&self, args: CreateGameArgs) -> BoxFuture<Result<CreateGameResponse>> {
-        log::debug!("create_game API method called!");
-        let vm = self.vm.clone();
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetPlayerStatsArgs {
+    pub player: Address,
+}
 
-        Box::pin(async move {
-            let act = ActionType::CreateGame {
-                white: args.white,
-                black: args.black,
-                block_id: ids::Id::empty(),
-            };
-            let tx = Transaction {
-                action: act,
-                bytes: Vec::new(),
-                id: ids::Id::empty(),
-                size: 0,
-                sender: args.white,
-            };
-            vm.submit_tx(tx).await.map_err(create_jsonrpc_error)?;
-            Ok(CreateGameResponse {
-                game_id: calculate_game_id(args.white, args.black),
-            })
-        })
-    }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetPlayerStatsResponse {
+    #[serde(flatten)]
+    pub stats: PlayerStats,
+    /// Average number of half-moves per game
+    pub average_length: Option<f64>,
+}
 
-    #[doc = r" Make a Chess move"]
-    /// Write method
-    fn make_move(&self, args: MakeMoveArgs) -> BoxFuture<Result<MakeMoveResponse>> {
-        log::debug!("make_move method called");
-        let vm = self.vm.clone();
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NameArgs {
+    /// Owner of the name
+    player: Address,
+    name: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
 
-        Box::pin(async move {
-            let vm_state = vm.vm_state.read().await;
-            if let Some(_) = &vm_state.state {
-                // Create TX and send to mempool
-                // TODO: fix block_id
-                let act = ActionType::MakeMove {
-                    player: args.player,
-                    game_id: args.game_id.parse::<u64>().unwrap(),
-                    mv: args.mv,
-                    block_id: ids::Id::empty(),
-                };
-                let tx = Transaction {
-                    action: act,
-                    bytes: Vec::new(),
-                    id: ids::Id::empty(),
-                    size: 0,
-                    sender: args.player,
-                };
-                let r_val = vm.submit_tx(tx).await;
-                if r_val.is_err() {
-                    return Err(Error {
-                        code: ErrorCode::InternalError,
-                        message: String::from("Submitting make move transaction failed!"),
-                        data: None,
-                    });
-                }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TransferNameArgs {
+    /// Owner of the name
+    player: Address,
+    name: String,
+    to: Address,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
 
-                return Ok(MakeMoveResponse { status: true });
-            }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ResolveNameArgs {
+    pub name: String,
+}
 
-            Err(Error {
-                code: ErrorCode::InternalError,
-                message: String::from("no state manager found"),
-                data: None,
-            })
-        })
-    }
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ResolveNameResponse {
+    pub address: Address,
+}
 
-    #[doc = r" End a Chess game"]
-    /// Write method
-    fn end_game(&self, args: EndGameArgs) -> BoxFuture<Result<EndGameResponse>> {
-        log::debug!("end_game method called");
-        let vm = self.vm.clone();
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReverseResolveArgs {
+    pub address: Address,
+}
 
-        Box::pin(async move {
-            let vm_state = vm.vm_state.write().await;
-            if let Some(_) = &vm_state.state {
-                // Create TX and submit to mempool
-                // Can set block_id to 0 since never used
-                // TODO: Fix block_id
-                let act = ActionType::EndGame {
-                    game_id: args.game_id,
-                    block_id: ids::Id::empty(),
-                };
-                let tx = Transaction {
-                    action: act,
-                    bytes: Vec::new(),
-                    id: ids::Id::empty(),
-                    size: 0,
-                    sender: Address::default(),
-                };
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReverseResolveResponse {
+    /// `None` if the address has no name
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateTournamentArgs {
+    organizer: Address,
+    format: TournamentFormat,
+    rounds: usize,
+    #[serde(default)]
+    variant: GameVariant,
+    #[serde(default)]
+    time_control: Option<TimeControl>,
+    /// Block timestamp from which registration closes
+    registration_deadline: u64,
+    /// How drawn knockout matches are decided
+    #[serde(default)]
+    tiebreak: Tiebreak,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateTournamentResponse {
+    pub tournament_id: u64,
+    pub tx_id: ids::Id,
+}
+
+/// Arguments of actions a player, or the organizer, takes on a tournament
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TournamentActionArgs {
+    player: Address,
+    tournament_id: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTournamentArgs {
+    pub tournament_id: String,
+}
+
+/// A board of a tournament round, with its result once decided
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RoundPairing {
+    pub white: Address,
+    /// `None` for a bye
+    pub black: Option<Address>,
+    pub game_id: Option<u64>,
+    pub result: Option<GameResult>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTournamentResponse {
+    pub organizer: Address,
+    pub format: TournamentFormat,
+    pub rounds: usize,
+    pub variant: GameVariant,
+    pub time_control: Option<TimeControl>,
+    pub registration_deadline: u64,
+    /// Registered players, ordered by seed once the first round started
+    pub players: Vec<Address>,
+    pub withdrawn: Vec<Address>,
+    /// Boards of every round started so far
+    pub pairings: Vec<Vec<RoundPairing>>,
+    pub created_at: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetStandingsResponse {
+    /// Best first
+    pub standings: Vec<Standing>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetBracketResponse {
+    pub tiebreak: Tiebreak,
+    /// Matches of every round, from the first round to the final, with
+    /// their games and results
+    pub rounds: Vec<Vec<BracketMatch>>,
+    /// Winner of the final, once decided
+    pub winner: Option<Address>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PostSeekArgs {
+    player: Address,
+    #[serde(default)]
+    time_control: Option<TimeControl>,
+    #[serde(default)]
+    variant: GameVariant,
+    /// Ratings of acceptable opponents; any if not given
+    #[serde(default)]
+    rating_range: Option<RatingRange>,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PostSeekResponse {
+    pub seek_id: u64,
+    pub tx_id: ids::Id,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CancelSeekArgs {
+    player: Address,
+    seek_id: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SeekEntry {
+    pub seek_id: u64,
+    #[serde(flatten)]
+    pub seek: Seek,
+    /// Block timestamp from which the seek is no longer paired
+    pub expires_at: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ListSeeksResponse {
+    /// Oldest first
+    pub seeks: Vec<SeekEntry>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TransferArgs {
+    to: Address,
+    amount: u64,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetBalanceArgs {
+    pub address: Address,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetBalanceResponse {
+    pub balance: u64,
+    /// Funds staked in wagered games in progress, not part of the balance
+    pub locked: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EstimateFeeArgs {
+    /// Name of the action, e.g. `MakeMove`
+    pub action: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EstimateFeeResponse {
+    /// Fee charged for the action, without tip
+    pub fee: u64,
+    /// Highest tip of the transactions waiting in the mempool, to be outbid
+    /// for a transaction to be included first
+    pub pending_tip: u64,
+    /// Account collecting the fees; they are burned if not set
+    pub treasury: Option<Address>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PostPuzzleArgs {
+    poster: Address,
+    fen: String,
+    /// Hash of the solution's moves in UCI notation, separated by spaces
+    solution_hash: B256,
+    reward: u64,
+    /// Block timestamp after which no solution can be committed
+    deadline: u64,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PostPuzzleResponse {
+    pub puzzle_id: u64,
+    pub tx_id: ids::Id,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CommitSolutionArgs {
+    solver: Address,
+    puzzle_id: String,
+    /// Hash of the solver's address, the solution and a salt
+    hash: B256,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RevealSolutionArgs {
+    solver: Address,
+    puzzle_id: String,
+    /// Moves of the solution in UCI notation
+    moves: Vec<String>,
+    salt: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetPuzzleArgs {
+    pub puzzle_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetPuzzleResponse {
+    #[serde(flatten)]
+    pub puzzle: Puzzle,
+    /// Block timestamp after which no solution can be revealed
+    pub reveal_deadline: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateSimulArgs {
+    host: Address,
+    /// Maximum number of opponents
+    boards: usize,
+    #[serde(default)]
+    variant: GameVariant,
+    #[serde(default)]
+    time_control: Option<TimeControl>,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateSimulResponse {
+    pub simul_id: u64,
+    pub tx_id: ids::Id,
+}
+
+/// Arguments of actions a player, or the host, takes on a simul
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SimulActionArgs {
+    player: Address,
+    simul_id: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetSimulArgs {
+    pub simul_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetSimulResponse {
+    #[serde(flatten)]
+    pub simul: Simul,
+    /// Boards in order, once the simul started
+    pub boards: Vec<SimulBoard>,
+    pub score: SimulScore,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateTeamArgs {
+    owner: Address,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateTeamResponse {
+    pub team_id: u64,
+    pub tx_id: ids::Id,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TeamMemberArgs {
+    /// Who adds or removes the member
+    player: Address,
+    team_id: String,
+    member: Address,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTeamArgs {
+    pub team_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTeamResponse {
+    pub team: Team,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateConsultationGameArgs {
+    creator: Address,
+    white_team: String,
+    black_team: String,
+    #[serde(default)]
+    variant: GameVariant,
+    /// Seconds each side votes before its most voted move is played
+    voting_window: u64,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateConsultationGameResponse {
+    pub game_id: u64,
+    pub tx_id: ids::Id,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct VoteMoveArgs {
+    voter: Address,
+    game_id: String,
+    /// Exactly one of `mv`, `uci` and `san` must be given
+    #[serde(default)]
+    mv: Option<MoveEnum>,
+    #[serde(default)]
+    uci: Option<String>,
+    #[serde(default)]
+    san: Option<String>,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ImportGameArgs {
+    pub white: Address,
+    pub black: Address,
+    #[serde(default)]
+    pub variant: GameVariant,
+    /// Starting position in FEN; the variant's initial one if not given
+    #[serde(default)]
+    pub fen: Option<String>,
+    /// PGN movetext, ending with the game result
+    pub movetext: String,
+    /// Signatures of [`pgn::import_message`] by each player
+    pub white_signature: String,
+    pub black_signature: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ImportGameResponse {
+    pub game_id: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateChallengeArgs {
+    challenger: Address,
+    #[serde(default)]
+    opponent: Option<Address>,
+    #[serde(default)]
+    color: ColorPreference,
+    #[serde(default)]
+    time_control: Option<TimeControl>,
+    #[serde(default)]
+    variant: GameVariant,
+    /// Amount each player wagers, locked when the challenge is accepted
+    #[serde(default)]
+    stake: u64,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CreateChallengeResponse {
+    pub challenge_id: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AcceptChallengeArgs {
+    player: Address,
+    challenge_id: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AcceptChallengeResponse {
+    /// ID of the game created by the challenge
+    pub game_id: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CancelChallengeArgs {
+    player: Address,
+    challenge_id: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CancelChallengeResponse {
+    pub status: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RequestTakebackArgs {
+    player: Address,
+    game_id: String,
+    /// Number of half-moves to undo
+    plies: usize,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+/// Arguments of actions a player takes on a game
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GameActionArgs {
+    player: Address,
+    game_id: String,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SetConditionalMovesArgs {
+    player: Address,
+    game_id: String,
+    /// Replies keyed by the expected opponent move, all in UCI; an empty tree
+    /// clears the player's conditional moves
+    moves: ConditionalMoves,
+    #[serde(flatten)]
+    pub auth: TxAuth,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ListOpenChallengesArgs {
+    /// Also includes the challenges addressed to this player
+    #[serde(default)]
+    pub player: Option<Address>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OpenChallenge {
+    pub challenge_id: u64,
+    pub challenge: Challenge,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ListOpenChallengesResponse {
+    pub challenges: Vec<OpenChallenge>,
+}
+
+/// Implements API services for the chain-specific handlers.
+#[derive(Clone)]
+pub struct ChainService<A> {
+    pub vm: Vm<A>,
+}
+
+impl<A> ChainService<A> {
+    pub fn new(vm: Vm<A>) -> Self {
+        Self { vm }
+    }
+}
+
+impl<A> Rpc for ChainService<A>
+where
+    A: Send + Sync + Clone + 'static,
+{
+    #[doc = r" Pings the VM."]
+    fn ping(&self) -> BoxFuture<Result<crate::api::PingResponse>> {
+        log::debug!("ping called");
+        Box::pin(async move { Ok(crate::api::PingResponse { success: true }) })
+    }
+
+    #[doc = r" Fetches the last accepted block."]
+    fn last_accepted(&self) -> BoxFuture<Result<LastAcceptedResponse>> {
+        log::debug!("last accept method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let last_accepted = state
+                    .get_last_accepted_block_id()
+                    .await
+                    .map_err(create_jsonrpc_error)?;
+
+                return Ok(LastAcceptedResponse { id: last_accepted });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("No state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Fetches the block."]
+    fn get_block(&self, args: GetBlockArgs) -> BoxFuture<Result<GetBlockResponse>> {
+        let blk_id = ids::Id::from_str(&args.id).unwrap();
+        log::info!("get_block called for {}", blk_id);
+
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let block = state
+                    .get_block(&blk_id)
+                    .await
+                    .map_err(create_jsonrpc_error)?;
+
+                return Ok(GetBlockResponse { block });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Creates new Chess game"]
+    /// Write method
+    fn create_game(&self, args: CreateGameArgs) -> BoxFuture<Result<CreateGameResponse>> {
+        log::debug!("create_game API method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // Reject bad positions before they reach the mempool
+            if let Some(fen) = &args.fen {
+                starting_position(args.variant, Some(fen), 0).map_err(create_jsonrpc_error)?;
+            }
+            // The nonce only needs to differ between games of the same
+            // players, so the transaction's one is good enough
+            let nonce = args.auth.nonce;
+            let act = ActionType::CreateGame {
+                white: args.white,
+                black: args.black,
+                variant: args.variant,
+                fen: args.fen,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx = Transaction {
+                action: act,
+                bytes: Vec::new(),
+                id: ids::Id::empty(),
+                size: 0,
+                sender: args.white,
+                tip: args.auth.tip,
+                nonce: args.auth.nonce,
+                signature: args.auth.signature,
+            };
+            vm.submit_tx(tx).await.map_err(create_jsonrpc_error)?;
+            Ok(CreateGameResponse {
+                game_id: calculate_game_id(args.white, args.black, nonce),
+            })
+        })
+    }
+
+    #[doc = r" Make a Chess move"]
+    /// Write method
+    fn make_move(&self, args: MakeMoveArgs) -> BoxFuture<Result<MakeMoveResponse>> {
+        log::debug!("make_move method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // The state lock is released before submitting, which reads it
+            if vm.vm_state.read().await.state.is_some() {
+                let player = args.player;
+                let game_id = parse_id(&args.game_id)?;
+                let auth = args.auth.clone();
+                let mv = args.into_move()?;
+                // Create TX and send to mempool
+                // TODO: fix block_id
+                let act = ActionType::MakeMove {
+                    player,
+                    game_id,
+                    mv,
+                    block_id: ids::Id::empty(),
+                };
+                let tx = Transaction {
+                    action: act,
+                    bytes: Vec::new(),
+                    id: ids::Id::empty(),
+                    size: 0,
+                    sender: player,
+                    tip: auth.tip,
+                    nonce: auth.nonce,
+                    signature: auth.signature,
+                };
+                let r_val = vm.submit_tx(tx).await;
+                if r_val.is_err() {
+                    return Err(Error {
+                        code: ErrorCode::InternalError,
+                        message: String::from("Submitting make move transaction failed!"),
+                        data: None,
+                    });
+                }
+
+                return Ok(MakeMoveResponse { status: true });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" End a Chess game"]
+    /// Write method
+    fn end_game(&self, args: EndGameArgs) -> BoxFuture<Result<EndGameResponse>> {
+        log::debug!("end_game method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // The state lock is released before submitting, which reads it
+            if vm.vm_state.read().await.state.is_some() {
+                // Create TX and submit to mempool
+                // Can set block_id to 0 since never used
+                // TODO: Fix block_id
+                let act = ActionType::EndGame {
+                    game_id: args.game_id,
+                    block_id: ids::Id::empty(),
+                };
+                // The game is ended on behalf of whoever signed the request
+                let sender = recover_sender(&act, &args.auth)?;
+                let tx = Transaction {
+                    action: act,
+                    bytes: Vec::new(),
+                    id: ids::Id::empty(),
+                    size: 0,
+                    sender,
+                    tip: args.auth.tip,
+                    nonce: args.auth.nonce,
+                    signature: args.auth.signature,
+                };
+                let tx_id = tx.compute_id().map_err(create_jsonrpc_error)?;
                 let r_val = vm.submit_tx(tx).await;
                 if r_val.is_err() {
                     return Err(Error {
@@ -324,7 +1334,873 @@ where
                         data: None,
                     });
                 }
-                return Ok(EndGameResponse { status: true });
+                return Ok(EndGameResponse {
+                    status: true,
+                    tx_id,
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r"Get Chess game state"]
+    /// Read method
+    fn get_game(&self, args: GetGameArgs) -> BoxFuture<Result<GetGameResponse>> {
+        log::debug!("get_game method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let game_id = parse_id(&args.game_id)?;
+                let active = state.get_game_state(game_id).await;
+                if let Some(game) = state.get_game_record(game_id).await {
+                    // The board is the first field of the FEN
+                    let board = game.final_fen.split(' ').next().unwrap_or_default();
+                    return Ok(GetGameResponse {
+                        game: board.to_string(),
+                        variant: game.variant,
+                        starting_fen: game.starting_fen,
+                        imported: game.imported,
+                        rated: game.rated,
+                        archived: state.is_archived(game_id).await,
+                        outcome: game.outcome,
+                        move_count: game.move_count,
+                        takeback: active.as_ref().and_then(|g| g.takeback()),
+                        pause: active.as_ref().and_then(|g| g.pause()),
+                        rematch: state.get_rematch(game_id).await,
+                        rematch_chain: state.get_rematch_chain(game_id).await,
+                        tournament: game.tournament,
+                        simul: game.simul,
+                        stake: game.stake,
+                        escrow: state.get_escrow(game_id).await,
+                        consultation: game.consultation,
+                        votes: state.get_vote_tally(game_id).await,
+                        voting_deadline: active.as_ref().and_then(|g| g.voting_deadline()),
+                    });
+                }
+                log::info!("Game was NOT found in state :(");
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r"Check if game exists"]
+    /// Read method
+    fn exists(&self, args: ExistsArgs) -> BoxFuture<Result<ExistsResponse>> {
+        log::debug!("exists method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+
+            if let Some(state) = &vm_state.state {
+                return Ok(ExistsResponse {
+                    exists: state.game_exists(parse_id(&args.game_id)?).await,
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Get the moves played in a Chess game"]
+    /// Read method
+    fn get_game_history(
+        &self,
+        args: GetGameHistoryArgs,
+    ) -> BoxFuture<Result<GetGameHistoryResponse>> {
+        log::debug!("get_game_history method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let game_id = parse_id(&args.game_id)?;
+            let limit = args.limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                return match state.get_game_history(game_id, args.offset, limit).await {
+                    Some((moves, total)) => Ok(GetGameHistoryResponse { moves, total }),
+                    None => Err(Error::invalid_params("game not found")),
+                };
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Export Chess games in PGN"]
+    /// Read method
+    fn get_game_pgn(&self, args: GetGamePgnArgs) -> BoxFuture<Result<GetGamePgnResponse>> {
+        log::debug!("get_game_pgn method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let games = match (args.game_id, args.player) {
+                    (Some(game_id), None) => {
+                        let game_id = parse_id(&game_id)?;
+                        match state.get_game_record(game_id).await {
+                            Some(game) => vec![(game_id, game)],
+                            None => return Err(Error::invalid_params("game not found")),
+                        }
+                    }
+                    (None, Some(player)) => state.get_games_of_player(player).await,
+                    _ => {
+                        return Err(Error::invalid_params(
+                            "exactly one of game_id and player must be given",
+                        ))
+                    }
+                };
+
+                return Ok(GetGamePgnResponse {
+                    pgn: pgn::games_to_pgn(&games),
+                    games: games.len(),
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Get the outcome of an accepted transaction"]
+    /// Read method
+    fn get_receipt(&self, args: GetReceiptArgs) -> BoxFuture<Result<GetReceiptResponse>> {
+        log::debug!("get_receipt method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let tx_id = ids::Id::from_str(&args.tx_id)
+                .map_err(|e| Error::invalid_params(format!("invalid tx_id: {e}")))?;
+
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                return match state.get_receipt(&tx_id).await {
+                    Some(receipt) => Ok(GetReceiptResponse { receipt }),
+                    None => Err(Error::invalid_params("receipt not found")),
+                };
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Records a game played outside of ChessVM"]
+    /// Write method
+    fn import_game(&self, args: ImportGameArgs) -> BoxFuture<Result<ImportGameResponse>> {
+        log::debug!("import_game method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // Reject unsigned and unreadable games before they reach the mempool
+            tx::verify_import(
+                args.white,
+                args.black,
+                args.variant,
+                args.fen.as_deref(),
+                &args.movetext,
+                [&args.white_signature, &args.black_signature],
+            )
+            .map_err(create_jsonrpc_error)?;
+            pgn::parse_movetext(&args.movetext).map_err(create_jsonrpc_error)?;
+
+            let game_id = calculate_import_id(args.white, args.black, &args.movetext);
+            let act = ActionType::ImportGame {
+                white: args.white,
+                black: args.black,
+                variant: args.variant,
+                fen: args.fen,
+                movetext: args.movetext,
+                white_signature: args.white_signature,
+                black_signature: args.black_signature,
+                block_id: ids::Id::empty(),
+            };
+            let tx = Transaction {
+                action: act,
+                bytes: Vec::new(),
+                id: ids::Id::empty(),
+                size: 0,
+                sender: args.white,
+                tip: args.auth.tip,
+                nonce: args.auth.nonce,
+                signature: args.auth.signature,
+            };
+            vm.submit_tx(tx).await.map_err(create_jsonrpc_error)?;
+
+            Ok(ImportGameResponse { game_id })
+        })
+    }
+
+    #[doc = r" Offers a game to a specific opponent or to anyone"]
+    /// Write method
+    fn create_challenge(
+        &self,
+        args: CreateChallengeArgs,
+    ) -> BoxFuture<Result<CreateChallengeResponse>> {
+        log::debug!("create_challenge method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // The nonce only needs to differ between challenges of the same
+            // challenger, so the transaction's one is good enough
+            let nonce = args.auth.nonce;
+            let act = ActionType::CreateChallenge {
+                challenger: args.challenger,
+                opponent: args.opponent,
+                color: args.color,
+                time_control: args.time_control,
+                variant: args.variant,
+                stake: args.stake,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx = Transaction {
+                action: act,
+                bytes: Vec::new(),
+                id: ids::Id::empty(),
+                size: 0,
+                sender: args.challenger,
+                tip: args.auth.tip,
+                nonce: args.auth.nonce,
+                signature: args.auth.signature,
+            };
+            vm.submit_tx(tx).await.map_err(create_jsonrpc_error)?;
+            Ok(CreateChallengeResponse {
+                challenge_id: calculate_challenge_id(args.challenger, nonce),
+            })
+        })
+    }
+
+    #[doc = r" Accepts a challenge, starting the game"]
+    /// Write method
+    fn accept_challenge(
+        &self,
+        args: AcceptChallengeArgs,
+    ) -> BoxFuture<Result<AcceptChallengeResponse>> {
+        log::debug!("accept_challenge method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let challenge_id = parse_id(&args.challenge_id)?;
+            let act = ActionType::AcceptChallenge {
+                player: args.player,
+                challenge_id,
+                block_id: ids::Id::empty(),
+            };
+            let tx = Transaction {
+                action: act,
+                bytes: Vec::new(),
+                id: ids::Id::empty(),
+                size: 0,
+                sender: args.player,
+                tip: args.auth.tip,
+                nonce: args.auth.nonce,
+                signature: args.auth.signature,
+            };
+            vm.submit_tx(tx).await.map_err(create_jsonrpc_error)?;
+            Ok(AcceptChallengeResponse {
+                game_id: challenge_id,
+            })
+        })
+    }
+
+    #[doc = r" Withdraws a challenge"]
+    /// Write method
+    fn cancel_challenge(
+        &self,
+        args: CancelChallengeArgs,
+    ) -> BoxFuture<Result<CancelChallengeResponse>> {
+        log::debug!("cancel_challenge method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::CancelChallenge {
+                player: args.player,
+                challenge_id: parse_id(&args.challenge_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx = Transaction {
+                action: act,
+                bytes: Vec::new(),
+                id: ids::Id::empty(),
+                size: 0,
+                sender: args.player,
+                tip: args.auth.tip,
+                nonce: args.auth.nonce,
+                signature: args.auth.signature,
+            };
+            vm.submit_tx(tx).await.map_err(create_jsonrpc_error)?;
+            Ok(CancelChallengeResponse { status: true })
+        })
+    }
+
+    #[doc = r" Lists challenges waiting for an opponent"]
+    /// Read method
+    fn list_open_challenges(
+        &self,
+        args: ListOpenChallengesArgs,
+    ) -> BoxFuture<Result<ListOpenChallengesResponse>> {
+        log::debug!("list_open_challenges method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let challenges = state
+                    .list_open_challenges(args.player)
+                    .await
+                    .into_iter()
+                    .map(|(challenge_id, challenge)| OpenChallenge {
+                        challenge_id,
+                        challenge,
+                    })
+                    .collect();
+
+                return Ok(ListOpenChallengesResponse { challenges });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Asks the opponent to undo the last moves of a game"]
+    /// Write method
+    fn request_takeback(&self, args: RequestTakebackArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("request_takeback method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::RequestTakeback {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                plies: args.plies,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Accepts the opponent's takeback request"]
+    /// Write method
+    fn accept_takeback(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("accept_takeback method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::AcceptTakeback {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Sets up replies to the opponent's next moves"]
+    /// Write method
+    fn set_conditional_moves(
+        &self,
+        args: SetConditionalMovesArgs,
+    ) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("set_conditional_moves method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::SetConditionalMoves {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                moves: args.moves,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Asks the opponent to freeze the clocks of a game"]
+    /// Write method
+    fn request_pause(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("request_pause method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::RequestPause {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Accepts the opponent's pause request"]
+    /// Write method
+    fn accept_pause(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("accept_pause method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::AcceptPause {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Restarts the clocks of a paused game"]
+    /// Write method
+    fn resume(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("resume method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::Resume {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Offers to play a finished game again with colors swapped"]
+    /// Write method
+    fn offer_rematch(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("offer_rematch method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::OfferRematch {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Accepts the opponent's rematch offer"]
+    /// Write method
+    fn accept_rematch(&self, args: GameActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("accept_rematch method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::AcceptRematch {
+                player: args.player,
+                game_id: parse_id(&args.game_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Get the ratings of a player"]
+    /// Read method
+    fn get_rating(&self, args: GetRatingArgs) -> BoxFuture<Result<GetRatingResponse>> {
+        log::debug!("get_rating method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let found = match (args.variant, args.category) {
+                    // A single pool always has a rating, possibly the initial one
+                    (Some(variant), Some(category)) => {
+                        let pool = RatingPool { variant, category };
+                        vec![(pool, state.get_rating(args.player, pool).await)]
+                    }
+                    (variant, category) => state
+                        .get_ratings(args.player)
+                        .await
+                        .into_iter()
+                        .filter(|(pool, _)| {
+                            variant.is_none_or(|v| v == pool.variant)
+                                && category.is_none_or(|c| c == pool.category)
+                        })
+                        .collect(),
+                };
+                let ratings = found
+                    .into_iter()
+                    .map(|(pool, rating)| PoolRating {
+                        variant: pool.variant,
+                        category: pool.category,
+                        rating: rating.rating,
+                        games: rating.games,
+                    })
+                    .collect();
+                return Ok(GetRatingResponse { ratings });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Get the best rated players of a pool"]
+    /// Read method
+    fn get_leaderboard(
+        &self,
+        args: GetLeaderboardArgs,
+    ) -> BoxFuture<Result<GetLeaderboardResponse>> {
+        log::debug!("get_leaderboard method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let limit = args.limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+            let pool = RatingPool {
+                variant: args.variant,
+                category: args.category,
+            };
+
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let (players, total) = state.get_leaderboard(pool, args.offset, limit).await;
+                let entries = players
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (player, rating))| LeaderboardEntry {
+                        rank: args.offset + i + 1,
+                        player,
+                        rating: rating.rating,
+                        games: rating.games,
+                    })
+                    .collect();
+                return Ok(GetLeaderboardResponse { entries, total });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Get the statistics of a player over their finished games"]
+    /// Read method
+    fn get_player_stats(
+        &self,
+        args: GetPlayerStatsArgs,
+    ) -> BoxFuture<Result<GetPlayerStatsResponse>> {
+        log::debug!("get_player_stats method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let stats = state.get_player_stats(args.player).await;
+                return Ok(GetPlayerStatsResponse {
+                    average_length: stats.average_length(),
+                    stats,
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Registers a human readable name for an address"]
+    /// Write method
+    fn register_name(&self, args: NameArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("register_name method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::RegisterName {
+                player: args.player,
+                name: args.name,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Gives a name to another address"]
+    /// Write method
+    fn transfer_name(&self, args: TransferNameArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("transfer_name method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::TransferName {
+                player: args.player,
+                name: args.name,
+                to: args.to,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Frees a name so that anyone can register it"]
+    /// Write method
+    fn release_name(&self, args: NameArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("release_name method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::ReleaseName {
+                player: args.player,
+                name: args.name,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Get the address owning a name"]
+    /// Read method
+    fn resolve_name(&self, args: ResolveNameArgs) -> BoxFuture<Result<ResolveNameResponse>> {
+        log::debug!("resolve_name method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                return match state.resolve_name(&args.name).await {
+                    Some(address) => Ok(ResolveNameResponse { address }),
+                    None => Err(Error::invalid_params("name not found")),
+                };
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Get the name owned by an address"]
+    /// Read method
+    fn reverse_resolve(
+        &self,
+        args: ReverseResolveArgs,
+    ) -> BoxFuture<Result<ReverseResolveResponse>> {
+        log::debug!("reverse_resolve method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                return Ok(ReverseResolveResponse {
+                    name: state.reverse_resolve(args.address).await,
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Creates a round-robin or Swiss tournament"]
+    /// Write method
+    fn create_tournament(
+        &self,
+        args: CreateTournamentArgs,
+    ) -> BoxFuture<Result<CreateTournamentResponse>> {
+        log::debug!("create_tournament method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // As for challenges, the nonce only needs to differ between
+            // tournaments of the same organizer
+            let nonce = args.auth.nonce;
+            let act = ActionType::CreateTournament {
+                organizer: args.organizer,
+                format: args.format,
+                rounds: args.rounds,
+                variant: args.variant,
+                time_control: args.time_control,
+                registration_deadline: args.registration_deadline,
+                tiebreak: args.tiebreak,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.organizer, args.auth).await?;
+            Ok(CreateTournamentResponse {
+                tournament_id: calculate_tournament_id(args.organizer, nonce),
+                tx_id,
+            })
+        })
+    }
+
+    #[doc = r" Registers a player for a tournament"]
+    /// Write method
+    fn register_for_tournament(&self, args: TournamentActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("register_for_tournament method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::RegisterForTournament {
+                player: args.player,
+                tournament_id: parse_id(&args.tournament_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Withdraws a player from a tournament"]
+    /// Write method
+    fn withdraw_from_tournament(
+        &self,
+        args: TournamentActionArgs,
+    ) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("withdraw_from_tournament method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::WithdrawFromTournament {
+                player: args.player,
+                tournament_id: parse_id(&args.tournament_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Pairs the next round of a tournament and creates its games"]
+    /// Write method
+    fn start_round(&self, args: TournamentActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("start_round method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::StartRound {
+                organizer: args.player,
+                tournament_id: parse_id(&args.tournament_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Get a tournament and the pairings of its rounds"]
+    /// Read method
+    fn get_tournament(&self, args: GetTournamentArgs) -> BoxFuture<Result<GetTournamentResponse>> {
+        log::debug!("get_tournament method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let tournament_id = parse_id(&args.tournament_id)?;
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let tournament = match state.get_tournament(tournament_id).await {
+                    Some(t) => t,
+                    None => return Err(Error::invalid_params("tournament not found")),
+                };
+                let results = state.get_tournament_results(&tournament).await;
+                let pairings = tournament
+                    .pairings
+                    .iter()
+                    .map(|round| {
+                        round
+                            .iter()
+                            .map(|p| RoundPairing {
+                                white: p.white,
+                                black: p.black,
+                                game_id: p.game_id,
+                                result: p.game_id.and_then(|id| results.get(&id).copied()),
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                return Ok(GetTournamentResponse {
+                    organizer: tournament.organizer,
+                    format: tournament.format,
+                    rounds: tournament.rounds,
+                    variant: tournament.variant,
+                    time_control: tournament.time_control,
+                    registration_deadline: tournament.registration_deadline,
+                    players: tournament.players,
+                    withdrawn: tournament.withdrawn,
+                    pairings,
+                    created_at: tournament.created_at,
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Get the standings of a tournament"]
+    /// Read method
+    fn get_standings(&self, args: GetTournamentArgs) -> BoxFuture<Result<GetStandingsResponse>> {
+        log::debug!("get_standings method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let tournament_id = parse_id(&args.tournament_id)?;
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let standings = state
+                    .get_standings(tournament_id)
+                    .await
+                    .map_err(|e| Error::invalid_params(e.to_string()))?;
+                return Ok(GetStandingsResponse { standings });
             }
 
             Err(Error {
@@ -335,22 +2211,25 @@ where
         })
     }
 
-    #[doc = r"Get Chess game state"]
+    #[doc = r" Get the bracket of a knockout"]
     /// Read method
-    fn get_game(&self, args: GetGameArgs) -> BoxFuture<Result<GetGameResponse>> {
-        log::debug!("get_game method called!");
+    fn get_bracket(&self, args: GetTournamentArgs) -> BoxFuture<Result<GetBracketResponse>> {
+        log::debug!("get_bracket method called!");
         let vm = self.vm.clone();
 
         Box::pin(async move {
+            let tournament_id = parse_id(&args.tournament_id)?;
             let vm_state = vm.vm_state.read().await;
             if let Some(state) = &vm_state.state {
-                if let Some(game) = state.get_game(args.game_id.parse::<u64>().unwrap()).await {
-                    // TODO: Convert Chess board to string
-                    return Ok(GetGameResponse {
-                        game: game.board().to_string(),
-                    });
-                }
-                log::info!("Game was NOT found in state :(");
+                return match state.get_tournament(tournament_id).await {
+                    Some(t) if t.format == TournamentFormat::Knockout => Ok(GetBracketResponse {
+                        tiebreak: t.tiebreak,
+                        winner: t.bracket_winner(),
+                        rounds: t.bracket,
+                    }),
+                    Some(_) => Err(Error::invalid_params("tournament is not a knockout")),
+                    None => Err(Error::invalid_params("tournament not found")),
+                };
             }
 
             Err(Error {
@@ -361,20 +2240,474 @@ where
         })
     }
 
-    #[doc = r"Check if game exists"]
+    #[doc = r" Looks for any opponent with matching settings"]
+    /// Write method
+    fn post_seek(&self, args: PostSeekArgs) -> BoxFuture<Result<PostSeekResponse>> {
+        log::debug!("post_seek method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // As for challenges, the nonce only needs to differ between seeks
+            // of the same player
+            let nonce = args.auth.nonce;
+            let act = ActionType::PostSeek {
+                player: args.player,
+                time_control: args.time_control,
+                variant: args.variant,
+                rating_range: args.rating_range,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(PostSeekResponse {
+                seek_id: calculate_seek_id(args.player, nonce),
+                tx_id,
+            })
+        })
+    }
+
+    #[doc = r" Withdraws a seek"]
+    /// Write method
+    fn cancel_seek(&self, args: CancelSeekArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("cancel_seek method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::CancelSeek {
+                player: args.player,
+                seek_id: parse_id(&args.seek_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Lists the seeks waiting for an opponent"]
     /// Read method
-    fn exists(&self, args: ExistsArgs) -> BoxFuture<Result<ExistsResponse>> {
-        log::debug!("exists method called!");
+    fn list_seeks(&self) -> BoxFuture<Result<ListSeeksResponse>> {
+        log::debug!("list_seeks method called!");
         let vm = self.vm.clone();
 
         Box::pin(async move {
             let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let now = Utc::now().timestamp().try_into().unwrap_or_default();
+                let seeks = state
+                    .list_seeks(now)
+                    .await
+                    .into_iter()
+                    .map(|(seek_id, seek)| SeekEntry {
+                        seek_id,
+                        expires_at: seek.created_at.saturating_add(state.genesis.seek_ttl),
+                        seek,
+                    })
+                    .collect();
+                return Ok(ListSeeksResponse { seeks });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Moves funds to another account"]
+    /// Write method
+    fn transfer(&self, args: TransferArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("transfer method called");
+        let vm = self.vm.clone();
 
+        Box::pin(async move {
+            let act = ActionType::Transfer {
+                to: args.to,
+                amount: args.amount,
+                block_id: ids::Id::empty(),
+            };
+            // The funds are taken from whoever signed the transfer
+            let from = recover_sender(&act, &args.auth)?;
+            let tx_id = submit_action(&vm, act, from, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Get the balance of an account"]
+    /// Read method
+    fn get_balance(&self, args: GetBalanceArgs) -> BoxFuture<Result<GetBalanceResponse>> {
+        log::debug!("get_balance method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let vm_state = vm.vm_state.read().await;
             if let Some(state) = &vm_state.state {
-                return Ok(ExistsResponse {
-                    exists: state
-                        .game_exists(args.game_id.parse::<u64>().unwrap())
-                        .await,
+                return Ok(GetBalanceResponse {
+                    balance: state.get_balance(args.address).await,
+                    locked: state.get_locked(args.address).await,
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Get the fee of an action and the tips pending in the mempool"]
+    /// Read method
+    fn estimate_fee(&self, args: EstimateFeeArgs) -> BoxFuture<Result<EstimateFeeResponse>> {
+        log::debug!("estimate_fee method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let pending_tip = vm
+                .mempool
+                .read()
+                .await
+                .iter()
+                .map(|tx| tx.tip)
+                .max()
+                .unwrap_or_default();
+
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let fees = &state.genesis.fees;
+                return Ok(EstimateFeeResponse {
+                    fee: fees.fee_of(&args.action),
+                    pending_tip,
+                    treasury: fees.treasury,
+                });
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Offers a reward for the solution of a position"]
+    /// Write method
+    fn post_puzzle(&self, args: PostPuzzleArgs) -> BoxFuture<Result<PostPuzzleResponse>> {
+        log::debug!("post_puzzle method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            // Reject bad positions before they reach the mempool
+            starting_position(GameVariant::Standard, Some(&args.fen), 0)
+                .map_err(create_jsonrpc_error)?;
+            // As for challenges, the nonce only needs to differ between
+            // puzzles of the same poster
+            let nonce = args.auth.nonce;
+            let act = ActionType::PostPuzzle {
+                poster: args.poster,
+                fen: args.fen,
+                solution_hash: args.solution_hash,
+                reward: args.reward,
+                deadline: args.deadline,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.poster, args.auth).await?;
+            Ok(PostPuzzleResponse {
+                puzzle_id: calculate_puzzle_id(args.poster, nonce),
+                tx_id,
+            })
+        })
+    }
+
+    #[doc = r" Commits to the solution of a puzzle without revealing it"]
+    /// Write method
+    fn commit_solution(&self, args: CommitSolutionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("commit_solution method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::CommitSolution {
+                solver: args.solver,
+                puzzle_id: parse_id(&args.puzzle_id)?,
+                hash: args.hash,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.solver, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Reveals the solution committed to"]
+    /// Write method
+    fn reveal_solution(&self, args: RevealSolutionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("reveal_solution method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::RevealSolution {
+                solver: args.solver,
+                puzzle_id: parse_id(&args.puzzle_id)?,
+                moves: args.moves,
+                salt: args.salt,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.solver, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Get a puzzle and its commitments"]
+    /// Read method
+    fn get_puzzle(&self, args: GetPuzzleArgs) -> BoxFuture<Result<GetPuzzleResponse>> {
+        log::debug!("get_puzzle method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let puzzle_id = parse_id(&args.puzzle_id)?;
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                return match state.get_puzzle(puzzle_id).await {
+                    Some(puzzle) => Ok(GetPuzzleResponse {
+                        reveal_deadline: puzzle
+                            .deadline
+                            .saturating_add(state.genesis.puzzle_reveal_window),
+                        puzzle,
+                    }),
+                    None => Err(Error::invalid_params("puzzle not found")),
+                };
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Creates a team owned by the caller"]
+    /// Write method
+    fn create_team(&self, args: CreateTeamArgs) -> BoxFuture<Result<CreateTeamResponse>> {
+        log::debug!("create_team method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let nonce = args.auth.nonce;
+            let act = ActionType::CreateTeam {
+                owner: args.owner,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.owner, args.auth).await?;
+            Ok(CreateTeamResponse {
+                team_id: calculate_team_id(args.owner, nonce),
+                tx_id,
+            })
+        })
+    }
+
+    #[doc = r" Adds a member to a team"]
+    /// Write method
+    fn add_team_member(&self, args: TeamMemberArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("add_team_member method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::AddTeamMember {
+                owner: args.player,
+                team_id: parse_id(&args.team_id)?,
+                member: args.member,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Removes a member from a team, or leaves it"]
+    /// Write method
+    fn remove_team_member(&self, args: TeamMemberArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("remove_team_member method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::RemoveTeamMember {
+                player: args.player,
+                team_id: parse_id(&args.team_id)?,
+                member: args.member,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Get the owner and members of a team"]
+    /// Read method
+    fn get_team(&self, args: GetTeamArgs) -> BoxFuture<Result<GetTeamResponse>> {
+        log::debug!("get_team method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let team_id = parse_id(&args.team_id)?;
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                return match state.get_team(team_id).await {
+                    Some(team) => Ok(GetTeamResponse { team }),
+                    None => Err(Error::invalid_params("team not found")),
+                };
+            }
+
+            Err(Error {
+                code: ErrorCode::InternalError,
+                message: String::from("no state manager found"),
+                data: None,
+            })
+        })
+    }
+
+    #[doc = r" Starts a game between two teams voting on their moves"]
+    /// Write method
+    fn create_consultation_game(
+        &self,
+        args: CreateConsultationGameArgs,
+    ) -> BoxFuture<Result<CreateConsultationGameResponse>> {
+        log::debug!("create_consultation_game method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let nonce = args.auth.nonce;
+            let act = ActionType::CreateConsultationGame {
+                creator: args.creator,
+                white_team: parse_id(&args.white_team)?,
+                black_team: parse_id(&args.black_team)?,
+                variant: args.variant,
+                voting_window: args.voting_window,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.creator, args.auth).await?;
+            Ok(CreateConsultationGameResponse {
+                game_id: calculate_consultation_game_id(args.creator, nonce),
+                tx_id,
+            })
+        })
+    }
+
+    #[doc = r" Votes for the next move of a team in a consultation game"]
+    /// Write method
+    fn vote_move(&self, args: VoteMoveArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("vote_move method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::VoteMove {
+                voter: args.voter,
+                game_id: parse_id(&args.game_id)?,
+                mv: parse_move(args.mv, args.uci, args.san)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.voter, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Opens boards against a host for opponents to register"]
+    /// Write method
+    fn create_simul(&self, args: CreateSimulArgs) -> BoxFuture<Result<CreateSimulResponse>> {
+        log::debug!("create_simul method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let nonce = args.auth.nonce;
+            let act = ActionType::CreateSimul {
+                host: args.host,
+                boards: args.boards,
+                variant: args.variant,
+                time_control: args.time_control,
+                nonce,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.host, args.auth).await?;
+            Ok(CreateSimulResponse {
+                simul_id: calculate_simul_id(args.host, nonce),
+                tx_id,
+            })
+        })
+    }
+
+    #[doc = r" Registers a player for a board of a simul"]
+    /// Write method
+    fn register_for_simul(&self, args: SimulActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("register_for_simul method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::RegisterForSimul {
+                player: args.player,
+                simul_id: parse_id(&args.simul_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Withdraws a player from a simul before it starts"]
+    /// Write method
+    fn withdraw_from_simul(&self, args: SimulActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("withdraw_from_simul method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::WithdrawFromSimul {
+                player: args.player,
+                simul_id: parse_id(&args.simul_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Closes registration and creates the games of a simul"]
+    /// Write method
+    fn start_simul(&self, args: SimulActionArgs) -> BoxFuture<Result<TxResponse>> {
+        log::debug!("start_simul method called");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let act = ActionType::StartSimul {
+                host: args.player,
+                simul_id: parse_id(&args.simul_id)?,
+                block_id: ids::Id::empty(),
+            };
+            let tx_id = submit_action(&vm, act, args.player, args.auth).await?;
+            Ok(TxResponse { tx_id })
+        })
+    }
+
+    #[doc = r" Get a simul, its boards and the host's score"]
+    /// Read method
+    fn get_simul(&self, args: GetSimulArgs) -> BoxFuture<Result<GetSimulResponse>> {
+        log::debug!("get_simul method called!");
+        let vm = self.vm.clone();
+
+        Box::pin(async move {
+            let simul_id = parse_id(&args.simul_id)?;
+            let vm_state = vm.vm_state.read().await;
+            if let Some(state) = &vm_state.state {
+                let simul = match state.get_simul(simul_id).await {
+                    Some(simul) => simul,
+                    None => return Err(Error::invalid_params("simul not found")),
+                };
+                let boards = state.get_simul_boards(&simul).await;
+
+                return Ok(GetSimulResponse {
+                    score: simul::score(&boards),
+                    boards,
+                    simul,
                 });
             }
 
@@ -431,8 +2764,61 @@ fn create_jsonrpc_error<E: Borrow<std::io::Error>>(e: E) -> Error {
     error
 }
 
+/// Submits `action` to the mempool on behalf of `sender`, returning the ID of
+/// the transaction to look its receipt up with
+async fn submit_action<A>(
+    vm: &Vm<A>,
+    action: ActionType,
+    sender: Address,
+    auth: TxAuth,
+) -> Result<ids::Id>
+where
+    A: Send + Sync + Clone + 'static,
+{
+    let tx = Transaction {
+        action,
+        bytes: Vec::new(),
+        id: ids::Id::empty(),
+        size: 0,
+        sender,
+        tip: auth.tip,
+        nonce: auth.nonce,
+        signature: auth.signature,
+    };
+    let tx_id = tx.compute_id().map_err(create_jsonrpc_error)?;
+    vm.submit_tx(tx).await.map_err(create_jsonrpc_error)?;
+
+    Ok(tx_id)
+}
+
+/// Returns the account that signed `action`, for actions that do not name the
+/// account they are taken for
+fn recover_sender(action: &ActionType, auth: &TxAuth) -> Result<Address> {
+    let tx = Transaction {
+        action: action.clone(),
+        bytes: Vec::new(),
+        id: ids::Id::empty(),
+        size: 0,
+        sender: Address::ZERO,
+        tip: auth.tip,
+        nonce: auth.nonce,
+        signature: auth.signature.clone(),
+    };
+
+    tx.signer().map_err(create_jsonrpc_error)
+}
+
+/// Parses a game or challenge ID, which are passed as strings since they do
+/// not fit in a JSON number
+fn parse_id(id: &str) -> Result<u64> {
+    id.parse::<u64>()
+        .map_err(|e| Error::invalid_params(format!("invalid ID '{id}': {e}")))
+}
+
 #[tokio::test]
 async fn test_chess() {
+    use shakmaty::{Chess, Position};
+
     let _ = env_logger::builder()
         .filter_level(log::LevelFilter::Info)
         .is_test(true)
@@ -7,5 +7,8 @@ pub fn command() -> Command {
     Command::new(NAME)
         .about("Write a genesis file")
         .arg(arg!(<DATA> "Genesis message data"))
+        .arg(arg!(--arbiter <ADDRESS> "Address allowed to end any game"))
+        .arg(arg!(--"base-fee" <AMOUNT> "Fee charged for every transaction"))
+        .arg(arg!(--treasury <ADDRESS> "Address collecting the fees; they are burned if not set"))
         .arg_required_else_help(true)
 }
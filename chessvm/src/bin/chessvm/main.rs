@@ -1,8 +1,9 @@
 pub mod genesis;
 pub mod vm_id;
 
-use std::io;
+use std::{io, str::FromStr};
 
+use alloy_primitives::Address;
 use avalanche_types::subnet;
 use chessvm::vm;
 use clap::{crate_version, Command};
@@ -26,7 +27,25 @@ async fn main() -> io::Result<()> {
     match matches.subcommand() {
         Some((genesis::NAME, sub_matches)) => {
             let data = sub_matches.get_one::<String>("DATA").expect("required");
-            let genesis = chessvm::genesis::Genesis { data: data.clone() };
+            let arbiter = sub_matches
+                .get_one::<String>("arbiter")
+                .map(|a| Address::from_str(a).expect("invalid arbiter address"));
+            let fees = chessvm::genesis::FeeSchedule {
+                base_fee: sub_matches
+                    .get_one::<String>("base-fee")
+                    .map(|f| f.parse().expect("invalid base fee"))
+                    .unwrap_or_default(),
+                treasury: sub_matches
+                    .get_one::<String>("treasury")
+                    .map(|a| Address::from_str(a).expect("invalid treasury address")),
+                ..Default::default()
+            };
+            let genesis = chessvm::genesis::Genesis {
+                data: data.clone(),
+                arbiter,
+                fees,
+                ..Default::default()
+            };
             println!("{genesis}");
 
             Ok(())
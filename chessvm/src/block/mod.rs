@@ -3,6 +3,7 @@ use derivative::{self, Derivative};
 use serde::{Deserialize, Serialize};
 
 use std::{
+    collections::HashSet,
     fmt,
     io::{self, Error, ErrorKind},
 };
@@ -35,6 +36,9 @@ pub struct Block {
     // Transactions
     #[derivative(PartialEq = "ignore")]
     txs: Vec<tx::Transaction>,
+    /// Seeks paired by this block, as the IDs of the older and newer seek
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    seek_pairs: Vec<(u64, u64)>,
     /// Generated block Id.
     #[serde(skip)]
     id: ids::Id,
@@ -74,6 +78,10 @@ impl Block {
         self.txs.len()
     }
 
+    pub fn seek_pairs(&self) -> &[(u64, u64)] {
+        &self.seek_pairs
+    }
+
     /// Returns the status of this block.
     #[must_use]
     pub fn status(&self) -> choices::status::Status {
@@ -170,6 +178,38 @@ impl Block {
             ));
         }
 
+        // The block is checked against the state as of its parent, which
+        // may not be accepted yet
+        let prnt_state = self.state.state_at(&self.parent_id).await?;
+
+        // Transactions have to be signed by their sender, and can only be
+        // included once
+        let mut tx_ids = HashSet::new();
+        for tx in self.txs.iter() {
+            tx.verify_signature()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+            let tx_id = tx.compute_id()?;
+            if !tx_ids.insert(tx_id) || prnt_state.get_receipt(&tx_id).await.is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("transaction {tx_id} is included more than once"),
+                ));
+            }
+        }
+
+        // Seeks are paired by the block builder from the pool as of the
+        // parent block; every node pairs them again from the same pool
+        let seek_pairs = prnt_state.pair_seeks(self.timestamp).await;
+        if self.seek_pairs != seek_pairs {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "block pairs seeks {:?}, expected {:?}",
+                    self.seek_pairs, seek_pairs
+                ),
+            ));
+        }
+
         // add newly verified block to memory
         self.state.add_verified(&self.clone()).await;
         Ok(())
@@ -181,12 +221,19 @@ impl Block {
         self.height
     }
 
+    /// Returns the ID of the parent of this block.
+    #[must_use]
+    pub fn parent_id(&self) -> ids::Id {
+        self.parent_id
+    }
+
     pub fn try_new(
         parent_id: ids::Id,
         height: u64,
         timestamp: u64,
         message: String,
         txs: Vec<tx::Transaction>,
+        seek_pairs: Vec<(u64, u64)>,
         status: choices::status::Status,
     ) -> io::Result<Self> {
         let mut b = Self {
@@ -195,6 +242,7 @@ impl Block {
             timestamp,
             message,
             txs,
+            seek_pairs,
             ..Default::default()
         };
         b.status = status;
@@ -216,26 +264,95 @@ impl Block {
     pub async fn accept(&mut self) -> io::Result<()> {
         self.set_status(Status::Accepted);
 
+        self.apply(&self.state).await;
+
+        self.state.write_block(&self.clone()).await?;
+        self.state.set_last_accepted_block(&self.id()).await?;
+
+        self.state.remove_verified(&self.id()).await;
+
+        Ok(())
+    }
+
+    /// Applies the time based rules and the transactions of this block to
+    /// `state`, which is the state as of its parent
+    pub async fn apply(&self, state: &state::State) {
         // Construct TX context
         // TODO: Fix TX ID
         let tx_context = tx::TransactionContext {
-            state: self.state.clone(),
+            state: state.clone(),
             block_time: self.timestamp,
+            block_id: self.id,
+            block_height: self.height,
             tx_id: ids::Id::default(),
             sender: Address::default(),
         };
 
-        // Iterate over each transaction and execute
+        // Time based rules apply before the block's transactions
+        state.expire_pauses(self.timestamp).await;
+        state.flag_games(self.timestamp).await;
+        state.resolve_votes(tx_context.block_info()).await;
+        state
+            .start_seek_games(&self.seek_pairs, tx_context.block_info())
+            .await;
+        state.expire_seeks(self.timestamp).await;
+        state.close_puzzles(self.timestamp).await;
+        state.settle_games(self.timestamp).await;
+
+        // Iterate over each transaction and execute. A failing transaction
+        // does not fail the block; its error is recorded in its receipt. The
+        // fee is paid even if the transaction fails, and a transaction that is
+        // not signed by its sender, or whose sender cannot pay it, is not
+        // executed.
         for tx in self.txs.iter() {
-            tx.execute(tx_context.clone()).await?;
+            let tx_id = match tx.compute_id() {
+                Ok(id) => id,
+                Err(e) => {
+                    log::warn!("skipping transaction without an ID: {e}");
+                    continue;
+                }
+            };
+            // A transaction included again is not executed twice
+            if state.get_receipt(&tx_id).await.is_some() {
+                log::warn!("skipping transaction {tx_id}, which was already accepted");
+                continue;
+            }
+            let fee = tx.fee(&state.genesis.fees);
+            let paid = match tx.verify_signature() {
+                Ok(()) => state
+                    .pay_fee(tx.sender, fee)
+                    .await
+                    .map_err(|e| format!("cannot pay the fee of {fee}: {e}")),
+                Err(e) => Err(e.to_string()),
+            };
+            let (error, fee) = match paid {
+                Ok(()) => {
+                    let result = tx
+                        .execute(tx::TransactionContext {
+                            tx_id,
+                            sender: tx.sender,
+                            ..tx_context.clone()
+                        })
+                        .await;
+                    (result.err().map(|e| e.to_string()), fee)
+                }
+                Err(e) => (Some(e), 0),
+            };
+            if let Some(error) = &error {
+                log::warn!("transaction {tx_id} failed: {error}");
+            }
+
+            state
+                .put_receipt(state::Receipt {
+                    tx_id,
+                    block_id: self.id,
+                    block_height: self.height,
+                    error,
+                    fee,
+                })
+                .await;
+            state.settle_games(self.timestamp).await;
         }
-
-        self.state.write_block(&self.clone()).await?;
-        self.state.set_last_accepted_block(&self.id()).await?;
-
-        self.state.remove_verified(&self.id()).await;
-
-        Ok(())
     }
 
     /// Mark this [`Block`](Block) rejected and updates [`State`](crate::state::State) accordingly.
@@ -324,6 +441,7 @@ async fn test_block() {
         Utc::now().timestamp() as u64,
         String::from("Genesis Block!"),
         Vec::new(),
+        Vec::new(),
         choices::status::Status::default(),
     )
     .unwrap();
@@ -360,6 +478,9 @@ async fn test_block() {
     let action1 = tx::ActionType::CreateGame {
         white: Address::ZERO,
         black: Address::default(),
+        variant: state::variant::GameVariant::Standard,
+        fen: None,
+        nonce: 0,
         block_id: ids::Id::default(),
     };
     let blk_tx = tx::Transaction {
@@ -368,6 +489,9 @@ async fn test_block() {
         id: ids::Id::default(),
         size: 0,
         sender: Address::default(),
+        tip: 0,
+        nonce: 0,
+        signature: String::new(),
     };
     let mut blk1 = Block::try_new(
         genesis_blk.id,
@@ -375,6 +499,7 @@ async fn test_block() {
         genesis_blk.timestamp + 1,
         String::from("first block!"),
         vec![blk_tx],
+        Vec::new(),
         choices::status::Status::default(),
     )
     .unwrap();
@@ -387,5 +512,124 @@ async fn test_block() {
     log::info!(
         "deserialized blk1: {deserialized_blk1} (block id: {})",
         deserialized_blk1.id
+    );
+
+    // Blocks can be verified on top of verified blocks that are not
+    // accepted yet, but not on top of unknown ones
+    let mut blk2 = Block::try_new(
+        genesis_blk.id,
+        genesis_blk.height + 1,
+        genesis_blk.timestamp + 1,
+        String::from("second block!"),
+        Vec::new(),
+        Vec::new(),
+        choices::status::Status::default(),
+    )
+    .unwrap();
+    blk2.set_state(state.clone());
+    blk2.verify().await.unwrap();
+    let mut blk3 = Block::try_new(
+        blk2.id,
+        blk2.height + 1,
+        blk2.timestamp + 1,
+        String::from("third block!"),
+        Vec::new(),
+        Vec::new(),
+        choices::status::Status::default(),
+    )
+    .unwrap();
+    blk3.set_state(state.clone());
+    blk3.verify().await.unwrap();
+    assert!(state.has_verified(&blk3.id()).await);
+
+    let mut orphan_blk = Block::try_new(
+        blk1.id,
+        blk1.height + 1,
+        blk1.timestamp + 1,
+        String::from("orphan block!"),
+        Vec::new(),
+        Vec::new(),
+        choices::status::Status::default(),
     )
+    .unwrap();
+    orphan_blk.set_state(state.clone());
+    assert!(orphan_blk.verify().await.is_err());
+}
+
+#[tokio::test]
+async fn test_replay_accepted_blocks() {
+    use crate::genesis::Genesis;
+    use avalanche_types::key::secp256k1::private_key;
+    use std::{collections::BTreeMap, sync::Arc};
+
+    let key = private_key::Key::from_bytes(&[1; 32]).unwrap();
+    let alice = Address::from_slice(key.to_public_key().to_h160().as_bytes());
+    let bob = Address::repeat_byte(2);
+    let genesis = Arc::new(Genesis {
+        allocations: BTreeMap::from([(alice, 100)]),
+        ..Default::default()
+    });
+    let new_state = |db| state::State {
+        db,
+        ledger: Arc::new(tokio::sync::RwLock::new(
+            state::ledger::Ledger::from_allocations(&genesis.allocations),
+        )),
+        genesis: genesis.clone(),
+        ..Default::default()
+    };
+    let state = new_state(state::State::default().db);
+
+    let mut genesis_blk = Block::try_new(
+        ids::Id::empty(),
+        0,
+        0,
+        String::from("Genesis Block!"),
+        Vec::new(),
+        Vec::new(),
+        choices::status::Status::default(),
+    )
+    .unwrap();
+    genesis_blk.set_state(state.clone());
+    genesis_blk.accept().await.unwrap();
+
+    let mut transfer = tx::Transaction {
+        action: tx::ActionType::Transfer {
+            to: bob,
+            amount: 30,
+            block_id: ids::Id::empty(),
+        },
+        bytes: Vec::new(),
+        id: ids::Id::empty(),
+        size: 0,
+        sender: alice,
+        tip: 0,
+        nonce: 0,
+        signature: String::new(),
+    };
+    transfer.sign(&key).unwrap();
+    let mut blk = Block::try_new(
+        genesis_blk.id(),
+        1,
+        1,
+        String::from("first block!"),
+        vec![transfer],
+        Vec::new(),
+        choices::status::Status::default(),
+    )
+    .unwrap();
+    blk.set_state(state.clone());
+    blk.verify().await.unwrap();
+    blk.accept().await.unwrap();
+    assert_eq!(state.get_balance(bob).await, 30);
+
+    // A restarted node only finds the blocks in its database
+    let restarted = new_state(state.db.clone());
+    assert_eq!(restarted.get_balance(bob).await, 0);
+    restarted.replay_accepted_blocks().await.unwrap();
+    assert_eq!(restarted.get_balance(alice).await, 70);
+    assert_eq!(restarted.get_balance(bob).await, 30);
+    assert_eq!(
+        restarted.receipts.read().await.len(),
+        state.receipts.read().await.len()
+    );
 }
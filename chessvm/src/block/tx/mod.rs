@@ -3,11 +3,25 @@ use std::{
     io::{self, Error, ErrorKind},
 };
 
-use crate::{api::chain_handlers, state};
-use alloy_primitives::Address;
-use avalanche_types::ids;
+use crate::{
+    api::chain_handlers,
+    genesis::FeeSchedule,
+    pgn,
+    state::{
+        self,
+        clock::TimeControl,
+        puzzle::Puzzle,
+        simul::Simul,
+        team::Consultation,
+        tournament::{Tiebreak, Tournament, TournamentFormat},
+        variant::{starting_position, GameVariant},
+        ColorPreference, ConditionalMoves, GameState, RatingRange,
+    },
+};
+use alloy_primitives::{Address, B256};
+use avalanche_types::{ids, key::secp256k1::private_key};
 use serde::{Deserialize, Serialize};
-use shakmaty::{Move, Role, Square};
+use shakmaty::{san::SanPlus, uci::Uci, variant::VariantPosition, Move, Role, Square};
 
 // pub mod action;
 // pub mod create_game;
@@ -79,8 +93,29 @@ fn convert_castle_move(king: String, rook: String) -> io::Result<Move> {
     })
 }
 
-pub fn convert_move(mv: chain_handlers::MoveEnum) -> io::Result<Move> {
+fn convert_uci_move(uci: String, pos: &VariantPosition) -> io::Result<Move> {
+    let uci = Uci::from_ascii(uci.as_bytes())
+        .map_err(|e| Error::other(format!("could not parse UCI move '{uci}': {e}")))?;
+    uci.to_move(pos)
+        .map_err(|e| Error::other(format!("UCI move '{uci}' is not legal: {e}")))
+}
+
+fn convert_san_move(san: String, pos: &VariantPosition) -> io::Result<Move> {
+    // Check and checkmate suffixes are accepted but not required
+    let san_plus = SanPlus::from_ascii(san.as_bytes())
+        .map_err(|e| Error::other(format!("could not parse SAN move '{san}': {e}")))?;
+    san_plus
+        .san
+        .to_move(pos)
+        .map_err(|e| Error::other(format!("SAN move '{san}' is not legal: {e}")))
+}
+
+/// Converts a move to its shakmaty representation. UCI and SAN moves are
+/// resolved against `pos`, the position they are played in.
+pub fn convert_move(mv: chain_handlers::MoveEnum, pos: &VariantPosition) -> io::Result<Move> {
     match mv {
+        chain_handlers::MoveEnum::Uci(uci) => convert_uci_move(uci, pos),
+        chain_handlers::MoveEnum::San(san) => convert_san_move(san, pos),
         chain_handlers::MoveEnum::Normal {
             role,
             from,
@@ -98,8 +133,15 @@ pub enum ActionType {
     CreateGame {
         white: Address,
         black: Address,
+        #[serde(default)]
+        variant: GameVariant,
+        /// Starting position in FEN; the variant's initial one if not given
+        #[serde(default)]
+        fen: Option<String>,
+        nonce: u64,
         block_id: ids::Id,
     },
+    /// Aborts or ends a game on behalf of the sender
     EndGame {
         game_id: u64,
         block_id: ids::Id,
@@ -110,6 +152,251 @@ pub enum ActionType {
         mv: chain_handlers::MoveEnum,
         block_id: ids::Id,
     },
+    CreateChallenge {
+        challenger: Address,
+        opponent: Option<Address>,
+        color: ColorPreference,
+        time_control: Option<TimeControl>,
+        #[serde(default)]
+        variant: GameVariant,
+        /// Amount each player wagers, locked when the challenge is accepted
+        #[serde(default)]
+        stake: u64,
+        nonce: u64,
+        block_id: ids::Id,
+    },
+    AcceptChallenge {
+        player: Address,
+        challenge_id: u64,
+        block_id: ids::Id,
+    },
+    CancelChallenge {
+        player: Address,
+        challenge_id: u64,
+        block_id: ids::Id,
+    },
+    /// Asks the opponent for the last `plies` moves to be undone
+    RequestTakeback {
+        player: Address,
+        game_id: u64,
+        plies: usize,
+        block_id: ids::Id,
+    },
+    AcceptTakeback {
+        player: Address,
+        game_id: u64,
+        block_id: ids::Id,
+    },
+    /// Sets up replies to the opponent's next moves
+    SetConditionalMoves {
+        player: Address,
+        game_id: u64,
+        moves: ConditionalMoves,
+        block_id: ids::Id,
+    },
+    /// Asks the opponent to freeze the clocks
+    RequestPause {
+        player: Address,
+        game_id: u64,
+        block_id: ids::Id,
+    },
+    AcceptPause {
+        player: Address,
+        game_id: u64,
+        block_id: ids::Id,
+    },
+    /// Restarts the clocks of a paused game
+    Resume {
+        player: Address,
+        game_id: u64,
+        block_id: ids::Id,
+    },
+    /// Offers to play a finished game again with colors swapped
+    OfferRematch {
+        player: Address,
+        game_id: u64,
+        block_id: ids::Id,
+    },
+    AcceptRematch {
+        player: Address,
+        game_id: u64,
+        block_id: ids::Id,
+    },
+    /// Registers a human readable name for the player's address
+    RegisterName {
+        player: Address,
+        name: String,
+        block_id: ids::Id,
+    },
+    TransferName {
+        player: Address,
+        name: String,
+        to: Address,
+        block_id: ids::Id,
+    },
+    ReleaseName {
+        player: Address,
+        name: String,
+        block_id: ids::Id,
+    },
+    CreateTournament {
+        organizer: Address,
+        format: TournamentFormat,
+        rounds: usize,
+        #[serde(default)]
+        variant: GameVariant,
+        #[serde(default)]
+        time_control: Option<TimeControl>,
+        registration_deadline: u64,
+        /// How drawn knockout matches are decided
+        #[serde(default)]
+        tiebreak: Tiebreak,
+        nonce: u64,
+        block_id: ids::Id,
+    },
+    RegisterForTournament {
+        player: Address,
+        tournament_id: u64,
+        block_id: ids::Id,
+    },
+    WithdrawFromTournament {
+        player: Address,
+        tournament_id: u64,
+        block_id: ids::Id,
+    },
+    /// Pairs the next round of a tournament and creates its games
+    StartRound {
+        organizer: Address,
+        tournament_id: u64,
+        block_id: ids::Id,
+    },
+    /// Opens `boards` boards against the host, who plays white on each
+    CreateSimul {
+        host: Address,
+        boards: usize,
+        #[serde(default)]
+        variant: GameVariant,
+        #[serde(default)]
+        time_control: Option<TimeControl>,
+        nonce: u64,
+        block_id: ids::Id,
+    },
+    RegisterForSimul {
+        player: Address,
+        simul_id: u64,
+        block_id: ids::Id,
+    },
+    WithdrawFromSimul {
+        player: Address,
+        simul_id: u64,
+        block_id: ids::Id,
+    },
+    /// Closes registration and creates the games of a simul
+    StartSimul {
+        host: Address,
+        simul_id: u64,
+        block_id: ids::Id,
+    },
+    /// Looks for any opponent, to be paired when a block is built
+    PostSeek {
+        player: Address,
+        time_control: Option<TimeControl>,
+        #[serde(default)]
+        variant: GameVariant,
+        #[serde(default)]
+        rating_range: Option<RatingRange>,
+        nonce: u64,
+        block_id: ids::Id,
+    },
+    CancelSeek {
+        player: Address,
+        seek_id: u64,
+        block_id: ids::Id,
+    },
+    /// Offers `reward` for the solution of a position
+    PostPuzzle {
+        poster: Address,
+        fen: String,
+        solution_hash: B256,
+        reward: u64,
+        deadline: u64,
+        nonce: u64,
+        block_id: ids::Id,
+    },
+    /// Commits to a solution without revealing it
+    CommitSolution {
+        solver: Address,
+        puzzle_id: u64,
+        hash: B256,
+        block_id: ids::Id,
+    },
+    /// Reveals the solution committed to, in UCI notation
+    RevealSolution {
+        solver: Address,
+        puzzle_id: u64,
+        moves: Vec<String>,
+        salt: String,
+        block_id: ids::Id,
+    },
+    /// Creates a team owned by `owner`
+    CreateTeam {
+        owner: Address,
+        nonce: u64,
+        block_id: ids::Id,
+    },
+    AddTeamMember {
+        owner: Address,
+        team_id: u64,
+        member: Address,
+        block_id: ids::Id,
+    },
+    /// Removes a member, or leaves the team if `member` is the player
+    RemoveTeamMember {
+        player: Address,
+        team_id: u64,
+        member: Address,
+        block_id: ids::Id,
+    },
+    /// Starts a game between two teams voting on their moves
+    CreateConsultationGame {
+        creator: Address,
+        white_team: u64,
+        black_team: u64,
+        #[serde(default)]
+        variant: GameVariant,
+        /// Seconds each side votes before its most voted move is played
+        voting_window: u64,
+        nonce: u64,
+        block_id: ids::Id,
+    },
+    /// Votes for the next move of the voter's team in a consultation game
+    VoteMove {
+        voter: Address,
+        game_id: u64,
+        mv: chain_handlers::MoveEnum,
+        block_id: ids::Id,
+    },
+    /// Moves funds from the sender's account to another
+    Transfer {
+        to: Address,
+        amount: u64,
+        block_id: ids::Id,
+    },
+    /// Records a game played outside of ChessVM, approved by both players
+    ImportGame {
+        white: Address,
+        black: Address,
+        #[serde(default)]
+        variant: GameVariant,
+        #[serde(default)]
+        fen: Option<String>,
+        /// PGN movetext, ending with the game result
+        movetext: String,
+        /// Signatures of [`pgn::import_message`] by each player
+        white_signature: String,
+        black_signature: String,
+        block_id: ids::Id,
+    },
     Unknown,
 }
 
@@ -117,10 +404,23 @@ pub enum ActionType {
 pub struct TransactionContext {
     pub state: state::State,
     pub block_time: u64,
+    pub block_id: ids::Id,
+    pub block_height: u64,
     pub tx_id: ids::Id,
     pub sender: Address,
 }
 
+impl TransactionContext {
+    /// Returns the provenance of state changes made by this transaction
+    pub fn block_info(&self) -> state::BlockInfo {
+        state::BlockInfo {
+            id: self.block_id,
+            height: self.block_height,
+            timestamp: self.block_time,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 // #[derivative(Debug, PartialEq, Eq)]
 pub struct Transaction {
@@ -135,59 +435,202 @@ pub struct Transaction {
     #[serde(skip)]
     pub size: u64,
 
-    #[serde(skip)]
+    /// Account paying the fee
+    #[serde(default)]
     pub sender: Address,
+
+    /// Paid on top of the fee for the transaction to be included first
+    #[serde(default)]
+    pub tip: u64,
+
+    /// Chosen by the sender, so that the same action can be sent again as a
+    /// different transaction
+    #[serde(default)]
+    pub nonce: u64,
+
+    /// Sender's signature of the [`signing message`](Transaction::signing_message)
+    #[serde(default)]
+    pub signature: String,
 }
 
-impl Transaction {
-    async fn get_block_id(&self) -> ids::Id {
-        match &self.action {
-            ActionType::Unknown => ids::Id::default(),
-            ActionType::EndGame { game_id, block_id } => block_id.clone(),
-            ActionType::MakeMove {
-                player,
-                game_id,
-                mv,
-                block_id,
-            } => block_id.clone(),
-            ActionType::CreateGame {
-                white,
-                black,
-                block_id,
-            } => block_id.clone(),
+impl ActionType {
+    /// Returns the name of the action, which fees are set by
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActionType::CreateGame { .. } => "CreateGame",
+            ActionType::EndGame { .. } => "EndGame",
+            ActionType::MakeMove { .. } => "MakeMove",
+            ActionType::CreateChallenge { .. } => "CreateChallenge",
+            ActionType::AcceptChallenge { .. } => "AcceptChallenge",
+            ActionType::CancelChallenge { .. } => "CancelChallenge",
+            ActionType::RequestTakeback { .. } => "RequestTakeback",
+            ActionType::AcceptTakeback { .. } => "AcceptTakeback",
+            ActionType::SetConditionalMoves { .. } => "SetConditionalMoves",
+            ActionType::RequestPause { .. } => "RequestPause",
+            ActionType::AcceptPause { .. } => "AcceptPause",
+            ActionType::Resume { .. } => "Resume",
+            ActionType::OfferRematch { .. } => "OfferRematch",
+            ActionType::AcceptRematch { .. } => "AcceptRematch",
+            ActionType::RegisterName { .. } => "RegisterName",
+            ActionType::TransferName { .. } => "TransferName",
+            ActionType::ReleaseName { .. } => "ReleaseName",
+            ActionType::CreateTournament { .. } => "CreateTournament",
+            ActionType::RegisterForTournament { .. } => "RegisterForTournament",
+            ActionType::WithdrawFromTournament { .. } => "WithdrawFromTournament",
+            ActionType::StartRound { .. } => "StartRound",
+            ActionType::CreateSimul { .. } => "CreateSimul",
+            ActionType::RegisterForSimul { .. } => "RegisterForSimul",
+            ActionType::WithdrawFromSimul { .. } => "WithdrawFromSimul",
+            ActionType::StartSimul { .. } => "StartSimul",
+            ActionType::PostSeek { .. } => "PostSeek",
+            ActionType::CancelSeek { .. } => "CancelSeek",
+            ActionType::PostPuzzle { .. } => "PostPuzzle",
+            ActionType::CommitSolution { .. } => "CommitSolution",
+            ActionType::RevealSolution { .. } => "RevealSolution",
+            ActionType::CreateTeam { .. } => "CreateTeam",
+            ActionType::AddTeamMember { .. } => "AddTeamMember",
+            ActionType::RemoveTeamMember { .. } => "RemoveTeamMember",
+            ActionType::CreateConsultationGame { .. } => "CreateConsultationGame",
+            ActionType::VoteMove { .. } => "VoteMove",
+            ActionType::Transfer { .. } => "Transfer",
+            ActionType::ImportGame { .. } => "ImportGame",
+            ActionType::Unknown => "Unknown",
         }
     }
 
-    async fn set_block_id(&mut self, id: ids::Id) {
-        match &mut self.action {
-            ActionType::CreateGame {
-                white,
-                black,
-                block_id,
-            } => *block_id = id,
-            ActionType::EndGame { game_id, block_id } => *block_id = id,
-            ActionType::MakeMove {
-                player,
-                game_id,
-                mv,
-                block_id,
-            } => *block_id = id,
-            _ => return,
+    /// Returns the account the action is taken on behalf of, which has to be
+    /// the sender of the transaction. Transfers and ended games always act for
+    /// the sender, and imported games are approved by the signatures of both
+    /// players instead.
+    pub fn actor(&self) -> Option<Address> {
+        match self {
+            ActionType::CreateGame { white, .. } => Some(*white),
+            ActionType::CreateChallenge { challenger, .. } => Some(*challenger),
+            ActionType::CreateTournament { organizer, .. }
+            | ActionType::StartRound { organizer, .. } => Some(*organizer),
+            ActionType::CreateSimul { host, .. } | ActionType::StartSimul { host, .. } => {
+                Some(*host)
+            }
+            ActionType::PostPuzzle { poster, .. } => Some(*poster),
+            ActionType::CommitSolution { solver, .. }
+            | ActionType::RevealSolution { solver, .. } => Some(*solver),
+            ActionType::CreateTeam { owner, .. } | ActionType::AddTeamMember { owner, .. } => {
+                Some(*owner)
+            }
+            ActionType::CreateConsultationGame { creator, .. } => Some(*creator),
+            ActionType::VoteMove { voter, .. } => Some(*voter),
+            ActionType::MakeMove { player, .. }
+            | ActionType::AcceptChallenge { player, .. }
+            | ActionType::CancelChallenge { player, .. }
+            | ActionType::RequestTakeback { player, .. }
+            | ActionType::AcceptTakeback { player, .. }
+            | ActionType::SetConditionalMoves { player, .. }
+            | ActionType::RequestPause { player, .. }
+            | ActionType::AcceptPause { player, .. }
+            | ActionType::Resume { player, .. }
+            | ActionType::OfferRematch { player, .. }
+            | ActionType::AcceptRematch { player, .. }
+            | ActionType::RegisterName { player, .. }
+            | ActionType::TransferName { player, .. }
+            | ActionType::ReleaseName { player, .. }
+            | ActionType::RegisterForTournament { player, .. }
+            | ActionType::WithdrawFromTournament { player, .. }
+            | ActionType::RegisterForSimul { player, .. }
+            | ActionType::WithdrawFromSimul { player, .. }
+            | ActionType::PostSeek { player, .. }
+            | ActionType::CancelSeek { player, .. }
+            | ActionType::RemoveTeamMember { player, .. } => Some(*player),
+            ActionType::EndGame { .. }
+            | ActionType::Transfer { .. }
+            | ActionType::ImportGame { .. }
+            | ActionType::Unknown => None,
+        }
+    }
+}
+
+impl Transaction {
+    /// Returns the fee paid by the sender for this transaction, tip included
+    pub fn fee(&self, fees: &FeeSchedule) -> u64 {
+        fees.fee_of(self.action.name()).saturating_add(self.tip)
+    }
+
+    /// Returns the message signed by the sender: the action, the tip and the
+    /// nonce. The sender is left out, as it is recovered from the signature.
+    /// # Errors
+    /// Fails if the action can't be serialized
+    pub fn signing_message(&self) -> io::Result<String> {
+        serde_json::to_string(&(&self.action, self.tip, self.nonce))
+            .map_err(|e| Error::other(format!("failed to serialize transaction {e}")))
+    }
+
+    /// Returns the ID of the transaction, derived from its action, sender, tip
+    /// and nonce
+    /// # Errors
+    /// Fails if the action can't be serialized
+    pub fn compute_id(&self) -> io::Result<ids::Id> {
+        let encoded = serde_json::to_string(&(&self.action, self.sender, self.tip, self.nonce))
+            .map_err(|e| Error::other(format!("failed to serialize transaction {e}")))?;
+
+        Ok(ids::Id::sha256(encoded))
+    }
+
+    /// Makes the owner of `key` the sender of the transaction and signs it
+    pub fn sign(&mut self, key: &private_key::Key) -> io::Result<()> {
+        self.sender = Address::from_slice(key.to_public_key().to_h160().as_bytes());
+        self.signature = pgn::sign_message(key, &self.signing_message()?)?;
+
+        Ok(())
+    }
+
+    /// Returns the account that signed the transaction
+    pub fn signer(&self) -> io::Result<Address> {
+        pgn::recover_signer(&self.signing_message()?, &self.signature)
+    }
+
+    /// Checks that the transaction was signed by its sender
+    pub fn verify_signature(&self) -> io::Result<()> {
+        if self.signer()? != self.sender {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "transaction is not signed by its sender",
+            ));
         }
+
+        Ok(())
     }
 
     pub async fn execute(&self, tx_context: TransactionContext) -> io::Result<()> {
+        if let Some(actor) = self.action.actor() {
+            if actor != tx_context.sender {
+                return Err(Error::new(
+                    ErrorKind::PermissionDenied,
+                    format!("{actor} did not send this transaction"),
+                ));
+            }
+        }
+
         match &self.action {
             ActionType::Unknown => Ok(()),
             ActionType::CreateGame {
                 white,
                 black,
+                variant,
+                fen,
+                nonce,
                 block_id,
             } => {
-                create_game(tx_context, white.clone(), black.clone()).await?;
+                create_game(
+                    tx_context,
+                    white.clone(),
+                    black.clone(),
+                    *variant,
+                    fen.clone(),
+                    *nonce,
+                )
+                .await?;
                 Ok(())
             }
-            ActionType::EndGame { game_id, block_id } => {
+            ActionType::EndGame { game_id, .. } => {
                 end_game(tx_context, *game_id).await?;
                 Ok(())
             }
@@ -200,39 +643,1240 @@ impl Transaction {
                 make_move(tx_context, player.clone(), *game_id, mv.clone()).await?;
                 Ok(())
             }
-        }
-    }
-
-    async fn typ(&self) -> ActionType {
-        self.action.clone()
-    }
-}
-
-pub async fn create_game(
-    tx_context: TransactionContext,
-    white: Address,
-    black: Address,
-) -> io::Result<()> {
-    // Create game
-    tx_context.state.create_new_game(white, black).await?;
-
-    Ok(())
-}
-
-pub async fn end_game(tx_context: TransactionContext, game_id: u64) -> io::Result<()> {
-    tx_context.state.end_game(game_id).await?;
-
-    Ok(())
-}
-
-pub async fn make_move(
-    tx_context: TransactionContext,
-    player: Address,
-    game_id: u64,
-    mv: chain_handlers::MoveEnum,
-) -> io::Result<()> {
-    let mv = convert_move(mv)?;
-    tx_context.state.make_move(player, game_id, &mv).await?;
-
-    Ok(())
+            ActionType::CreateChallenge {
+                challenger,
+                opponent,
+                color,
+                time_control,
+                variant,
+                stake,
+                nonce,
+                ..
+            } => {
+                let challenge = state::Challenge {
+                    challenger: *challenger,
+                    opponent: *opponent,
+                    color: *color,
+                    time_control: *time_control,
+                    variant: *variant,
+                    stake: *stake,
+                    created_at: tx_context.block_time,
+                };
+                create_challenge(tx_context, challenge, *nonce).await?;
+                Ok(())
+            }
+            ActionType::AcceptChallenge {
+                player,
+                challenge_id,
+                ..
+            } => {
+                accept_challenge(tx_context, *player, *challenge_id).await?;
+                Ok(())
+            }
+            ActionType::CancelChallenge {
+                player,
+                challenge_id,
+                ..
+            } => {
+                cancel_challenge(tx_context, *player, *challenge_id).await?;
+                Ok(())
+            }
+            ActionType::RequestTakeback {
+                player,
+                game_id,
+                plies,
+                ..
+            } => {
+                request_takeback(tx_context, *player, *game_id, *plies).await?;
+                Ok(())
+            }
+            ActionType::AcceptTakeback {
+                player, game_id, ..
+            } => {
+                accept_takeback(tx_context, *player, *game_id).await?;
+                Ok(())
+            }
+            ActionType::SetConditionalMoves {
+                player,
+                game_id,
+                moves,
+                ..
+            } => {
+                set_conditional_moves(tx_context, *player, *game_id, moves).await?;
+                Ok(())
+            }
+            ActionType::RequestPause {
+                player, game_id, ..
+            } => {
+                request_pause(tx_context, *player, *game_id).await?;
+                Ok(())
+            }
+            ActionType::AcceptPause {
+                player, game_id, ..
+            } => {
+                accept_pause(tx_context, *player, *game_id).await?;
+                Ok(())
+            }
+            ActionType::Resume {
+                player, game_id, ..
+            } => {
+                resume(tx_context, *player, *game_id).await?;
+                Ok(())
+            }
+            ActionType::OfferRematch {
+                player, game_id, ..
+            } => {
+                offer_rematch(tx_context, *player, *game_id).await?;
+                Ok(())
+            }
+            ActionType::AcceptRematch {
+                player, game_id, ..
+            } => {
+                accept_rematch(tx_context, *player, *game_id).await?;
+                Ok(())
+            }
+            ActionType::RegisterName { player, name, .. } => {
+                register_name(tx_context, *player, name).await?;
+                Ok(())
+            }
+            ActionType::TransferName {
+                player, name, to, ..
+            } => {
+                transfer_name(tx_context, *player, name, *to).await?;
+                Ok(())
+            }
+            ActionType::ReleaseName { player, name, .. } => {
+                release_name(tx_context, *player, name).await?;
+                Ok(())
+            }
+            ActionType::CreateTournament {
+                organizer,
+                format,
+                rounds,
+                variant,
+                time_control,
+                registration_deadline,
+                tiebreak,
+                nonce,
+                ..
+            } => {
+                let tournament = Tournament {
+                    organizer: *organizer,
+                    format: *format,
+                    rounds: *rounds,
+                    variant: *variant,
+                    time_control: *time_control,
+                    registration_deadline: *registration_deadline,
+                    players: Vec::new(),
+                    withdrawn: Vec::new(),
+                    pairings: Vec::new(),
+                    tiebreak: *tiebreak,
+                    bracket: Vec::new(),
+                    created_at: tx_context.block_time,
+                };
+                create_tournament(tx_context, tournament, *nonce).await?;
+                Ok(())
+            }
+            ActionType::RegisterForTournament {
+                player,
+                tournament_id,
+                ..
+            } => {
+                register_for_tournament(tx_context, *player, *tournament_id).await?;
+                Ok(())
+            }
+            ActionType::WithdrawFromTournament {
+                player,
+                tournament_id,
+                ..
+            } => {
+                withdraw_from_tournament(tx_context, *player, *tournament_id).await?;
+                Ok(())
+            }
+            ActionType::StartRound {
+                organizer,
+                tournament_id,
+                ..
+            } => {
+                start_round(tx_context, *organizer, *tournament_id).await?;
+                Ok(())
+            }
+            ActionType::PostSeek {
+                player,
+                time_control,
+                variant,
+                rating_range,
+                nonce,
+                ..
+            } => {
+                post_seek(
+                    tx_context,
+                    *player,
+                    *time_control,
+                    *variant,
+                    *rating_range,
+                    *nonce,
+                )
+                .await?;
+                Ok(())
+            }
+            ActionType::CancelSeek {
+                player, seek_id, ..
+            } => {
+                cancel_seek(tx_context, *player, *seek_id).await?;
+                Ok(())
+            }
+            ActionType::PostPuzzle {
+                poster,
+                fen,
+                solution_hash,
+                reward,
+                deadline,
+                nonce,
+                ..
+            } => {
+                let puzzle = Puzzle {
+                    poster: *poster,
+                    fen: fen.clone(),
+                    solution_hash: *solution_hash,
+                    reward: *reward,
+                    deadline: *deadline,
+                    commitments: Vec::new(),
+                    winner: None,
+                    closed: false,
+                    created_at: tx_context.block_time,
+                };
+                post_puzzle(tx_context, puzzle, *nonce).await?;
+                Ok(())
+            }
+            ActionType::CommitSolution {
+                solver,
+                puzzle_id,
+                hash,
+                ..
+            } => {
+                commit_solution(tx_context, *solver, *puzzle_id, *hash).await?;
+                Ok(())
+            }
+            ActionType::RevealSolution {
+                solver,
+                puzzle_id,
+                moves,
+                salt,
+                ..
+            } => {
+                reveal_solution(tx_context, *solver, *puzzle_id, moves, salt).await?;
+                Ok(())
+            }
+            ActionType::CreateSimul {
+                host,
+                boards,
+                variant,
+                time_control,
+                nonce,
+                ..
+            } => {
+                let simul = Simul {
+                    host: *host,
+                    boards: *boards,
+                    variant: *variant,
+                    time_control: *time_control,
+                    players: Vec::new(),
+                    game_ids: Vec::new(),
+                    created_at: tx_context.block_time,
+                };
+                create_simul(tx_context, simul, *nonce).await?;
+                Ok(())
+            }
+            ActionType::RegisterForSimul {
+                player, simul_id, ..
+            } => {
+                register_for_simul(tx_context, *player, *simul_id).await?;
+                Ok(())
+            }
+            ActionType::WithdrawFromSimul {
+                player, simul_id, ..
+            } => {
+                withdraw_from_simul(tx_context, *player, *simul_id).await?;
+                Ok(())
+            }
+            ActionType::StartSimul { host, simul_id, .. } => {
+                start_simul(tx_context, *host, *simul_id).await?;
+                Ok(())
+            }
+            ActionType::CreateTeam { owner, nonce, .. } => {
+                create_team(tx_context, *owner, *nonce).await?;
+                Ok(())
+            }
+            ActionType::AddTeamMember {
+                owner,
+                team_id,
+                member,
+                ..
+            } => {
+                add_team_member(tx_context, *owner, *team_id, *member).await?;
+                Ok(())
+            }
+            ActionType::RemoveTeamMember {
+                player,
+                team_id,
+                member,
+                ..
+            } => {
+                remove_team_member(tx_context, *player, *team_id, *member).await?;
+                Ok(())
+            }
+            ActionType::CreateConsultationGame {
+                creator,
+                white_team,
+                black_team,
+                variant,
+                voting_window,
+                nonce,
+                ..
+            } => {
+                let consultation = Consultation {
+                    white_team: *white_team,
+                    black_team: *black_team,
+                    voting_window: *voting_window,
+                    votes: Default::default(),
+                };
+                create_consultation_game(tx_context, *creator, consultation, *variant, *nonce)
+                    .await?;
+                Ok(())
+            }
+            ActionType::VoteMove {
+                voter, game_id, mv, ..
+            } => {
+                vote_move(tx_context, *voter, *game_id, mv.clone()).await?;
+                Ok(())
+            }
+            ActionType::Transfer { to, amount, .. } => {
+                transfer(tx_context, *to, *amount).await?;
+                Ok(())
+            }
+            ActionType::ImportGame {
+                white,
+                black,
+                variant,
+                fen,
+                movetext,
+                white_signature,
+                black_signature,
+                ..
+            } => {
+                verify_import(
+                    *white,
+                    *black,
+                    *variant,
+                    fen.as_deref(),
+                    movetext,
+                    [white_signature, black_signature],
+                )?;
+                import_game(
+                    tx_context,
+                    *white,
+                    *black,
+                    *variant,
+                    fen.as_deref(),
+                    movetext,
+                )
+                .await?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn typ(&self) -> ActionType {
+        self.action.clone()
+    }
+}
+
+pub async fn create_game(
+    tx_context: TransactionContext,
+    white: Address,
+    black: Address,
+    variant: GameVariant,
+    fen: Option<String>,
+    nonce: u64,
+) -> io::Result<()> {
+    // Create game
+    tx_context
+        .state
+        .create_new_game(
+            white,
+            black,
+            variant,
+            fen.as_deref(),
+            nonce,
+            tx_context.block_time,
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn end_game(tx_context: TransactionContext, game_id: u64) -> io::Result<()> {
+    tx_context
+        .state
+        .end_game(tx_context.sender, game_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn make_move(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+    mv: chain_handlers::MoveEnum,
+) -> io::Result<()> {
+    let pos = match tx_context.state.get_game(game_id).await {
+        Some(pos) => pos,
+        None => return Err(Error::other("Game does not exist!")),
+    };
+    let mv = convert_move(mv, &pos)?;
+    tx_context
+        .state
+        .make_move(player, game_id, &mv, tx_context.block_info())
+        .await?;
+
+    // Replies set up in advance are played in the same block, possibly
+    // triggering the other side's conditional moves in turn
+    while tx_context
+        .state
+        .play_conditional_move(game_id, tx_context.block_info())
+        .await?
+    {}
+
+    Ok(())
+}
+
+pub async fn create_challenge(
+    tx_context: TransactionContext,
+    challenge: state::Challenge,
+    nonce: u64,
+) -> io::Result<()> {
+    tx_context.state.create_challenge(challenge, nonce).await?;
+
+    Ok(())
+}
+
+pub async fn accept_challenge(
+    tx_context: TransactionContext,
+    player: Address,
+    challenge_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .accept_challenge(player, challenge_id, tx_context.block_info())
+        .await?;
+
+    Ok(())
+}
+
+pub async fn cancel_challenge(
+    tx_context: TransactionContext,
+    player: Address,
+    challenge_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .cancel_challenge(player, challenge_id)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn request_takeback(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+    plies: usize,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .request_takeback(player, game_id, plies)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn accept_takeback(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .accept_takeback(player, game_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn set_conditional_moves(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+    moves: &ConditionalMoves,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .set_conditional_moves(player, game_id, moves)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn request_pause(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+) -> io::Result<()> {
+    tx_context.state.request_pause(player, game_id).await?;
+
+    Ok(())
+}
+
+pub async fn accept_pause(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .accept_pause(player, game_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn resume(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .resume(player, game_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn offer_rematch(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+) -> io::Result<()> {
+    tx_context.state.offer_rematch(player, game_id).await?;
+
+    Ok(())
+}
+
+pub async fn accept_rematch(
+    tx_context: TransactionContext,
+    player: Address,
+    game_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .accept_rematch(player, game_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn register_name(
+    tx_context: TransactionContext,
+    player: Address,
+    name: &str,
+) -> io::Result<()> {
+    tx_context.state.register_name(player, name).await?;
+
+    Ok(())
+}
+
+pub async fn transfer_name(
+    tx_context: TransactionContext,
+    player: Address,
+    name: &str,
+    to: Address,
+) -> io::Result<()> {
+    tx_context.state.transfer_name(player, name, to).await?;
+
+    Ok(())
+}
+
+pub async fn release_name(
+    tx_context: TransactionContext,
+    player: Address,
+    name: &str,
+) -> io::Result<()> {
+    tx_context.state.release_name(player, name).await?;
+
+    Ok(())
+}
+
+pub async fn create_tournament(
+    tx_context: TransactionContext,
+    tournament: Tournament,
+    nonce: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .create_tournament(tournament, nonce)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn register_for_tournament(
+    tx_context: TransactionContext,
+    player: Address,
+    tournament_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .register_for_tournament(player, tournament_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn withdraw_from_tournament(
+    tx_context: TransactionContext,
+    player: Address,
+    tournament_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .withdraw_from_tournament(player, tournament_id)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn start_round(
+    tx_context: TransactionContext,
+    organizer: Address,
+    tournament_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .start_round(organizer, tournament_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn post_seek(
+    tx_context: TransactionContext,
+    player: Address,
+    time_control: Option<TimeControl>,
+    variant: GameVariant,
+    rating_range: Option<RatingRange>,
+    nonce: u64,
+) -> io::Result<()> {
+    let seek = state::Seek {
+        player,
+        time_control,
+        variant,
+        rating_range,
+        created_at: tx_context.block_time,
+    };
+    tx_context.state.post_seek(seek, nonce).await?;
+
+    Ok(())
+}
+
+pub async fn cancel_seek(
+    tx_context: TransactionContext,
+    player: Address,
+    seek_id: u64,
+) -> io::Result<()> {
+    tx_context.state.cancel_seek(player, seek_id).await?;
+
+    Ok(())
+}
+
+pub async fn post_puzzle(
+    tx_context: TransactionContext,
+    puzzle: Puzzle,
+    nonce: u64,
+) -> io::Result<()> {
+    tx_context.state.post_puzzle(puzzle, nonce).await?;
+
+    Ok(())
+}
+
+pub async fn commit_solution(
+    tx_context: TransactionContext,
+    solver: Address,
+    puzzle_id: u64,
+    hash: B256,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .commit_solution(solver, puzzle_id, hash, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn reveal_solution(
+    tx_context: TransactionContext,
+    solver: Address,
+    puzzle_id: u64,
+    moves: &[String],
+    salt: &str,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .reveal_solution(solver, puzzle_id, moves, salt, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn create_simul(
+    tx_context: TransactionContext,
+    simul: Simul,
+    nonce: u64,
+) -> io::Result<()> {
+    tx_context.state.create_simul(simul, nonce).await?;
+
+    Ok(())
+}
+
+pub async fn register_for_simul(
+    tx_context: TransactionContext,
+    player: Address,
+    simul_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .register_for_simul(player, simul_id)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn withdraw_from_simul(
+    tx_context: TransactionContext,
+    player: Address,
+    simul_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .withdraw_from_simul(player, simul_id)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn start_simul(
+    tx_context: TransactionContext,
+    host: Address,
+    simul_id: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .start_simul(host, simul_id, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn create_team(
+    tx_context: TransactionContext,
+    owner: Address,
+    nonce: u64,
+) -> io::Result<()> {
+    tx_context.state.create_team(owner, nonce).await?;
+
+    Ok(())
+}
+
+pub async fn add_team_member(
+    tx_context: TransactionContext,
+    owner: Address,
+    team_id: u64,
+    member: Address,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .add_team_member(owner, team_id, member)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn remove_team_member(
+    tx_context: TransactionContext,
+    player: Address,
+    team_id: u64,
+    member: Address,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .remove_team_member(player, team_id, member)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn create_consultation_game(
+    tx_context: TransactionContext,
+    creator: Address,
+    consultation: Consultation,
+    variant: GameVariant,
+    nonce: u64,
+) -> io::Result<()> {
+    tx_context
+        .state
+        .create_consultation_game(creator, consultation, variant, nonce, tx_context.block_time)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn vote_move(
+    tx_context: TransactionContext,
+    voter: Address,
+    game_id: u64,
+    mv: chain_handlers::MoveEnum,
+) -> io::Result<()> {
+    let pos = match tx_context.state.get_game(game_id).await {
+        Some(pos) => pos,
+        None => return Err(Error::other("Game does not exist!")),
+    };
+    let mv = convert_move(mv, &pos)?;
+    tx_context
+        .state
+        .vote_move(voter, game_id, &mv, tx_context.block_info())
+        .await?;
+
+    Ok(())
+}
+
+pub async fn transfer(tx_context: TransactionContext, to: Address, amount: u64) -> io::Result<()> {
+    tx_context
+        .state
+        .transfer(tx_context.sender, to, amount)
+        .await?;
+
+    Ok(())
+}
+
+/// Checks that both players signed the import of a game
+pub fn verify_import(
+    white: Address,
+    black: Address,
+    variant: GameVariant,
+    fen: Option<&str>,
+    movetext: &str,
+    signatures: [&str; 2],
+) -> io::Result<()> {
+    let message = pgn::import_message(white, black, variant, fen, movetext);
+    for (player, signature) in [white, black].into_iter().zip(signatures) {
+        if pgn::recover_signer(&message, signature)? != player {
+            return Err(Error::other(format!("import is not signed by {player}")));
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn import_game(
+    tx_context: TransactionContext,
+    white: Address,
+    black: Address,
+    variant: GameVariant,
+    fen: Option<&str>,
+    movetext: &str,
+) -> io::Result<()> {
+    let (moves, result) = pgn::parse_movetext(movetext)?;
+    let result = match result {
+        Some(result) => result,
+        None => return Err(Error::other("imported games must be finished")),
+    };
+
+    let game_id = state::calculate_import_id(white, black, movetext);
+    let pos = starting_position(variant, fen, game_id)?;
+    let game = GameState::new(pos, variant, white, black, None, tx_context.block_time);
+    tx_context
+        .state
+        .import_game(game_id, game, &moves, result, tx_context.block_info())
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_convert_move() {
+    let pos = VariantPosition::default();
+    let expected = Move::Normal {
+        role: Role::Knight,
+        from: Square::G1,
+        capture: None,
+        to: Square::F3,
+        promotion: None,
+    };
+
+    let uci = chain_handlers::MoveEnum::Uci(String::from("g1f3"));
+    assert_eq!(convert_move(uci, &pos).unwrap(), expected);
+    let san = chain_handlers::MoveEnum::San(String::from("Nf3"));
+    assert_eq!(convert_move(san, &pos).unwrap(), expected);
+
+    // Illegal and malformed moves are rejected
+    let illegal = chain_handlers::MoveEnum::San(String::from("Nf6"));
+    assert!(convert_move(illegal, &pos).is_err());
+    let malformed = chain_handlers::MoveEnum::Uci(String::from("g1"));
+    assert!(convert_move(malformed, &pos).is_err());
+}
+
+/// Returns the context of a transaction sent by `sender` in a block at time
+/// 10, along with an unsigned transaction of `action` from them
+#[cfg(test)]
+fn test_transaction(
+    state: state::State,
+    sender: Address,
+    action: ActionType,
+) -> (TransactionContext, Transaction) {
+    let tx_context = TransactionContext {
+        state,
+        block_time: 10,
+        block_id: ids::Id::empty(),
+        block_height: 1,
+        tx_id: ids::Id::empty(),
+        sender,
+    };
+    let tx = Transaction {
+        action,
+        bytes: Vec::new(),
+        id: ids::Id::empty(),
+        size: 0,
+        sender,
+        tip: 0,
+        nonce: 0,
+        signature: String::new(),
+    };
+
+    (tx_context, tx)
+}
+
+#[tokio::test]
+async fn test_import_game() {
+    use avalanche_types::key::secp256k1::private_key;
+
+    let white_key = private_key::Key::from_bytes(&[1; 32]).unwrap();
+    let black_key = private_key::Key::from_bytes(&[2; 32]).unwrap();
+    let white = Address::from_slice(white_key.to_public_key().to_h160().as_bytes());
+    let black = Address::from_slice(black_key.to_public_key().to_h160().as_bytes());
+    let movetext =
+        "[Event \"Club night\"]\n\n1. e4 {best by test} e5 2. Nf3 (2. f4) Nc6 3. Bb5 a6 1/2-1/2";
+
+    let message = pgn::import_message(white, black, GameVariant::Standard, None, movetext);
+    let white_signature = pgn::sign_message(&white_key, &message).unwrap();
+    let black_signature = pgn::sign_message(&black_key, &message).unwrap();
+
+    // Both players have to sign, each with their own key
+    let verify = |signatures| {
+        verify_import(
+            white,
+            black,
+            GameVariant::Standard,
+            None,
+            movetext,
+            signatures,
+        )
+    };
+    assert!(verify([&white_signature, &black_signature]).is_ok());
+    assert!(verify([&white_signature, &white_signature]).is_err());
+    assert!(verify([&black_signature, &white_signature]).is_err());
+
+    let (tx_context, _) = test_transaction(state::State::default(), white, ActionType::Unknown);
+    import_game(
+        tx_context.clone(),
+        white,
+        black,
+        GameVariant::Standard,
+        None,
+        movetext,
+    )
+    .await
+    .unwrap();
+
+    let game_id = state::calculate_import_id(white, black, movetext);
+    let game = tx_context.state.get_game_record(game_id).await.unwrap();
+    assert!(game.imported);
+    assert!(tx_context.state.is_archived(game_id).await);
+    assert_eq!(game.move_count, 6);
+    assert_eq!(
+        game.outcome.unwrap().termination,
+        state::Termination::Declared
+    );
+
+    // Illegal and unfinished games are rejected
+    for movetext in ["1. e4 e5 2. Ke3 1-0", "1. e4 e5 *"] {
+        assert!(import_game(
+            tx_context.clone(),
+            white,
+            black,
+            GameVariant::Standard,
+            None,
+            movetext
+        )
+        .await
+        .is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_conditional_moves() {
+    use shakmaty::Color;
+
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let (tx_context, _) = test_transaction(state::State::default(), alice, ActionType::Unknown);
+    let state = tx_context.state.clone();
+    let game_id = state
+        .create_new_game(alice, bob, GameVariant::Standard, None, 0, 0)
+        .await
+        .unwrap();
+    let play = |player, uci: &str| {
+        make_move(
+            tx_context.clone(),
+            player,
+            game_id,
+            chain_handlers::MoveEnum::Uci(uci.to_string()),
+        )
+    };
+    let line = |reply: &str, then: ConditionalMoves| state::ConditionalLine {
+        reply: reply.to_string(),
+        then,
+    };
+
+    play(alice, "e2e4").await.unwrap();
+    let moves = ConditionalMoves::from([
+        (
+            String::from("e7e5"),
+            line(
+                "g1f3",
+                ConditionalMoves::from([(String::from("b8c6"), line("f1b5", Default::default()))]),
+            ),
+        ),
+        (String::from("c7c5"), line("g1f3", Default::default())),
+    ]);
+    // Conditional moves are only accepted while the opponent is to move, and
+    // must be legal
+    assert!(state
+        .set_conditional_moves(bob, game_id, &moves)
+        .await
+        .is_err());
+    let illegal =
+        ConditionalMoves::from([(String::from("e7e5"), line("e1e3", Default::default()))]);
+    assert!(state
+        .set_conditional_moves(alice, game_id, &illegal)
+        .await
+        .is_err());
+    state
+        .set_conditional_moves(alice, game_id, &moves)
+        .await
+        .unwrap();
+
+    // Matching moves are answered in the same block
+    play(bob, "e7e5").await.unwrap();
+    play(bob, "b8c6").await.unwrap();
+    let game = state.get_game_state(game_id).await.unwrap();
+    let ucis: Vec<&str> = game.history().iter().map(|m| m.uci.as_str()).collect();
+    assert_eq!(ucis, ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5"]);
+
+    // An unmatched move clears the tree
+    let moves = ConditionalMoves::from([(String::from("a7a6"), line("b5a4", Default::default()))]);
+    state
+        .set_conditional_moves(alice, game_id, &moves)
+        .await
+        .unwrap();
+    play(bob, "d7d6").await.unwrap();
+    let game = state.get_game_state(game_id).await.unwrap();
+    assert_eq!(game.history().len(), 6);
+    assert!(game.conditional_moves(Color::White).is_empty());
+}
+
+#[tokio::test]
+async fn test_signed_transaction() {
+    use crate::state::calculate_game_id;
+
+    let white_key = private_key::Key::from_bytes(&[1; 32]).unwrap();
+    let black_key = private_key::Key::from_bytes(&[2; 32]).unwrap();
+    let white = Address::from_slice(white_key.to_public_key().to_h160().as_bytes());
+    let black = Address::from_slice(black_key.to_public_key().to_h160().as_bytes());
+    let action = ActionType::CreateGame {
+        white,
+        black,
+        variant: GameVariant::Standard,
+        fen: None,
+        nonce: 1,
+        block_id: ids::Id::empty(),
+    };
+    let (tx_context, unsigned) = test_transaction(state::State::default(), white, action);
+    // Signing sets the sender
+    let unsigned = Transaction {
+        sender: Address::ZERO,
+        nonce: 1,
+        ..unsigned
+    };
+    assert!(unsigned.verify_signature().is_err());
+
+    let mut tx = unsigned.clone();
+    tx.sign(&white_key).unwrap();
+    assert_eq!(tx.sender, white);
+    assert_eq!(tx.signer().unwrap(), white);
+    tx.verify_signature().unwrap();
+
+    // The ID covers the sender, the tip and the nonce
+    let id = tx.compute_id().unwrap();
+    for changed in [
+        Transaction {
+            sender: black,
+            ..tx.clone()
+        },
+        Transaction {
+            tip: 1,
+            ..tx.clone()
+        },
+        Transaction {
+            nonce: 2,
+            ..tx.clone()
+        },
+    ] {
+        assert_ne!(changed.compute_id().unwrap(), id);
+        // Nothing can be changed without signing again
+        assert!(changed.verify_signature().is_err());
+    }
+
+    // Actions can only be taken by their sender
+    let mut forged = unsigned;
+    forged.sign(&black_key).unwrap();
+    assert!(forged
+        .execute(TransactionContext {
+            sender: black,
+            ..tx_context.clone()
+        })
+        .await
+        .is_err());
+    tx.execute(tx_context.clone()).await.unwrap();
+    assert!(
+        tx_context
+            .state
+            .game_exists(calculate_game_id(white, black, 1))
+            .await
+    );
+}
+
+#[tokio::test]
+async fn test_transfer() {
+    use crate::state::ledger::Ledger;
+    use std::{collections::BTreeMap, sync::Arc};
+    use tokio::sync::RwLock;
+
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let state = state::State {
+        ledger: Arc::new(RwLock::new(Ledger::from_allocations(&BTreeMap::from([
+            (alice, 100),
+            (bob, 100),
+        ])))),
+        ..Default::default()
+    };
+
+    // The funds always come from the sender
+    let action = ActionType::Transfer {
+        to: alice,
+        amount: 30,
+        block_id: ids::Id::empty(),
+    };
+    let (tx_context, tx) = test_transaction(state, bob, action);
+    tx.execute(tx_context.clone()).await.unwrap();
+    assert_eq!(tx_context.state.get_balance(alice).await, 130);
+    assert_eq!(tx_context.state.get_balance(bob).await, 70);
+}
+
+#[tokio::test]
+async fn test_end_game_sender() {
+    let white = Address::repeat_byte(1);
+    let black = Address::repeat_byte(2);
+    let state = state::State::default();
+    let game_id = state
+        .create_new_game(white, black, GameVariant::Standard, None, 0, 0)
+        .await
+        .unwrap();
+    let action = ActionType::EndGame {
+        game_id,
+        block_id: ids::Id::empty(),
+    };
+    let (tx_context, tx) = test_transaction(state, Address::repeat_byte(3), action);
+
+    // Only the players can abort the game
+    assert!(tx.execute(tx_context.clone()).await.is_err());
+    tx.execute(TransactionContext {
+        sender: white,
+        ..tx_context.clone()
+    })
+    .await
+    .unwrap();
+    assert!(tx_context.state.is_archived(game_id).await);
+}
+
+#[tokio::test]
+async fn test_challenge_sender() {
+    let white = Address::repeat_byte(1);
+    let black = Address::repeat_byte(2);
+    let state = state::State::default();
+    let challenge_id = state
+        .create_challenge(
+            state::Challenge {
+                challenger: white,
+                opponent: None,
+                color: ColorPreference::White,
+                time_control: None,
+                variant: GameVariant::Standard,
+                stake: 0,
+                created_at: 0,
+            },
+            0,
+        )
+        .await
+        .unwrap();
+
+    // Challenges can't be cancelled or accepted on behalf of someone else
+    let action = ActionType::CancelChallenge {
+        player: white,
+        challenge_id,
+        block_id: ids::Id::empty(),
+    };
+    let (tx_context, cancel) = test_transaction(state, black, action);
+    let tx = |action| Transaction {
+        action,
+        ..cancel.clone()
+    };
+    assert!(cancel.execute(tx_context.clone()).await.is_err());
+    let accept = tx(ActionType::AcceptChallenge {
+        player: Address::repeat_byte(3),
+        challenge_id,
+        block_id: ids::Id::empty(),
+    });
+    assert!(accept.execute(tx_context.clone()).await.is_err());
+    assert_eq!(tx_context.state.list_open_challenges(None).await.len(), 1);
+
+    let accept = tx(ActionType::AcceptChallenge {
+        player: black,
+        challenge_id,
+        block_id: ids::Id::empty(),
+    });
+    accept.execute(tx_context.clone()).await.unwrap();
+    assert!(tx_context.state.list_open_challenges(None).await.is_empty());
 }
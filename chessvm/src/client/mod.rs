@@ -5,128 +5,1321 @@ use std::{
     io::{self, Error, ErrorKind},
 };
 
-use alloy_primitives::Address;
-use avalanche_types::{ids, jsonrpc};
+use alloy_primitives::{Address, B256};
+use avalanche_types::{ids, jsonrpc, key::secp256k1::private_key};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::api::chain_handlers;
+use crate::{
+    api::chain_handlers,
+    block::tx::{ActionType, Transaction},
+    state::{rating::TimeCategory, variant::GameVariant},
+};
+
+pub fn move_enum_to_json_string(mv: chain_handlers::MoveEnum) -> io::Result<String> {
+    serde_json::to_string(&mv).map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("failed to serialize MoveEnum to JSON string {e}"),
+        )
+    })
+}
+
+/// Returns the address of the owner of `key`
+pub fn address_of(key: &private_key::Key) -> Address {
+    Address::from_slice(key.to_public_key().to_h160().as_bytes())
+}
+
+/// Returns a nonce for a new transaction, so that the same action can be sent
+/// more than once
+fn new_nonce() -> u64 {
+    Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64
+}
+
+/// Adds `tip`, `nonce` and the signature of the transaction to the request
+/// `d`.
+/// `action` has to be the one the server builds from the request's arguments,
+/// or the signature won't match.
+fn sign_request(
+    d: &str,
+    key: &private_key::Key,
+    action: ActionType,
+    tip: u64,
+    nonce: u64,
+) -> io::Result<String> {
+    let mut tx = Transaction {
+        action,
+        bytes: Vec::new(),
+        id: ids::Id::empty(),
+        size: 0,
+        sender: Address::ZERO,
+        tip,
+        nonce,
+        signature: String::new(),
+    };
+    tx.sign(key)?;
+
+    let mut d_json: Value = serde_json::from_str(d)
+        .map_err(|e| Error::other(format!("failed to parse request '{e}'")))?;
+    let val = d_json["params"].get_mut(0).unwrap();
+    val["tip"] = tip.into();
+    val["nonce"] = nonce.into();
+    val["signature"] = tx.signature.into();
+
+    serde_json::to_string(&d_json)
+        .map_err(|e| Error::other(format!("failed to serialize request '{e}'")))
+}
+
+/// Represents the RPC response for API `ping`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PingResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::PingResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Ping the VM.
+/// # Errors
+/// Errors on an http failure or a failed deserialization.
+pub async fn ping(http_rpc: &str, url_path: &str) -> io::Result<PingResponse> {
+    log::info!("ping {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsArray::default();
+    data.method = String::from("chessvm.ping");
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed ping '{e}'")))
+}
+
+/// Represents the RPC response for API `createGame`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateGameResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::CreateGameResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Sends a TX to create a new chess game, signed by white
+pub async fn create_game(
+    http_rpc: &str,
+    url_path: &str,
+    white: &private_key::Key,
+    black: Address,
+    variant: GameVariant,
+    fen: Option<String>,
+) -> io::Result<CreateGameResponse> {
+    log::info!("create_game method to {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.createGame");
+
+    // The nonce is part of the game ID
+    let nonce = new_nonce();
+    let action = ActionType::CreateGame {
+        white: address_of(white),
+        black,
+        variant,
+        fen: fen.clone(),
+        nonce,
+        block_id: ids::Id::empty(),
+    };
+    let mut m = HashMap::new();
+    m.insert("white".to_string(), address_of(white).to_string());
+    m.insert("black".to_string(), black.to_string());
+    m.insert("variant".to_string(), format!("{variant:?}"));
+    if let Some(fen) = fen {
+        m.insert("fen".to_string(), fen);
+    }
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = sign_request(&data.encode_json()?, white, action, 0, nonce)?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed create_game '{e}'")))
+}
+
+/// Represents the RPC response for API `getGame`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetGameResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetGameResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the current state of a Chess Game
+pub async fn get_game(http_rpc: &str, url_path: &str, game_id: u64) -> io::Result<GetGameResponse> {
+    log::info!("get_game method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+
+    data.method = String::from("getGame");
+
+    let mut m = HashMap::new();
+    m.insert("game_id".to_string(), game_id.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed get_game '{e}'")))
+}
+
+/// Represents the RPC response for API `endGame`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EndGameResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::EndGameResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Asks for a game to be ended on behalf of the owner of `player`
+pub async fn end_game(
+    http_rpc: &str,
+    url_path: &str,
+    player: &private_key::Key,
+    game_id: u64,
+) -> io::Result<EndGameResponse> {
+    log::info!("end_game method to {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.endGame");
+
+    let params = vec![HashMap::new()];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+
+    // The game ID is a number, not a string
+    let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
+    d_json["params"].get_mut(0).unwrap()["game_id"] = game_id.into();
+    let d = serde_json::to_string(&d_json).unwrap();
+
+    let action = ActionType::EndGame {
+        game_id,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&d, player, action, 0, new_nonce())?;
+
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed end_game '{e}'")))
+}
+
+/// Represents the RPC response for API `getReceipt`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetReceiptResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetReceiptResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the outcome of an accepted transaction
+pub async fn get_receipt(
+    http_rpc: &str,
+    url_path: &str,
+    tx_id: ids::Id,
+) -> io::Result<GetReceiptResponse> {
+    log::info!("get_receipt method to {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getReceipt");
+
+    let mut m = HashMap::new();
+    m.insert("tx_id".to_string(), tx_id.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_receipt '{e}'")))
+}
+
+/// Represents the RPC response for API `importGame`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportGameResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::ImportGameResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Records a game played outside of ChessVM, signed by both players. The
+/// transaction is sent by white, the owner of `key`.
+pub async fn import_game(
+    http_rpc: &str,
+    url_path: &str,
+    key: &private_key::Key,
+    args: &crate::api::chain_handlers::ImportGameArgs,
+) -> io::Result<ImportGameResponse> {
+    log::info!("import_game method to {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.importGame");
+
+    let mut m = HashMap::new();
+    m.insert("white".to_string(), args.white.to_string());
+    m.insert("black".to_string(), args.black.to_string());
+    m.insert("variant".to_string(), format!("{:?}", args.variant));
+    if let Some(fen) = &args.fen {
+        m.insert("fen".to_string(), fen.clone());
+    }
+    m.insert("movetext".to_string(), args.movetext.clone());
+    m.insert("white_signature".to_string(), args.white_signature.clone());
+    m.insert("black_signature".to_string(), args.black_signature.clone());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let action = ActionType::ImportGame {
+        white: args.white,
+        black: args.black,
+        variant: args.variant,
+        fen: args.fen.clone(),
+        movetext: args.movetext.clone(),
+        white_signature: args.white_signature.clone(),
+        black_signature: args.black_signature.clone(),
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&data.encode_json()?, key, action, 0, new_nonce())?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed import_game '{e}'")))
+}
+
+/// Represents the RPC response for API `getGamePgn`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetGamePgnResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetGamePgnResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Exports a single game, or every game of `player`, in PGN
+pub async fn get_game_pgn(
+    http_rpc: &str,
+    url_path: &str,
+    game_id: Option<u64>,
+    player: Option<Address>,
+) -> io::Result<GetGamePgnResponse> {
+    log::info!("get_game_pgn method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getGamePgn");
+
+    let mut m = HashMap::new();
+    if let Some(game_id) = game_id {
+        m.insert("game_id".to_string(), game_id.to_string());
+    }
+    if let Some(player) = player {
+        m.insert("player".to_string(), player.to_string());
+    }
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_game_pgn '{e}'")))
+}
+
+/// Represents the RPC response for API `getRating`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetRatingResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetRatingResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the ratings of a player, optionally in a single variant or time
+/// control category
+pub async fn get_rating(
+    http_rpc: &str,
+    url_path: &str,
+    player: Address,
+    variant: Option<GameVariant>,
+    category: Option<TimeCategory>,
+) -> io::Result<GetRatingResponse> {
+    log::info!("get_rating method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getRating");
+
+    let mut m = HashMap::new();
+    m.insert("player".to_string(), player.to_string());
+    if let Some(variant) = variant {
+        m.insert("variant".to_string(), format!("{variant:?}"));
+    }
+    if let Some(category) = category {
+        m.insert("category".to_string(), format!("{category:?}"));
+    }
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_rating '{e}'")))
+}
+
+/// Represents the RPC response for API `getLeaderboard`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetLeaderboardResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetLeaderboardResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests a page of the best rated players of a pool
+pub async fn get_leaderboard(
+    http_rpc: &str,
+    url_path: &str,
+    variant: GameVariant,
+    category: TimeCategory,
+    offset: usize,
+    limit: Option<usize>,
+) -> io::Result<GetLeaderboardResponse> {
+    log::info!("get_leaderboard method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getLeaderboard");
+
+    let mut m = HashMap::new();
+    m.insert("variant".to_string(), format!("{variant:?}"));
+    m.insert("category".to_string(), format!("{category:?}"));
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+
+    // Pagination arguments are numbers, not strings
+    let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
+    let val = d_json["params"].get_mut(0).unwrap();
+    val["offset"] = offset.into();
+    if let Some(limit) = limit {
+        val["limit"] = limit.into();
+    }
+    let d = serde_json::to_string(&d_json).unwrap();
+
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_leaderboard '{e}'")))
+}
+
+/// Represents the RPC response for API `getPlayerStats`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetPlayerStatsResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetPlayerStatsResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the statistics of a player
+pub async fn get_player_stats(
+    http_rpc: &str,
+    url_path: &str,
+    player: Address,
+) -> io::Result<GetPlayerStatsResponse> {
+    log::info!("get_player_stats method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getPlayerStats");
+
+    let mut m = HashMap::new();
+    m.insert("player".to_string(), player.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_player_stats '{e}'")))
+}
+
+/// Represents the RPC response for API `resolveName`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResolveNameResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::ResolveNameResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the address owning a name
+pub async fn resolve_name(
+    http_rpc: &str,
+    url_path: &str,
+    name: &str,
+) -> io::Result<ResolveNameResponse> {
+    log::info!("resolve_name method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.resolveName");
+
+    let mut m = HashMap::new();
+    m.insert("name".to_string(), name.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed resolve_name '{e}'")))
+}
+
+/// Returns the address written as `input`, which is either a hex address or
+/// a registered name prefixed with `@`
+pub async fn parse_address(http_rpc: &str, url_path: &str, input: &str) -> io::Result<Address> {
+    let name = match input.strip_prefix('@') {
+        Some(name) => name,
+        None => {
+            return input.parse::<Address>().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("invalid address '{input}': {e}"),
+                )
+            })
+        }
+    };
+
+    let resp = resolve_name(http_rpc, url_path, name).await?;
+    match (resp.result, resp.error) {
+        (Some(v), _) => Ok(v.address),
+        (None, Some(e)) => Err(Error::new(
+            ErrorKind::NotFound,
+            format!("could not resolve '{input}': {}", e.message),
+        )),
+        (None, None) => Err(Error::new(
+            ErrorKind::NotFound,
+            format!("could not resolve '{input}'"),
+        )),
+    }
+}
+
+/// Represents the RPC response for API `getBalance`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetBalanceResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetBalanceResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the balance of an account
+pub async fn get_balance(
+    http_rpc: &str,
+    url_path: &str,
+    address: Address,
+) -> io::Result<GetBalanceResponse> {
+    log::info!("get_balance method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getBalance");
+
+    let mut m = HashMap::new();
+    m.insert("address".to_string(), address.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_balance '{e}'")))
+}
+
+/// Represents the RPC response for API `transfer`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::TxResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Submits a transfer of `amount` from the owner of `from` to `to`
+pub async fn transfer(
+    http_rpc: &str,
+    url_path: &str,
+    from: &private_key::Key,
+    to: Address,
+    amount: u64,
+    tip: u64,
+) -> io::Result<TransferResponse> {
+    log::info!("transfer method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.transfer");
+
+    let mut m = HashMap::new();
+    m.insert("to".to_string(), to.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+
+    // The amount is a number, not a string
+    let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
+    d_json["params"].get_mut(0).unwrap()["amount"] = amount.into();
+    let d = serde_json::to_string(&d_json).unwrap();
+
+    let action = ActionType::Transfer {
+        to,
+        amount,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&d, from, action, tip, new_nonce())?;
+
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed transfer '{e}'")))
+}
+
+/// Represents the RPC response for API `estimateFee`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EstimateFeeResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::EstimateFeeResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the fee of `action`, e.g. `MakeMove`
+pub async fn estimate_fee(
+    http_rpc: &str,
+    url_path: &str,
+    action: &str,
+) -> io::Result<EstimateFeeResponse> {
+    log::info!("estimate_fee method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.estimateFee");
+
+    let mut m = HashMap::new();
+    m.insert("action".to_string(), action.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed estimate_fee '{e}'")))
+}
+
+/// Represents the RPC response for API `postPuzzle`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostPuzzleResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::PostPuzzleResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Posts a puzzle whose solution hashes to `solution_hash`, see
+/// [`solution_hash`](crate::state::puzzle::solution_hash)
+pub async fn post_puzzle(
+    http_rpc: &str,
+    url_path: &str,
+    poster: &private_key::Key,
+    fen: &str,
+    solution_hash: B256,
+    reward: u64,
+    deadline: u64,
+) -> io::Result<PostPuzzleResponse> {
+    log::info!("post_puzzle method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.postPuzzle");
+
+    let mut m = HashMap::new();
+    m.insert("poster".to_string(), address_of(poster).to_string());
+    m.insert("fen".to_string(), fen.to_string());
+    m.insert("solution_hash".to_string(), solution_hash.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+
+    // The reward and deadline are numbers, not strings
+    let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
+    let val = d_json["params"].get_mut(0).unwrap();
+    val["reward"] = reward.into();
+    val["deadline"] = deadline.into();
+    let d = serde_json::to_string(&d_json).unwrap();
+
+    // The nonce is part of the puzzle ID
+    let nonce = new_nonce();
+    let action = ActionType::PostPuzzle {
+        poster: address_of(poster),
+        fen: fen.to_string(),
+        solution_hash,
+        reward,
+        deadline,
+        nonce,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&d, poster, action, 0, nonce)?;
+
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed post_puzzle '{e}'")))
+}
+
+/// Represents the RPC response for APIs `commitSolution` and `revealSolution`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SolutionResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::TxResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
 
-pub fn move_enum_to_json_string(mv: chain_handlers::MoveEnum) -> io::Result<String> {
-    serde_json::to_string(&mv).map_err(|e| {
-        Error::new(
-            ErrorKind::Other,
-            format!("failed to serialize MoveEnum to JSON string {e}"),
-        )
-    })
+/// Commits to the solution of a puzzle; only the hash of the solution leaves
+/// this machine
+pub async fn commit_solution(
+    http_rpc: &str,
+    url_path: &str,
+    solver: &private_key::Key,
+    puzzle_id: u64,
+    moves: &[String],
+    salt: &str,
+) -> io::Result<SolutionResponse> {
+    log::info!("commit_solution method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.commitSolution");
+
+    let hash = crate::state::puzzle::commitment_hash(address_of(solver), moves, salt);
+    let mut m = HashMap::new();
+    m.insert("solver".to_string(), address_of(solver).to_string());
+    m.insert("puzzle_id".to_string(), puzzle_id.to_string());
+    m.insert("hash".to_string(), hash.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let action = ActionType::CommitSolution {
+        solver: address_of(solver),
+        puzzle_id,
+        hash,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&data.encode_json()?, solver, action, 0, new_nonce())?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed commit_solution '{e}'")))
 }
 
-/// Represents the RPC response for API `ping`.
+/// Reveals the solution committed to with [`commit_solution`]
+pub async fn reveal_solution(
+    http_rpc: &str,
+    url_path: &str,
+    solver: &private_key::Key,
+    puzzle_id: u64,
+    moves: &[String],
+    salt: &str,
+) -> io::Result<SolutionResponse> {
+    log::info!("reveal_solution method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.revealSolution");
+
+    let mut m = HashMap::new();
+    m.insert("solver".to_string(), address_of(solver).to_string());
+    m.insert("puzzle_id".to_string(), puzzle_id.to_string());
+    m.insert("salt".to_string(), salt.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+
+    // The moves are a list, not a string
+    let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
+    d_json["params"].get_mut(0).unwrap()["moves"] = moves.into();
+    let d = serde_json::to_string(&d_json).unwrap();
+
+    let action = ActionType::RevealSolution {
+        solver: address_of(solver),
+        puzzle_id,
+        moves: moves.to_vec(),
+        salt: salt.to_string(),
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&d, solver, action, 0, new_nonce())?;
+
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed reveal_solution '{e}'")))
+}
+
+/// Represents the RPC response for API `getPuzzle`
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct PingResponse {
+pub struct GetPuzzleResponse {
     pub jsonrpc: String,
     pub id: u32,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub result: Option<crate::api::PingResponse>,
+    pub result: Option<crate::api::chain_handlers::GetPuzzleResponse>,
 
     /// Returns non-empty if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<APIError>,
 }
 
-/// Ping the VM.
-/// # Errors
-/// Errors on an http failure or a failed deserialization.
-pub async fn ping(http_rpc: &str, url_path: &str) -> io::Result<PingResponse> {
-    log::info!("ping {http_rpc} with {url_path}");
+/// Requests a puzzle and its commitments
+pub async fn get_puzzle(
+    http_rpc: &str,
+    url_path: &str,
+    puzzle_id: u64,
+) -> io::Result<GetPuzzleResponse> {
+    log::info!("get_puzzle method {http_rpc} with {url_path}");
 
-    let mut data = jsonrpc::RequestWithParamsArray::default();
-    data.method = String::from("chessvm.ping");
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getPuzzle");
+
+    let mut m = HashMap::new();
+    m.insert("puzzle_id".to_string(), puzzle_id.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
 
     let d = data.encode_json()?;
     log::info!("{}", d);
     let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
 
-    serde_json::from_slice(&rb)
-        .map_err(|e| Error::new(ErrorKind::Other, format!("failed ping '{e}'")))
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_puzzle '{e}'")))
 }
 
-/// Represents the RPC response for API `createGame`
+/// Represents the RPC response for API `createSimul`
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct CreateGameResponse {
+pub struct CreateSimulResponse {
     pub jsonrpc: String,
     pub id: u32,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub result: Option<crate::api::chain_handlers::CreateGameResponse>,
+    pub result: Option<crate::api::chain_handlers::CreateSimulResponse>,
 
     /// Returns non-empty if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<APIError>,
 }
 
-/// Sends a TX to create a new chess game
-pub async fn create_game(
+/// Opens `boards` standard boards against the owner of `host`
+pub async fn create_simul(
     http_rpc: &str,
     url_path: &str,
-    white: Address,
-    black: Address,
-) -> io::Result<CreateGameResponse> {
-    log::info!("create_game method to {http_rpc} with {url_path}");
+    host: &private_key::Key,
+    boards: usize,
+) -> io::Result<CreateSimulResponse> {
+    log::info!("create_simul method {http_rpc} with {url_path}");
 
     let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
-    data.method = String::from("chessvm.createGame");
+    data.method = String::from("chessvm.createSimul");
 
     let mut m = HashMap::new();
-    m.insert("white".to_string(), white.to_string());
-    m.insert("black".to_string(), black.to_string());
+    m.insert("host".to_string(), address_of(host).to_string());
 
     let params = vec![m];
     data.params = Some(params);
 
     let d = data.encode_json()?;
+
+    // The number of boards is a number, not a string
+    let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
+    d_json["params"].get_mut(0).unwrap()["boards"] = boards.into();
+    let d = serde_json::to_string(&d_json).unwrap();
+
+    // The nonce is part of the simul ID
+    let nonce = new_nonce();
+    let action = ActionType::CreateSimul {
+        host: address_of(host),
+        boards,
+        variant: GameVariant::default(),
+        time_control: None,
+        nonce,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&d, host, action, 0, nonce)?;
+
     log::info!("{}", d);
     let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
 
-    serde_json::from_slice(&rb)
-        .map_err(|e| Error::new(ErrorKind::Other, format!("failed create_game '{e}'")))
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed create_simul '{e}'")))
 }
 
-/// Represents the RPC response for API `getGame`
+/// Represents the RPC response for APIs `registerForSimul`,
+/// `withdrawFromSimul` and `startSimul`
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct GetGameResponse {
+pub struct SimulActionResponse {
     pub jsonrpc: String,
     pub id: u32,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub result: Option<crate::api::chain_handlers::GetGameResponse>,
+    pub result: Option<crate::api::chain_handlers::TxResponse>,
 
     /// Returns non-empty if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<APIError>,
 }
 
-/// Requests the current state of a Chess Game
-pub async fn get_game(http_rpc: &str, url_path: &str, game_id: u64) -> io::Result<GetGameResponse> {
-    log::info!("get_game method {http_rpc} with {url_path}");
+/// Calls `method`, one of `registerForSimul`, `withdrawFromSimul` and
+/// `startSimul`, on behalf of the owner of `player`
+pub async fn simul_action(
+    http_rpc: &str,
+    url_path: &str,
+    method: &str,
+    player: &private_key::Key,
+    simul_id: u64,
+) -> io::Result<SimulActionResponse> {
+    log::info!("{method} method {http_rpc} with {url_path}");
 
     let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = format!("chessvm.{method}");
+
+    let player_address = address_of(player);
+    let block_id = ids::Id::empty();
+    let action = match method {
+        "registerForSimul" => ActionType::RegisterForSimul {
+            player: player_address,
+            simul_id,
+            block_id,
+        },
+        "withdrawFromSimul" => ActionType::WithdrawFromSimul {
+            player: player_address,
+            simul_id,
+            block_id,
+        },
+        "startSimul" => ActionType::StartSimul {
+            host: player_address,
+            simul_id,
+            block_id,
+        },
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown simul method {method}"),
+            ))
+        }
+    };
 
-    data.method = String::from("getGame");
+    let mut m = HashMap::new();
+    m.insert("player".to_string(), player_address.to_string());
+    m.insert("simul_id".to_string(), simul_id.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = sign_request(&data.encode_json()?, player, action, 0, new_nonce())?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed {method} '{e}'")))
+}
+
+/// Represents the RPC response for API `getSimul`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetSimulResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetSimulResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests a simul, its boards and the host's score
+pub async fn get_simul(
+    http_rpc: &str,
+    url_path: &str,
+    simul_id: u64,
+) -> io::Result<GetSimulResponse> {
+    log::info!("get_simul method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getSimul");
 
     let mut m = HashMap::new();
-    m.insert("game_id".to_string(), game_id.to_string());
+    m.insert("simul_id".to_string(), simul_id.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_simul '{e}'")))
+}
+
+/// Represents the RPC response for API `createTeam`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateTeamResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::CreateTeamResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Creates a team owned by the owner of `owner`
+pub async fn create_team(
+    http_rpc: &str,
+    url_path: &str,
+    owner: &private_key::Key,
+) -> io::Result<CreateTeamResponse> {
+    log::info!("create_team method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.createTeam");
+
+    let mut m = HashMap::new();
+    m.insert("owner".to_string(), address_of(owner).to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    // The nonce is part of the team ID
+    let nonce = new_nonce();
+    let action = ActionType::CreateTeam {
+        owner: address_of(owner),
+        nonce,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&data.encode_json()?, owner, action, 0, nonce)?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed create_team '{e}'")))
+}
+
+/// Represents the RPC response for APIs `addTeamMember`, `removeTeamMember`
+/// and `voteMove`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TeamTxResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::TxResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Adds `member` to a team, or removes them if `add` is false. The owner of
+/// `player` must own the team, unless they remove themself.
+pub async fn update_team(
+    http_rpc: &str,
+    url_path: &str,
+    player: &private_key::Key,
+    team_id: u64,
+    member: Address,
+    add: bool,
+) -> io::Result<TeamTxResponse> {
+    log::info!("update_team method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = if add {
+        String::from("chessvm.addTeamMember")
+    } else {
+        String::from("chessvm.removeTeamMember")
+    };
+
+    let mut m = HashMap::new();
+    m.insert("player".to_string(), address_of(player).to_string());
+    m.insert("team_id".to_string(), team_id.to_string());
+    m.insert("member".to_string(), member.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let block_id = ids::Id::empty();
+    let action = if add {
+        ActionType::AddTeamMember {
+            owner: address_of(player),
+            team_id,
+            member,
+            block_id,
+        }
+    } else {
+        ActionType::RemoveTeamMember {
+            player: address_of(player),
+            team_id,
+            member,
+            block_id,
+        }
+    };
+    let d = sign_request(&data.encode_json()?, player, action, 0, new_nonce())?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed update_team '{e}'")))
+}
+
+/// Represents the RPC response for API `getTeam`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetTeamResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::GetTeamResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Requests the owner and members of a team
+pub async fn get_team(http_rpc: &str, url_path: &str, team_id: u64) -> io::Result<GetTeamResponse> {
+    log::info!("get_team method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.getTeam");
+
+    let mut m = HashMap::new();
+    m.insert("team_id".to_string(), team_id.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let d = data.encode_json()?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed get_team '{e}'")))
+}
+
+/// Represents the RPC response for API `createConsultationGame`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateConsultationGameResponse {
+    pub jsonrpc: String,
+    pub id: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<crate::api::chain_handlers::CreateConsultationGameResponse>,
+
+    /// Returns non-empty if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<APIError>,
+}
+
+/// Starts a standard game between two teams, each voting for `voting_window`
+/// seconds per move
+pub async fn create_consultation_game(
+    http_rpc: &str,
+    url_path: &str,
+    creator: &private_key::Key,
+    white_team: u64,
+    black_team: u64,
+    voting_window: u64,
+) -> io::Result<CreateConsultationGameResponse> {
+    log::info!("create_consultation_game method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.createConsultationGame");
+
+    let mut m = HashMap::new();
+    m.insert("creator".to_string(), address_of(creator).to_string());
+    m.insert("white_team".to_string(), white_team.to_string());
+    m.insert("black_team".to_string(), black_team.to_string());
 
     let params = vec![m];
     data.params = Some(params);
 
     let d = data.encode_json()?;
+
+    // The voting window is a number, not a string
+    let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
+    d_json["params"].get_mut(0).unwrap()["voting_window"] = voting_window.into();
+    let d = serde_json::to_string(&d_json).unwrap();
+
+    // The nonce is part of the game ID
+    let nonce = new_nonce();
+    let action = ActionType::CreateConsultationGame {
+        creator: address_of(creator),
+        white_team,
+        black_team,
+        variant: GameVariant::default(),
+        voting_window,
+        nonce,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&d, creator, action, 0, nonce)?;
+
     log::info!("{}", d);
     let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
 
     serde_json::from_slice(&rb)
-        .map_err(|e| Error::new(ErrorKind::Other, format!("failed get_game '{e}'")))
+        .map_err(|e| Error::other(format!("failed create_consultation_game '{e}'")))
+}
+
+/// Votes for a move of the voter's team, in UCI notation
+pub async fn vote_move(
+    http_rpc: &str,
+    url_path: &str,
+    voter: &private_key::Key,
+    game_id: u64,
+    uci: &str,
+) -> io::Result<TeamTxResponse> {
+    log::info!("vote_move method {http_rpc} with {url_path}");
+
+    let mut data = jsonrpc::RequestWithParamsHashMapArray::default();
+    data.method = String::from("chessvm.voteMove");
+
+    let mut m = HashMap::new();
+    m.insert("voter".to_string(), address_of(voter).to_string());
+    m.insert("game_id".to_string(), game_id.to_string());
+    m.insert("uci".to_string(), uci.to_string());
+
+    let params = vec![m];
+    data.params = Some(params);
+
+    let action = ActionType::VoteMove {
+        voter: address_of(voter),
+        game_id,
+        mv: chain_handlers::MoveEnum::Uci(uci.to_string()),
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&data.encode_json()?, voter, action, 0, new_nonce())?;
+    log::info!("{}", d);
+    let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
+
+    serde_json::from_slice(&rb).map_err(|e| Error::other(format!("failed vote_move '{e}'")))
 }
 
 /// Requests for the last accepted block Id.
@@ -213,11 +1406,11 @@ pub struct MakeMoveResponse {
     pub error: Option<APIError>,
 }
 
-/// Makes a move for a given Chess game
+/// Makes a move for a given Chess game on behalf of the owner of `player`
 pub async fn make_move(
     http_rpc: &str,
     url_path: &str,
-    player: Address,
+    player: &private_key::Key,
     game_id: u64,
     mv: chain_handlers::MoveEnum,
 ) -> io::Result<MakeMoveResponse> {
@@ -228,7 +1421,7 @@ pub async fn make_move(
 
     let mut m = HashMap::new();
     // Inserting player arg
-    m.insert("player".to_string(), player.to_string());
+    m.insert("player".to_string(), address_of(player).to_string());
     // Inserting game_id
     m.insert("game_id".to_string(), game_id.to_string());
 
@@ -241,13 +1434,21 @@ pub async fn make_move(
     // adding mv
     let mut d_json: Value = serde_json::from_slice(d.as_bytes()).unwrap();
     let mv_json: Value =
-        serde_json::from_slice(move_enum_to_json_string(mv).unwrap().as_bytes()).unwrap();
+        serde_json::from_slice(move_enum_to_json_string(mv.clone()).unwrap().as_bytes()).unwrap();
     let val = d_json["params"].get_mut(0).unwrap();
     val["mv"] = mv_json;
 
     // Serialize back to JSON string
     let d = serde_json::to_string(&d_json).unwrap();
 
+    let action = ActionType::MakeMove {
+        player: address_of(player),
+        game_id,
+        mv,
+        block_id: ids::Id::empty(),
+    };
+    let d = sign_request(&d, player, action, 0, new_nonce())?;
+
     log::info!("{}", d);
     let rb = http_manager::post_non_tls(http_rpc, url_path, &d).await?;
 
@@ -1,24 +1,112 @@
 //! Defines ChessVM genesis block.
 
 use std::{
+    collections::BTreeMap,
     fmt,
     fs::{self, File},
     io::{self, Error, ErrorKind, Write},
     path::Path,
 };
 
+use alloy_primitives::Address;
 use serde::{Deserialize, Serialize};
 
+/// Longest a game can stay paused by default, in seconds
+pub const DEFAULT_MAX_PAUSE: u64 = 24 * 60 * 60;
+
+fn default_max_pause() -> u64 {
+    DEFAULT_MAX_PAUSE
+}
+
+/// How long a seek waits for an opponent by default, in seconds
+pub const DEFAULT_SEEK_TTL: u64 = 10 * 60;
+
+fn default_seek_ttl() -> u64 {
+    DEFAULT_SEEK_TTL
+}
+
+/// How long solvers have to reveal their solution to a puzzle after its
+/// deadline by default, in seconds
+pub const DEFAULT_PUZZLE_REVEAL_WINDOW: u64 = 60 * 60;
+
+fn default_puzzle_reveal_window() -> u64 {
+    DEFAULT_PUZZLE_REVEAL_WINDOW
+}
+
+/// What happens to a paused game once the maximum pause duration elapsed
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Default)]
+pub enum PauseExpiry {
+    /// Clocks start running again
+    #[default]
+    Resume,
+    /// The game is adjudicated a draw
+    Draw,
+    /// The side with more material wins, or the game is drawn if even.
+    /// Games of variants other than standard chess and Chess960 are drawn
+    Material,
+}
+
+/// Fees charged to the sender of every transaction
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+pub struct FeeSchedule {
+    /// Fee of the actions without a fee of their own
+    #[serde(default)]
+    pub base_fee: u64,
+    /// Fees of specific actions, by action name, e.g. `MakeMove`
+    #[serde(default)]
+    pub action_fees: BTreeMap<String, u64>,
+    /// Account collecting the fees; they are burned if not set
+    #[serde(default)]
+    pub treasury: Option<Address>,
+}
+
+impl FeeSchedule {
+    /// Returns the fee of the action named `action`
+    pub fn fee_of(&self, action: &str) -> u64 {
+        self.action_fees
+            .get(action)
+            .copied()
+            .unwrap_or(self.base_fee)
+    }
+}
+
 /// Represents the genesis data specific to the VM.
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
 pub struct Genesis {
     pub data: String,
+    /// Address allowed to end any game, e.g. to settle disputes
+    #[serde(default)]
+    pub arbiter: Option<Address>,
+    /// Longest a game can stay paused, in seconds
+    #[serde(default = "default_max_pause")]
+    pub max_pause: u64,
+    #[serde(default)]
+    pub pause_expiry: PauseExpiry,
+    /// How long a seek waits for an opponent before expiring, in seconds
+    #[serde(default = "default_seek_ttl")]
+    pub seek_ttl: u64,
+    /// Initial balances of accounts
+    #[serde(default)]
+    pub allocations: BTreeMap<Address, u64>,
+    #[serde(default)]
+    pub fees: FeeSchedule,
+    /// How long solvers have to reveal their solution to a puzzle after its
+    /// deadline, in seconds
+    #[serde(default = "default_puzzle_reveal_window")]
+    pub puzzle_reveal_window: u64,
 }
 
 impl Default for Genesis {
     fn default() -> Self {
         Self {
             data: String::from("Hello from Chess VM!"),
+            arbiter: None,
+            max_pause: DEFAULT_MAX_PAUSE,
+            pause_expiry: PauseExpiry::default(),
+            seek_ttl: DEFAULT_SEEK_TTL,
+            allocations: BTreeMap::new(),
+            fees: FeeSchedule::default(),
+            puzzle_reveal_window: DEFAULT_PUZZLE_REVEAL_WINDOW,
         }
     }
 }
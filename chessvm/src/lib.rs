@@ -2,5 +2,6 @@ pub mod api;
 pub mod block;
 pub mod client;
 pub mod genesis;
+pub mod pgn;
 pub mod state;
 pub mod vm;
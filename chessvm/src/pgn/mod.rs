@@ -0,0 +1,319 @@
+//! Converts ChessVM games to and from PGN.
+
+use std::io::{self, Error};
+
+use alloy_primitives::{eip191_hash_message, hex, Address};
+use avalanche_types::key::secp256k1::{private_key, signature::Sig};
+use chrono::DateTime;
+use shakmaty::{fen::Fen, san::SanPlus, Color, Position};
+
+use crate::state::{
+    variant::{starting_position, GameVariant},
+    ArchivedGame, GameResult, Termination,
+};
+
+/// Maximum length of a movetext line, as recommended by the PGN standard
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Returns the PGN result token of a game
+pub fn result_token(result: Option<GameResult>) -> &'static str {
+    match result {
+        Some(GameResult::WhiteWins) => "1-0",
+        Some(GameResult::BlackWins) => "0-1",
+        Some(GameResult::Draw) => "1/2-1/2",
+        None => "*",
+    }
+}
+
+/// Parses a PGN result token, `*` standing for a game still in progress
+fn parse_result_token(token: &str) -> Option<Option<GameResult>> {
+    match token {
+        "1-0" => Some(Some(GameResult::WhiteWins)),
+        "0-1" => Some(Some(GameResult::BlackWins)),
+        "1/2-1/2" => Some(Some(GameResult::Draw)),
+        "*" => Some(None),
+        _ => None,
+    }
+}
+
+fn termination_tag(termination: Termination) -> &'static str {
+    match termination {
+        Termination::TimeForfeit => "time forfeit",
+        Termination::Aborted | Termination::Forfeit => "abandoned",
+        Termination::Arbiter => "adjudication",
+        _ => "normal",
+    }
+}
+
+/// Escapes a PGN tag value
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Renders a game as PGN, with Seven Tag Roster headers derived from chain
+/// data followed by the full movetext
+pub fn game_to_pgn(game_id: u64, game: &ArchivedGame) -> String {
+    // Called off games have no result
+    let result = result_token(
+        game.outcome
+            .filter(|o| !o.termination.is_void())
+            .map(|o| o.result),
+    );
+    let date = DateTime::from_timestamp(game.created_at as i64, 0)
+        .map(|d| d.format("%Y.%m.%d").to_string())
+        .unwrap_or_else(|| String::from("????.??.??"));
+
+    let mut tags = vec![
+        (
+            "Event",
+            String::from(if game.imported {
+                "ChessVM import"
+            } else {
+                "ChessVM game"
+            }),
+        ),
+        ("Site", String::from("ChessVM")),
+        ("Date", date),
+        ("Round", String::from("-")),
+        ("White", game.white.to_string()),
+        ("Black", game.black.to_string()),
+        ("Result", String::from(result)),
+        ("GameId", game_id.to_string()),
+    ];
+    if game.variant != GameVariant::Standard {
+        tags.push(("Variant", game.variant.name().to_string()));
+    }
+
+    // Games that do not start from the variant's usual position need their
+    // starting FEN to be replayable
+    let default_fen = starting_position(game.variant, None, game_id)
+        .map(|pos| Fen::from_position(pos, shakmaty::EnPassantMode::Legal).to_string())
+        .unwrap_or_default();
+    if game.variant == GameVariant::Chess960 || game.starting_fen != default_fen {
+        tags.push(("SetUp", String::from("1")));
+        tags.push(("FEN", game.starting_fen.clone()));
+    }
+    tags.push((
+        "TimeControl",
+        match game.time_control {
+            Some(tc) => format!("{}+{}", tc.initial, tc.increment),
+            None => String::from("-"),
+        },
+    ));
+    if let Some(outcome) = game.outcome {
+        tags.push((
+            "Termination",
+            termination_tag(outcome.termination).to_string(),
+        ));
+    }
+
+    let mut pgn = String::new();
+    for (name, value) in tags {
+        pgn.push_str(&format!("[{name} \"{}\"]\n", escape(&value)));
+    }
+    pgn.push('\n');
+
+    // Move numbers continue from the starting position
+    let (mut fullmove, mut turn) =
+        match starting_position(game.variant, Some(&game.starting_fen), game_id) {
+            Ok(pos) => (pos.fullmoves().get(), pos.turn()),
+            Err(_) => (1, Color::White),
+        };
+
+    let mut tokens = Vec::with_capacity(game.history.len() + 1);
+    for (i, record) in game.history.iter().enumerate() {
+        match turn {
+            Color::White => tokens.push(format!("{fullmove}. {}", record.san)),
+            Color::Black if i == 0 => tokens.push(format!("{fullmove}... {}", record.san)),
+            Color::Black => tokens.push(record.san.clone()),
+        }
+        if turn == Color::Black {
+            fullmove += 1;
+        }
+        turn = !turn;
+    }
+    tokens.push(String::from(result));
+
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+            pgn.push('\n');
+            line_length = 0;
+        } else if line_length > 0 {
+            pgn.push(' ');
+            line_length += 1;
+        }
+        line_length += token.len();
+        pgn.push_str(&token);
+    }
+    pgn.push('\n');
+
+    pgn
+}
+
+/// Renders several games as a single PGN database
+pub fn games_to_pgn(games: &[(u64, ArchivedGame)]) -> String {
+    games
+        .iter()
+        .map(|(game_id, game)| game_to_pgn(*game_id, game))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Splits PGN movetext into its moves and result. Move numbers, comments,
+/// variations and annotations are skipped, and so are tag pairs so that a
+/// whole PGN game is accepted too.
+pub fn parse_movetext(movetext: &str) -> io::Result<(Vec<SanPlus>, Option<GameResult>)> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut depth = 0usize;
+    let mut chars = movetext.chars();
+    while let Some(c) = chars.next() {
+        if !token.is_empty() && (c.is_whitespace() || "{;[()".contains(c)) {
+            tokens.push(std::mem::take(&mut token));
+        }
+        match c {
+            // Comments run to the closing brace or to the end of the line
+            '{' => {
+                let _ = chars.find(|c| *c == '}');
+            }
+            ';' => {
+                let _ = chars.find(|c| *c == '\n');
+            }
+            '[' if depth == 0 => {
+                let mut quoted = false;
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    match c {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '"' => quoted = !quoted,
+                        ']' if !quoted => break,
+                        _ => {}
+                    }
+                }
+            }
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth > 0 || c.is_whitespace() => {}
+            _ => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    let mut moves = Vec::new();
+    let mut result = None;
+    for token in tokens {
+        if result.is_some() {
+            return Err(Error::other(format!(
+                "unexpected '{token}' after the game result"
+            )));
+        }
+        if let Some(r) = parse_result_token(&token) {
+            result = Some(r);
+            continue;
+        }
+        if token.starts_with('$') {
+            continue;
+        }
+
+        // Move numbers may be glued to the move, as in "1.e4"
+        let san = token
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches('.')
+            .trim_end_matches(['!', '?']);
+        if san.is_empty() {
+            continue;
+        }
+        moves.push(
+            SanPlus::from_ascii(san.as_bytes())
+                .map_err(|e| Error::other(format!("could not parse SAN move '{san}': {e}")))?,
+        );
+    }
+
+    Ok((moves, result.flatten()))
+}
+
+/// Returns the message both players sign to approve the import of a game
+pub fn import_message(
+    white: Address,
+    black: Address,
+    variant: GameVariant,
+    fen: Option<&str>,
+    movetext: &str,
+) -> String {
+    format!(
+        "ChessVM game import\nWhite: {white}\nBlack: {black}\nVariant: {}\nFEN: {}\n\n{movetext}",
+        variant.name(),
+        fen.unwrap_or("-"),
+    )
+}
+
+/// Signs `message` as an EIP-191 personal message, returning the hex encoded
+/// 65 byte recoverable signature
+pub fn sign_message(key: &private_key::Key, message: &str) -> io::Result<String> {
+    let sig = key
+        .sign_digest(eip191_hash_message(message).as_slice())
+        .map_err(|e| Error::other(format!("failed to sign message: {e}")))?;
+
+    Ok(hex::encode_prefixed(sig.to_bytes()))
+}
+
+/// Returns the address that signed `message` as an EIP-191 personal message
+pub fn recover_signer(message: &str, signature: &str) -> io::Result<Address> {
+    let mut bytes =
+        hex::decode(signature).map_err(|e| Error::other(format!("invalid signature hex: {e}")))?;
+    // Wallets encode the recovery ID as 27 or 28
+    if let Some(v) = bytes.get_mut(64) {
+        if *v >= 27 {
+            *v -= 27;
+        }
+    }
+
+    let sig =
+        Sig::from_bytes(&bytes).map_err(|e| Error::other(format!("invalid signature: {e}")))?;
+    let (key, _) = sig
+        .recover_public_key(eip191_hash_message(message).as_slice())
+        .map_err(|e| Error::other(format!("invalid signature: {e}")))?;
+
+    Ok(Address::from_slice(key.to_h160().as_bytes()))
+}
+
+#[tokio::test]
+async fn test_game_to_pgn() {
+    use alloy_primitives::Address;
+    use avalanche_types::ids;
+    use shakmaty::{san::San, Chess};
+
+    use crate::state::{BlockInfo, State};
+
+    let state = State::default();
+    let white = Address::repeat_byte(1);
+    let black = Address::repeat_byte(2);
+    let game_id = state
+        .create_new_game(white, black, GameVariant::Standard, None, 0, 1_700_000_000)
+        .await
+        .unwrap();
+
+    // Fool's mate
+    let mut pos = Chess::default();
+    for (i, san) in ["f3", "e5", "g4", "Qh4#"].iter().enumerate() {
+        let mv = san.parse::<San>().unwrap().to_move(&pos).unwrap();
+        let player = if i % 2 == 0 { white } else { black };
+        let block = BlockInfo {
+            id: ids::Id::empty(),
+            height: i as u64,
+            timestamp: 1_700_000_000,
+        };
+        state.make_move(player, game_id, &mv, block).await.unwrap();
+        pos.play_unchecked(&mv);
+    }
+
+    let pgn = game_to_pgn(game_id, &state.get_game_record(game_id).await.unwrap());
+    assert!(pgn.contains("[Date \"2023.11.14\"]\n"));
+    assert!(pgn.contains("[Result \"0-1\"]\n"));
+    assert!(!pgn.contains("[FEN "));
+    assert!(pgn.ends_with("\n\n1. f3 e5 2. g4 Qh4# 0-1\n"));
+}
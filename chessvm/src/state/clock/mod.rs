@@ -0,0 +1,110 @@
+//! Clocks of timed games and losing on time.
+
+use serde::{Deserialize, Serialize};
+use shakmaty::Color;
+
+use super::{GameOutcome, GameResult, Termination};
+
+/// Time control of a game, in seconds
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Time each player starts with
+    pub initial: u64,
+    /// Time added to a player's clock after each of their moves
+    pub increment: u64,
+}
+
+/// Remaining time of both players, in seconds
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    pub white: u64,
+    pub black: u64,
+    /// Block timestamp at which the side to move started thinking
+    pub last_update: u64,
+}
+
+impl Clock {
+    /// Starts both clocks with the initial time of `time_control`
+    pub fn new(time_control: TimeControl, block_time: u64) -> Self {
+        Self {
+            white: time_control.initial,
+            black: time_control.initial,
+            last_update: block_time,
+        }
+    }
+
+    /// Returns the time `color` had left as of the last update
+    pub fn remaining(&self, color: Color) -> u64 {
+        match color {
+            Color::White => self.white,
+            Color::Black => self.black,
+        }
+    }
+
+    fn remaining_mut(&mut self, color: Color) -> &mut u64 {
+        match color {
+            Color::White => &mut self.white,
+            Color::Black => &mut self.black,
+        }
+    }
+
+    /// Returns `true` if `color` ran out of time by `block_time`, thinking
+    /// since the last update
+    pub fn is_out_of_time(&self, color: Color, block_time: u64) -> bool {
+        block_time.saturating_sub(self.last_update) >= self.remaining(color)
+    }
+
+    /// Returns the outcome of the game if `color` ran out of time by
+    /// `block_time`, leaving their clock at zero
+    pub fn flag(&mut self, color: Color, block_time: u64) -> Option<GameOutcome> {
+        if !self.is_out_of_time(color, block_time) {
+            return None;
+        }
+        *self.remaining_mut(color) = 0;
+
+        Some(GameOutcome {
+            result: GameResult::win_for(!color),
+            termination: Termination::TimeForfeit,
+        })
+    }
+
+    /// Charges the time `color` spent thinking until `block_time` to their
+    /// clock, then adds `increment`. Returns the outcome of the game if
+    /// `color` ran out of time instead, leaving their clock at zero.
+    pub fn charge(&mut self, color: Color, block_time: u64, increment: u64) -> Option<GameOutcome> {
+        if let Some(outcome) = self.flag(color, block_time) {
+            return Some(outcome);
+        }
+        let elapsed = block_time.saturating_sub(self.last_update);
+        let remaining = self.remaining_mut(color);
+        // Increments come from the players' time control, so they can be as
+        // large as they like
+        *remaining = (*remaining - elapsed).saturating_add(increment);
+        self.last_update = block_time;
+
+        None
+    }
+}
+
+#[test]
+fn test_charge() {
+    let mut clock = Clock::new(
+        TimeControl {
+            initial: 60,
+            increment: 5,
+        },
+        10,
+    );
+    assert_eq!(clock.charge(Color::White, 25, 5), None);
+    assert_eq!((clock.white, clock.black, clock.last_update), (50, 60, 25));
+
+    // Thinking for all the remaining time loses
+    let outcome = clock.charge(Color::Black, 85, 5).unwrap();
+    assert_eq!(outcome.result, GameResult::WhiteWins);
+    assert_eq!(outcome.termination, Termination::TimeForfeit);
+    assert_eq!((clock.white, clock.black, clock.last_update), (50, 0, 25));
+
+    // Huge increments fill the clock up
+    assert_eq!(clock.charge(Color::White, 30, u64::MAX), None);
+    assert_eq!(clock.white, u64::MAX);
+}
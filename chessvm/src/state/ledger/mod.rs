@@ -0,0 +1,236 @@
+//! Balances of accounts.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Error},
+};
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use super::GameResult;
+
+/// Stakes locked by the players of a wagered game until it ends
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Escrow {
+    pub white: Address,
+    pub black: Address,
+    /// Amount locked by each player
+    pub stake: u64,
+}
+
+/// Balance of every account holding funds
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    balances: HashMap<Address, u64>,
+    /// Stakes of the wagered games in progress, by game ID
+    escrows: BTreeMap<u64, Escrow>,
+}
+
+impl Ledger {
+    /// Creates a ledger holding the allocations made in genesis
+    pub fn from_allocations(allocations: &BTreeMap<Address, u64>) -> Self {
+        Self {
+            balances: allocations
+                .iter()
+                .filter(|(_, amount)| **amount > 0)
+                .map(|(account, amount)| (*account, *amount))
+                .collect(),
+            escrows: BTreeMap::new(),
+        }
+    }
+
+    pub fn balance(&self, account: Address) -> u64 {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    /// Adds `amount` to the balance of `account`
+    pub fn credit(&mut self, account: Address, amount: u64) -> io::Result<()> {
+        let balance = self
+            .balance(account)
+            .checked_add(amount)
+            .ok_or_else(|| Error::other(format!("balance of {account} would overflow")))?;
+        self.set_balance(account, balance);
+
+        Ok(())
+    }
+
+    /// Takes `amount` from the balance of `account`
+    pub fn debit(&mut self, account: Address, amount: u64) -> io::Result<()> {
+        let balance = self.balance(account).checked_sub(amount).ok_or_else(|| {
+            Error::other(format!(
+                "insufficient balance: {account} has {}, needs {amount}",
+                self.balance(account)
+            ))
+        })?;
+        self.set_balance(account, balance);
+
+        Ok(())
+    }
+
+    /// Moves `amount` from `from` to `to`, leaving both balances untouched if
+    /// it fails
+    pub fn transfer(&mut self, from: Address, to: Address, amount: u64) -> io::Result<()> {
+        if from == to {
+            return Err(Error::other("cannot transfer to the same account"));
+        }
+        if self.balance(to).checked_add(amount).is_none() {
+            return Err(Error::other(format!("balance of {to} would overflow")));
+        }
+
+        self.debit(from, amount)?;
+        self.credit(to, amount)
+    }
+
+    /// Takes a transaction fee from `payer`, crediting it to `treasury` or
+    /// burning it if there is none
+    pub fn pay_fee(
+        &mut self,
+        payer: Address,
+        fee: u64,
+        treasury: Option<Address>,
+    ) -> io::Result<()> {
+        self.debit(payer, fee)?;
+        if let Some(treasury) = treasury {
+            if let Err(e) = self.credit(treasury, fee) {
+                log::warn!("burning fee that the treasury cannot hold: {e}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Takes the stake of both players of `game_id`, leaving both balances
+    /// untouched if either cannot cover it
+    pub fn lock_stakes(&mut self, game_id: u64, escrow: Escrow) -> io::Result<()> {
+        if self.escrows.contains_key(&game_id) {
+            return Err(Error::other(format!(
+                "stakes are already locked for game {game_id}"
+            )));
+        }
+
+        self.debit(escrow.white, escrow.stake)?;
+        if let Err(e) = self.debit(escrow.black, escrow.stake) {
+            // Cannot overflow, the stake was just taken from this balance
+            self.credit(escrow.white, escrow.stake)?;
+            return Err(e);
+        }
+        self.escrows.insert(game_id, escrow);
+
+        Ok(())
+    }
+
+    /// Pays out the stakes of `game_id`: the winner takes both, and each
+    /// player gets theirs back on a draw or if the game ended without result.
+    /// Does nothing if no stakes are locked for the game.
+    pub fn release(&mut self, game_id: u64, result: Option<GameResult>) {
+        let escrow = match self.escrows.remove(&game_id) {
+            Some(escrow) => escrow,
+            None => return,
+        };
+        let payees = match result {
+            Some(GameResult::WhiteWins) => [escrow.white, escrow.white],
+            Some(GameResult::BlackWins) => [escrow.black, escrow.black],
+            Some(GameResult::Draw) | None => [escrow.white, escrow.black],
+        };
+        for payee in payees {
+            if let Err(e) = self.credit(payee, escrow.stake) {
+                log::warn!("could not pay out the stake of game {game_id}: {e}");
+            }
+        }
+    }
+
+    pub fn escrow(&self, game_id: u64) -> Option<Escrow> {
+        self.escrows.get(&game_id).copied()
+    }
+
+    /// Returns the funds `account` has locked in the games it is playing
+    pub fn locked(&self, account: Address) -> u64 {
+        self.escrows
+            .values()
+            .map(|e| {
+                let stakes = u64::from(e.white == account) + u64::from(e.black == account);
+                e.stake.saturating_mul(stakes)
+            })
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Empty accounts are not kept
+    fn set_balance(&mut self, account: Address, balance: u64) {
+        if balance == 0 {
+            self.balances.remove(&account);
+        } else {
+            self.balances.insert(account, balance);
+        }
+    }
+}
+
+#[test]
+fn test_ledger() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let mut ledger = Ledger::from_allocations(&BTreeMap::from([(alice, 100), (bob, u64::MAX)]));
+
+    assert!(ledger.transfer(alice, bob, 1).is_err());
+    assert_eq!(
+        (ledger.balance(alice), ledger.balance(bob)),
+        (100, u64::MAX)
+    );
+    assert!(ledger.transfer(alice, alice, 1).is_err());
+
+    ledger.transfer(bob, alice, 50).unwrap();
+    assert_eq!(ledger.balance(alice), 150);
+    assert!(ledger.transfer(alice, bob, 151).is_err());
+    ledger.transfer(alice, bob, 50).unwrap();
+    assert_eq!(ledger.balance(bob), u64::MAX);
+    ledger.debit(alice, 100).unwrap();
+    assert_eq!(ledger.balance(alice), 0);
+    assert!(!ledger.balances.contains_key(&alice));
+}
+
+#[test]
+fn test_fees() {
+    let alice = Address::repeat_byte(1);
+    let treasury = Address::repeat_byte(2);
+    let mut ledger = Ledger::from_allocations(&BTreeMap::from([(alice, 10)]));
+
+    ledger.pay_fee(alice, 3, None).unwrap();
+    ledger.pay_fee(alice, 3, Some(treasury)).unwrap();
+    assert!(ledger.pay_fee(alice, 5, Some(treasury)).is_err());
+    assert_eq!((ledger.balance(alice), ledger.balance(treasury)), (4, 3));
+}
+
+#[test]
+fn test_escrow() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let mut ledger = Ledger::from_allocations(&BTreeMap::from([(alice, 100), (bob, 30)]));
+    let escrow = |stake| Escrow {
+        white: alice,
+        black: bob,
+        stake,
+    };
+
+    // Bob cannot cover the stake, so Alice keeps hers
+    assert!(ledger.lock_stakes(1, escrow(50)).is_err());
+    assert_eq!((ledger.balance(alice), ledger.balance(bob)), (100, 30));
+
+    ledger.lock_stakes(1, escrow(20)).unwrap();
+    assert!(ledger.lock_stakes(1, escrow(1)).is_err());
+    assert_eq!((ledger.balance(alice), ledger.balance(bob)), (80, 10));
+    assert_eq!((ledger.locked(alice), ledger.locked(bob)), (20, 20));
+
+    ledger.release(1, Some(GameResult::BlackWins));
+    assert_eq!((ledger.balance(alice), ledger.balance(bob)), (80, 50));
+    assert_eq!(ledger.escrow(1), None);
+
+    // Paying out twice does nothing
+    ledger.release(1, Some(GameResult::WhiteWins));
+    assert_eq!((ledger.balance(alice), ledger.balance(bob)), (80, 50));
+
+    ledger.lock_stakes(2, escrow(10)).unwrap();
+    ledger.release(2, Some(GameResult::Draw));
+    ledger.lock_stakes(3, escrow(10)).unwrap();
+    ledger.release(3, None);
+    assert_eq!((ledger.balance(alice), ledger.balance(bob)), (80, 50));
+}
@@ -1,25 +1,598 @@
 //! Manages the virtual machine states.
 
+pub mod clock;
+pub mod ledger;
+pub mod names;
+pub mod puzzle;
+pub mod rating;
+pub mod simul;
+pub mod stats;
+pub mod team;
+pub mod tournament;
+pub mod variant;
+
 use std::{
-    collections::{hash_map::DefaultHasher, HashMap},
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
     hash::{Hash, Hasher},
     io::{self, Error, ErrorKind},
     sync::Arc,
 };
 
-use crate::block::Block;
+use crate::{
+    block::Block,
+    genesis::{Genesis, PauseExpiry},
+};
 use avalanche_types::{choices, ids, subnet};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
-use alloy_primitives::Address;
-use shakmaty::{Chess, Color, Move, Position};
+use alloy_primitives::{Address, B256};
+use shakmaty::{
+    fen::Fen, san::SanPlus, uci::Uci, variant::VariantPosition, ByColor, CastlingMode, Color,
+    EnPassantMode, Move, Outcome, Position,
+};
+
+use self::{
+    clock::{Clock, TimeControl},
+    ledger::{Escrow, Ledger},
+    names::NameRegistry,
+    puzzle::Puzzle,
+    rating::{Rating, RatingPool, TimeCategory},
+    simul::{Simul, SimulBoard},
+    stats::{PlayerStats, Score},
+    team::{Consultation, Team},
+    tournament::{BracketGame, Pairing, Standing, Tournament, TournamentFormat},
+    variant::{starting_position, GameVariant},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+impl GameResult {
+    /// Returns the result where `color` has won
+    pub fn win_for(color: Color) -> Self {
+        match color {
+            Color::White => GameResult::WhiteWins,
+            Color::Black => GameResult::BlackWins,
+        }
+    }
+}
+
+/// Reason for a game being over
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    Checkmate,
+    Stalemate,
+    InsufficientMaterial,
+    /// Ended by a variant specific rule, e.g. a king reaching the hill
+    VariantEnd,
+    TimeForfeit,
+    /// Result declared by the players of an imported game, e.g. after a
+    /// resignation
+    Declared,
+    /// Decided by the chain's rule for games paused for too long
+    Adjudication,
+    /// Called off by a player before any move was made
+    Aborted,
+    /// Called off by the arbiter set in genesis
+    Arbiter,
+    /// Lost by a player who withdrew from the knockout it was played in
+    Forfeit,
+}
+
+impl Termination {
+    /// Returns `true` if the game was called off rather than decided. Such
+    /// games are recorded as draws, but are neither rated nor paid out.
+    pub fn is_void(self) -> bool {
+        matches!(self, Termination::Aborted | Termination::Arbiter)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameOutcome {
+    pub result: GameResult,
+    pub termination: Termination,
+}
+
+impl GameOutcome {
+    /// Derives the outcome of a game from a position where the game is over
+    fn from_position<P: Position>(pos: &P) -> Option<Self> {
+        let result = match pos.outcome()? {
+            Outcome::Decisive { winner } => GameResult::win_for(winner),
+            Outcome::Draw => GameResult::Draw,
+        };
+        let termination = if pos.is_variant_end() {
+            Termination::VariantEnd
+        } else if pos.is_checkmate() {
+            Termination::Checkmate
+        } else if pos.is_stalemate() {
+            Termination::Stalemate
+        } else {
+            Termination::InsufficientMaterial
+        };
+
+        Some(GameOutcome {
+            result,
+            termination,
+        })
+    }
+}
+
+/// The block a state transition was included in
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInfo {
+    pub id: ids::Id,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+/// A move that was applied to a game
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MoveRecord {
+    /// Half-move number within the game, starting at 1
+    pub ply: usize,
+    pub san: String,
+    pub uci: String,
+    /// Block that included the move
+    pub block_id: ids::Id,
+    pub block_height: u64,
+    pub block_timestamp: u64,
+    /// Clocks just before the move was made, if the game is timed
+    #[serde(default)]
+    pub clock: Option<Clock>,
+}
+
+/// A player's request to undo the last moves of a game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TakebackRequest {
+    pub requester: Address,
+    /// Number of half-moves to undo
+    pub plies: usize,
+}
+
+/// Pause of a timed game, agreed on by both players
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pause {
+    pub requested_by: Address,
+    /// Block timestamp the clocks were frozen at, once the opponent accepted
+    pub since: Option<u64>,
+}
+
+/// Maximum number of moves a player can have in their conditional moves
+pub const MAX_CONDITIONAL_MOVES: usize = 32;
+
+/// Replies to play automatically, keyed by the expected opponent move in UCI
+pub type ConditionalMoves = BTreeMap<String, ConditionalLine>;
+
+/// A reply to an expected opponent move, and what to play after it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConditionalLine {
+    /// Reply in UCI
+    pub reply: String,
+    /// Conditional moves for after the reply
+    #[serde(default)]
+    pub then: ConditionalMoves,
+}
+
+/// Checks that every move of `moves` is legal where it would be played from
+/// `pos`, where the opponent is to move, and returns them with moves written
+/// the way the game history writes them
+fn normalize_conditional_moves(
+    pos: &VariantPosition,
+    moves: &ConditionalMoves,
+    castling_mode: CastlingMode,
+    budget: &mut usize,
+) -> io::Result<ConditionalMoves> {
+    let parse = |pos: &VariantPosition, uci: &str| {
+        Uci::from_ascii(uci.as_bytes())
+            .ok()
+            .and_then(|u| u.to_move(pos).ok())
+            .ok_or_else(|| Error::other(format!("conditional move '{uci}' is not legal")))
+    };
+
+    let mut normalized = ConditionalMoves::new();
+    for (expected, line) in moves {
+        *budget = budget.checked_sub(2).ok_or_else(|| {
+            Error::other(format!(
+                "at most {MAX_CONDITIONAL_MOVES} conditional moves can be set"
+            ))
+        })?;
+
+        let mut after_expected = pos.clone();
+        let expected = parse(pos, expected)?;
+        after_expected.play_unchecked(&expected);
+        let mut after_reply = after_expected.clone();
+        let reply = parse(&after_expected, &line.reply)?;
+        after_reply.play_unchecked(&reply);
+
+        normalized.insert(
+            expected.to_uci(castling_mode).to_string(),
+            ConditionalLine {
+                reply: reply.to_uci(castling_mode).to_string(),
+                then: normalize_conditional_moves(&after_reply, &line.then, castling_mode, budget)?,
+            },
+        );
+    }
+
+    Ok(normalized)
+}
 
 #[derive(Clone)]
 pub struct GameState {
-    game: Chess,
+    game: VariantPosition,
+    variant: GameVariant,
     white: Address,
     black: Address,
+    /// FEN of the position the game started from
+    starting_fen: String,
+    time_control: Option<TimeControl>,
+    clock: Option<Clock>,
+    outcome: Option<GameOutcome>,
+    /// Every move played so far, in order
+    history: Vec<MoveRecord>,
+    /// Block timestamp of the game's creation
+    created_at: u64,
+    /// Whether the game was played outside of ChessVM and imported
+    imported: bool,
+    /// Whether the game counts towards ratings and statistics
+    rated: bool,
+    /// Takeback waiting for the opponent's approval, until the next move
+    takeback: Option<TakebackRequest>,
+    /// Replies each side set up in advance for the opponent's next moves
+    conditional_moves: ByColor<ConditionalMoves>,
+    /// Pause requested or in effect
+    pause: Option<Pause>,
+    /// Game this game is a rematch of
+    rematch_of: Option<u64>,
+    /// Tournament the game is played in
+    tournament: Option<u64>,
+    /// Simul the game is a board of
+    simul: Option<u64>,
+    /// Amount each player wagered on the game
+    stake: u64,
+    /// Teams voting on the moves, if it is a consultation game
+    consultation: Option<Consultation>,
+}
+
+impl GameState {
+    pub fn new(
+        game: VariantPosition,
+        variant: GameVariant,
+        white: Address,
+        black: Address,
+        time_control: Option<TimeControl>,
+        block_time: u64,
+    ) -> Self {
+        Self {
+            starting_fen: Fen::from_position(game.clone(), EnPassantMode::Legal).to_string(),
+            variant,
+            white,
+            black,
+            time_control,
+            clock: time_control.map(|tc| Clock::new(tc, block_time)),
+            outcome: GameOutcome::from_position(&game),
+            history: Vec::new(),
+            created_at: block_time,
+            imported: false,
+            rated: true,
+            takeback: None,
+            conditional_moves: ByColor::default(),
+            pause: None,
+            rematch_of: None,
+            tournament: None,
+            simul: None,
+            stake: 0,
+            consultation: None,
+            game,
+        }
+    }
+
+    pub fn game(&self) -> &VariantPosition {
+        &self.game
+    }
+
+    pub fn variant(&self) -> GameVariant {
+        self.variant
+    }
+
+    pub fn white(&self) -> Address {
+        self.white
+    }
+
+    pub fn black(&self) -> Address {
+        self.black
+    }
+
+    pub fn starting_fen(&self) -> &str {
+        &self.starting_fen
+    }
+
+    pub fn time_control(&self) -> Option<TimeControl> {
+        self.time_control
+    }
+
+    pub fn clock(&self) -> Option<Clock> {
+        self.clock
+    }
+
+    pub fn outcome(&self) -> Option<GameOutcome> {
+        self.outcome
+    }
+
+    pub fn history(&self) -> &[MoveRecord] {
+        &self.history
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn imported(&self) -> bool {
+        self.imported
+    }
+
+    pub fn rated(&self) -> bool {
+        self.rated
+    }
+
+    pub fn takeback(&self) -> Option<TakebackRequest> {
+        self.takeback
+    }
+
+    pub fn conditional_moves(&self, color: Color) -> &ConditionalMoves {
+        self.conditional_moves.get(color)
+    }
+
+    pub fn pause(&self) -> Option<Pause> {
+        self.pause
+    }
+
+    pub fn rematch_of(&self) -> Option<u64> {
+        self.rematch_of
+    }
+
+    pub fn tournament(&self) -> Option<u64> {
+        self.tournament
+    }
+
+    pub fn stake(&self) -> u64 {
+        self.stake
+    }
+
+    pub fn consultation(&self) -> Option<&Consultation> {
+        self.consultation.as_ref()
+    }
+
+    /// Returns the block timestamp at which the side to move started its turn
+    pub fn turn_started_at(&self) -> u64 {
+        self.history
+            .last()
+            .map_or(self.created_at, |m| m.block_timestamp)
+    }
+
+    /// Returns the block timestamp after which the most voted move is
+    /// played, if it is a consultation game
+    pub fn voting_deadline(&self) -> Option<u64> {
+        self.consultation
+            .as_ref()
+            .map(|c| self.turn_started_at().saturating_add(c.voting_window))
+    }
+
+    /// Returns `true` if the clocks are frozen
+    pub fn is_paused(&self) -> bool {
+        matches!(self.pause, Some(Pause { since: Some(_), .. }))
+    }
+
+    /// Returns the player of the game that is not `player`
+    fn opponent_of(&self, player: Address) -> Address {
+        if player == self.white {
+            self.black
+        } else {
+            self.white
+        }
+    }
+
+    /// Decides a game whose pause lasted too long, following `rule`
+    fn adjudicate(&mut self, rule: PauseExpiry) {
+        let result = match rule {
+            PauseExpiry::Resume => return,
+            PauseExpiry::Draw => GameResult::Draw,
+            // Piece values only hold for the rules of standard chess; in
+            // Antichess losing material is the goal, and Horde starts with
+            // uneven armies
+            PauseExpiry::Material
+                if !matches!(self.variant, GameVariant::Standard | GameVariant::Chess960) =>
+            {
+                GameResult::Draw
+            }
+            PauseExpiry::Material => {
+                let material = self.game.board().material();
+                let value = |color: Color| {
+                    let pieces = material.get(color);
+                    pieces.pawn as u32
+                        + 3 * (pieces.knight as u32 + pieces.bishop as u32)
+                        + 5 * pieces.rook as u32
+                        + 9 * pieces.queen as u32
+                };
+                match value(Color::White).cmp(&value(Color::Black)) {
+                    std::cmp::Ordering::Greater => GameResult::WhiteWins,
+                    std::cmp::Ordering::Less => GameResult::BlackWins,
+                    std::cmp::Ordering::Equal => GameResult::Draw,
+                }
+            }
+        };
+        self.outcome = Some(GameOutcome {
+            result,
+            termination: Termination::Adjudication,
+        });
+    }
+
+    /// Returns the address of the player of `color`
+    pub fn address_of(&self, color: Color) -> Address {
+        match color {
+            Color::White => self.white,
+            Color::Black => self.black,
+        }
+    }
+
+    /// Returns the position after the first `plies` moves of the game,
+    /// replayed from its starting position
+    fn replay(&self, plies: usize) -> io::Result<VariantPosition> {
+        let mut pos = starting_position(self.variant, Some(&self.starting_fen), 0)?;
+        for record in self.history.iter().take(plies) {
+            let mv = Uci::from_ascii(record.uci.as_bytes())
+                .ok()
+                .and_then(|uci| uci.to_move(&pos).ok())
+                .ok_or_else(|| Error::other(format!("could not replay move {}", record.ply)))?;
+            pos.play_unchecked(&mv);
+        }
+
+        Ok(pos)
+    }
+
+    /// Returns the record of the game as it stands
+    pub fn to_archive(&self) -> ArchivedGame {
+        ArchivedGame {
+            variant: self.variant,
+            white: self.white,
+            black: self.black,
+            starting_fen: self.starting_fen.clone(),
+            final_fen: Fen::from_position(self.game.clone(), EnPassantMode::Legal).to_string(),
+            outcome: self.outcome,
+            move_count: self.history.len(),
+            time_control: self.time_control,
+            history: self.history.clone(),
+            created_at: self.created_at,
+            imported: self.imported,
+            rated: self.rated,
+            rematch_of: self.rematch_of,
+            tournament: self.tournament,
+            simul: self.simul,
+            stake: self.stake,
+            consultation: self.consultation.clone(),
+        }
+    }
+}
+
+/// What is kept of a game once it has ended
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedGame {
+    pub variant: GameVariant,
+    pub white: Address,
+    pub black: Address,
+    /// FEN of the position the game started from
+    pub starting_fen: String,
+    /// FEN of the position the game ended in
+    pub final_fen: String,
+    /// Aborted games and games ended by the arbiter are recorded as draws
+    /// with the `Aborted` or `Arbiter` termination
+    pub outcome: Option<GameOutcome>,
+    /// Number of half-moves played
+    pub move_count: usize,
+    pub time_control: Option<TimeControl>,
+    pub history: Vec<MoveRecord>,
+    /// Block timestamp of the game's creation
+    pub created_at: u64,
+    /// Whether the game was played outside of ChessVM and imported
+    pub imported: bool,
+    /// Whether the game counted towards ratings and statistics
+    #[serde(default)]
+    pub rated: bool,
+    /// Game this game is a rematch of
+    #[serde(default)]
+    pub rematch_of: Option<u64>,
+    /// Tournament the game was played in
+    #[serde(default)]
+    pub tournament: Option<u64>,
+    /// Simul the game was a board of
+    #[serde(default)]
+    pub simul: Option<u64>,
+    /// Amount each player wagered on the game
+    #[serde(default)]
+    pub stake: u64,
+    /// Teams that played the game, if it was a consultation game
+    #[serde(default)]
+    pub consultation: Option<Consultation>,
+}
+
+/// Which side the creator of a challenge wants to play
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorPreference {
+    White,
+    Black,
+    #[default]
+    Random,
+}
+
+/// A game offer waiting for an opponent to accept it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub challenger: Address,
+    /// If set, only this address may accept the challenge
+    pub opponent: Option<Address>,
+    pub color: ColorPreference,
+    pub time_control: Option<TimeControl>,
+    pub variant: GameVariant,
+    /// Amount each player wagers, locked when the challenge is accepted
+    #[serde(default)]
+    pub stake: u64,
+    /// Block timestamp of the challenge's creation
+    pub created_at: u64,
+}
+
+/// Ratings, inclusive, a seek accepts opponents from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatingRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+/// A player looking for any opponent to play a game with, paired by the
+/// chain when blocks are built
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Seek {
+    pub player: Address,
+    pub time_control: Option<TimeControl>,
+    pub variant: GameVariant,
+    /// Ratings of acceptable opponents, in the pool of the seek; any if not
+    /// set
+    pub rating_range: Option<RatingRange>,
+    /// Block timestamp of the seek's creation
+    pub created_at: u64,
+}
+
+impl Seek {
+    fn accepts(&self, rating: u32) -> bool {
+        self.rating_range
+            .is_none_or(|range| range.min <= rating && rating <= range.max)
+    }
+}
+
+/// A player's offer to play a finished game again with colors swapped
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rematch {
+    pub offered_by: Address,
+    /// ID of the rematch game, once the opponent accepted
+    pub game_id: Option<u64>,
+}
+
+/// Outcome of a transaction once its block was accepted
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    pub tx_id: ids::Id,
+    /// Block that included the transaction
+    pub block_id: ids::Id,
+    pub block_height: u64,
+    /// Why the transaction was rejected, if it was; a rejected transaction
+    /// leaves the state untouched, apart from the fee it paid
+    pub error: Option<String>,
+    /// Fee paid by the sender, tip included
+    #[serde(default)]
+    pub fee: u64,
 }
 
 /// Manages block and chain states for this Vm, both in-memory and persistent.
@@ -32,6 +605,49 @@ pub struct State {
     pub verified_blocks: Arc<RwLock<HashMap<ids::Id, Block>>>,
 
     pub game_states: Arc<RwLock<HashMap<u64, GameState>>>,
+
+    /// Maps game ID to games that have ended, keeping `game_states` to the
+    /// games being played
+    pub archived_games: Arc<RwLock<HashMap<u64, ArchivedGame>>>,
+
+    /// Maps challenge ID to challenges that have not been accepted yet
+    pub challenges: Arc<RwLock<HashMap<u64, Challenge>>>,
+
+    /// Maps game ID to the rematch offered after it
+    pub rematches: Arc<RwLock<HashMap<u64, Rematch>>>,
+
+    /// Ratings of players in each pool
+    pub ratings: Arc<RwLock<HashMap<RatingPool, HashMap<Address, Rating>>>>,
+
+    /// Statistics of every player who finished a game
+    pub player_stats: Arc<RwLock<HashMap<Address, PlayerStats>>>,
+
+    /// Names registered by players
+    pub names: Arc<RwLock<NameRegistry>>,
+
+    /// Maps tournament ID to tournaments, finished or not
+    pub tournaments: Arc<RwLock<HashMap<u64, Tournament>>>,
+
+    /// Maps simul ID to simuls, finished or not
+    pub simuls: Arc<RwLock<HashMap<u64, Simul>>>,
+
+    /// Maps seek ID to seeks waiting for an opponent
+    pub seeks: Arc<RwLock<HashMap<u64, Seek>>>,
+
+    /// Balances of accounts, seeded from the genesis allocations
+    pub ledger: Arc<RwLock<Ledger>>,
+
+    /// Maps puzzle ID to puzzles, kept once closed
+    pub puzzles: Arc<RwLock<HashMap<u64, Puzzle>>>,
+
+    /// Maps team ID to teams voting together in consultation games
+    pub teams: Arc<RwLock<HashMap<u64, Team>>>,
+
+    /// Maps transaction ID to the receipt of its execution
+    pub receipts: Arc<RwLock<HashMap<ids::Id, Receipt>>>,
+
+    /// Chain configuration the VM was started with
+    pub genesis: Arc<Genesis>,
 }
 
 impl Default for State {
@@ -40,10 +656,29 @@ impl Default for State {
             db: Arc::new(RwLock::new(subnet::rpc::database::memdb::Database::new())),
             verified_blocks: Arc::new(RwLock::new(HashMap::new())),
             game_states: Arc::new(RwLock::new(HashMap::new())),
+            archived_games: Arc::new(RwLock::new(HashMap::new())),
+            challenges: Arc::new(RwLock::new(HashMap::new())),
+            rematches: Arc::new(RwLock::new(HashMap::new())),
+            ratings: Arc::new(RwLock::new(HashMap::new())),
+            player_stats: Arc::new(RwLock::new(HashMap::new())),
+            names: Arc::new(RwLock::new(NameRegistry::default())),
+            tournaments: Arc::new(RwLock::new(HashMap::new())),
+            simuls: Arc::new(RwLock::new(HashMap::new())),
+            seeks: Arc::new(RwLock::new(HashMap::new())),
+            ledger: Arc::new(RwLock::new(Ledger::default())),
+            puzzles: Arc::new(RwLock::new(HashMap::new())),
+            teams: Arc::new(RwLock::new(HashMap::new())),
+            receipts: Arc::new(RwLock::new(HashMap::new())),
+            genesis: Arc::new(Genesis::default()),
         }
     }
 }
 
+/// Returns a copy of `table` that can be changed on its own
+async fn copy_table<T: Clone>(table: &Arc<RwLock<T>>) -> Arc<RwLock<T>> {
+    Arc::new(RwLock::new(table.read().await.clone()))
+}
+
 const LAST_ACCEPTED_BLOCK_KEY: &[u8] = b"last_accepted_block";
 
 const STATUS_PREFIX: u8 = 0x0;
@@ -60,13 +695,141 @@ fn block_with_status_key(blk_id: &ids::Id) -> Vec<u8> {
     k
 }
 
-pub fn calculate_game_id(white: Address, black: Address) -> u64 {
-    let mut combined_addresses = Vec::new();
-    combined_addresses.extend_from_slice(white.as_slice());
-    combined_addresses.extend_from_slice(black.as_slice());
+/// Returns the ID of a game created by `white`; the nonce tells apart the
+/// games of the same players
+pub fn calculate_game_id(white: Address, black: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    white.as_slice().hash(&mut hasher);
+    black.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of a challenge, which is also the ID of the game it creates
+/// once accepted
+pub fn calculate_challenge_id(challenger: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    challenger.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether `player` gets white in a game started by a block. Colors are
+/// drawn from the ID of the block so that neither player can choose theirs,
+/// while every node agrees on them.
+pub fn draws_white(block_id: &ids::Id, player: Address) -> bool {
+    let mut hasher = DefaultHasher::new();
+    block_id.as_ref().hash(&mut hasher);
+    player.as_slice().hash(&mut hasher);
+    "color".hash(&mut hasher);
+    hasher.finish() & 1 == 0
+}
+
+/// Returns the ID of an imported game
+pub fn calculate_import_id(white: Address, black: Address, movetext: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    white.as_slice().hash(&mut hasher);
+    black.as_slice().hash(&mut hasher);
+    movetext.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of the rematch of a game
+pub fn calculate_rematch_id(game_id: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    game_id.hash(&mut hasher);
+    "rematch".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of a seek, which is also the ID of the game it creates if
+/// it is the older seek of the pair
+pub fn calculate_seek_id(player: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    player.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    "seek".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of a puzzle
+pub fn calculate_puzzle_id(poster: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    poster.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    "puzzle".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of a simul
+pub fn calculate_simul_id(host: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    host.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    "simul".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of the game played on a board of a simul, numbered from 1
+pub fn calculate_simul_game_id(simul_id: u64, board: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    simul_id.hash(&mut hasher);
+    board.hash(&mut hasher);
+    "simul".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of a team
+pub fn calculate_team_id(owner: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    owner.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    "team".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of a consultation game
+pub fn calculate_consultation_game_id(creator: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    creator.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    "consultation".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of a tournament
+pub fn calculate_tournament_id(organizer: Address, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    organizer.as_slice().hash(&mut hasher);
+    nonce.hash(&mut hasher);
+    "tournament".hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the ID of the game played on a board of a tournament round, both
+/// numbered from 1
+pub fn calculate_tournament_game_id(tournament_id: u64, round: usize, board: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    tournament_id.hash(&mut hasher);
+    round.hash(&mut hasher);
+    board.hash(&mut hasher);
+    hasher.finish()
+}
 
+/// Returns the ID of a game of a knockout match; `game` numbers the tiebreak
+/// games after the first one, from 1
+pub fn calculate_bracket_game_id(
+    tournament_id: u64,
+    round: usize,
+    index: usize,
+    game: usize,
+) -> u64 {
     let mut hasher = DefaultHasher::new();
-    combined_addresses.hash(&mut hasher);
+    tournament_id.hash(&mut hasher);
+    round.hash(&mut hasher);
+    index.hash(&mut hasher);
+    game.hash(&mut hasher);
+    "bracket".hash(&mut hasher);
     hasher.finish()
 }
 
@@ -145,6 +908,92 @@ impl State {
         }
     }
 
+    /// Returns a copy of the state whose tables can be changed without
+    /// touching this one, e.g. to apply blocks that are not accepted yet.
+    /// The database and the verified blocks are shared.
+    pub async fn fork(&self) -> State {
+        State {
+            db: self.db.clone(),
+            verified_blocks: self.verified_blocks.clone(),
+            game_states: copy_table(&self.game_states).await,
+            archived_games: copy_table(&self.archived_games).await,
+            challenges: copy_table(&self.challenges).await,
+            rematches: copy_table(&self.rematches).await,
+            ratings: copy_table(&self.ratings).await,
+            player_stats: copy_table(&self.player_stats).await,
+            names: copy_table(&self.names).await,
+            tournaments: copy_table(&self.tournaments).await,
+            simuls: copy_table(&self.simuls).await,
+            seeks: copy_table(&self.seeks).await,
+            ledger: copy_table(&self.ledger).await,
+            puzzles: copy_table(&self.puzzles).await,
+            teams: copy_table(&self.teams).await,
+            receipts: copy_table(&self.receipts).await,
+            genesis: self.genesis.clone(),
+        }
+    }
+
+    /// Returns the state as of block `blk_id`: this state if it is the last
+    /// accepted block, or else a fork of it with the verified blocks leading
+    /// to `blk_id` applied
+    pub async fn state_at(&self, blk_id: &ids::Id) -> io::Result<State> {
+        let last_accepted = self.get_last_accepted_block_id().await?;
+        let mut pending = Vec::new();
+        let mut id = *blk_id;
+        while id != last_accepted {
+            let blk = match self.verified_blocks.read().await.get(&id) {
+                Some(blk) => blk.clone(),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("block {blk_id} does not follow the last accepted block"),
+                    ))
+                }
+            };
+            id = blk.parent_id();
+            pending.push(blk);
+        }
+        if pending.is_empty() {
+            return Ok(self.clone());
+        }
+
+        let fork = self.fork().await;
+        for blk in pending.iter().rev() {
+            blk.apply(&fork).await;
+        }
+
+        Ok(fork)
+    }
+
+    /// Rebuilds the tables by applying every accepted block again, from the
+    /// genesis block up to the last accepted one. Only blocks are written to
+    /// the database, so this is how a restarted node gets back the games,
+    /// balances, ratings and everything else its peers kept in memory.
+    pub async fn replay_accepted_blocks(&self) -> io::Result<()> {
+        if !self.has_last_accepted_block().await? {
+            return Ok(());
+        }
+
+        let mut accepted = Vec::new();
+        let mut id = self.get_last_accepted_block_id().await?;
+        loop {
+            let blk = self.get_block(&id).await?;
+            id = blk.parent_id();
+            let is_genesis = blk.height() == 0;
+            accepted.push(blk);
+            if is_genesis {
+                break;
+            }
+        }
+
+        for blk in accepted.iter().rev() {
+            blk.apply(self).await;
+        }
+        log::info!("replayed {} accepted blocks", accepted.len());
+
+        Ok(())
+    }
+
     /// Adds a block to "`verified_blocks`".
     pub async fn add_verified(&mut self, block: &Block) {
         let blk_id = block.id();
@@ -210,19 +1059,32 @@ impl State {
         Ok(blk)
     }
 
-    /// Creates a new chess game without making a move
-    pub async fn create_new_game(&self, white: Address, black: Address) -> io::Result<u64> {
-        let mut game_states = self.game_states.write().await;
+    /// Creates a new chess game without making a move. Black never agrees
+    /// to such a game, so it is not rated; rated games are played through
+    /// challenges, which both players agree to.
+    pub async fn create_new_game(
+        &self,
+        white: Address,
+        black: Address,
+        variant: GameVariant,
+        fen: Option<&str>,
+        nonce: u64,
+        block_time: u64,
+    ) -> io::Result<u64> {
+        if white == black {
+            return Err(Error::other("a game needs two different players"));
+        }
+        let game_id = calculate_game_id(white, black, nonce);
 
-        let new_game = Chess::default();
-        let new_game_state = GameState {
-            game: new_game,
-            white,
-            black,
-        };
+        let new_game = starting_position(variant, fen, game_id)?;
+
+        let mut game_states = self.game_states.write().await;
+        if game_states.contains_key(&game_id) || self.is_archived(game_id).await {
+            return Err(Error::other("Game already exists!"));
+        }
 
-        // Need to create game ID
-        let game_id = calculate_game_id(white, black);
+        let mut new_game_state = GameState::new(new_game, variant, white, black, None, block_time);
+        new_game_state.rated = false;
 
         game_states.insert(game_id, new_game_state);
 
@@ -230,7 +1092,36 @@ impl State {
     }
 
     /// Makes a move on an already existing chess board
-    pub async fn make_move(&self, player: Address, game_id: u64, mv: &Move) -> io::Result<()> {
+    pub async fn make_move(
+        &self,
+        player: Address,
+        game_id: u64,
+        mv: &Move,
+        block: BlockInfo,
+    ) -> io::Result<()> {
+        let game_states = self.game_states.read().await;
+        if game_states
+            .get(&game_id)
+            .is_some_and(|g| g.consultation.is_some())
+        {
+            return Err(Error::other(
+                "the moves of consultation games are decided by votes",
+            ));
+        }
+        drop(game_states);
+
+        self.play_move(player, game_id, mv, block).await
+    }
+
+    /// Plays a move for `player` if it is their turn and the move is legal
+    async fn play_move(
+        &self,
+        player: Address,
+        game_id: u64,
+        mv: &Move,
+        block: BlockInfo,
+    ) -> io::Result<()> {
+        let block_time = block.timestamp;
         // Retrieve game board from state
         let mut game_states = self.game_states.write().await;
 
@@ -244,29 +1135,64 @@ impl State {
         // Game exists, we can unwrap directly without panicking
         let mut curr_game = game_states.get(&game_id).unwrap().clone();
 
+        // No more moves can be made once a game is over
+        if curr_game.outcome.is_some() {
+            return Err(Error::other("game is over"));
+        }
+
         // Check if player can make move
-        if curr_game.game.turn() == Color::White {
-            if player != curr_game.white {
-                // return Err(Error::new(ErrorKind::Other, "It is not the
-                // player's turn!"));
-                return Ok(()); // Not changing game state maintains invariants
-            }
-        } else {
-            if player != curr_game.black {
-                // return Err(Error::new(ErrorKind::Other, "It is not the
-                // player's turn!"));
-                return Ok(());
-            }
+        let turn = curr_game.game.turn();
+        if player != curr_game.address_of(turn) {
+            return Err(Error::other("It is not the player's turn!"));
         }
 
         if !curr_game.game.is_legal(mv) {
-            return Ok(());
+            return Err(Error::other("illegal move"));
+        }
+
+        if curr_game.is_paused() {
+            return Err(Error::other("game is paused"));
+        }
+
+        let clock_before = curr_game.clock;
+        // Charge the time spent thinking to the player's clock; a player who
+        // ran out of time loses instead of making the move
+        if let (Some(clock), Some(tc)) = (curr_game.clock.as_mut(), curr_game.time_control) {
+            if let Some(outcome) = clock.charge(turn, block_time, tc.increment) {
+                curr_game.outcome = Some(outcome);
+                game_states.insert(game_id, curr_game);
+
+                return Ok(());
+            }
         }
 
+        // Notations have to be computed from the position the move is made in
+        let san = SanPlus::from_move(curr_game.game.clone(), mv).to_string();
+        let uci = mv.to_uci(curr_game.variant.castling_mode()).to_string();
+
         // Player can make the move, we update the game state and write back
         if let Ok(v) = curr_game.game.play(mv) {
             // Update game state
+            curr_game.history.push(MoveRecord {
+                ply: curr_game.history.len() + 1,
+                san,
+                uci,
+                block_id: block.id,
+                block_height: block.height,
+                block_timestamp: block.timestamp,
+                clock: clock_before,
+            });
+            curr_game.outcome = GameOutcome::from_position(&v);
             curr_game.game = v;
+            // Pending takeback and pause requests expire once a move is made
+            curr_game.takeback = None;
+            curr_game.pause = None;
+            // Votes are cast again for the next move
+            if let Some(consultation) = curr_game.consultation.as_mut() {
+                consultation.votes.clear();
+            }
+            // Conditional moves are only kept for the opponent's turn
+            curr_game.conditional_moves.get_mut(turn).clear();
             // Write back to state
             game_states.insert(game_id, curr_game);
 
@@ -276,22 +1202,648 @@ impl State {
         Err(Error::new(ErrorKind::Other, "MakeMove Failed!"))
     }
 
-    /// Ends a chess game, if possible
-    pub async fn end_game(&self, game_id: u64) -> io::Result<Chess> {
-        // Get write access to state
-        let mut game_states = self.game_states.write().await;
-
-        // If game not found
-        if !game_states.contains_key(&game_id) {
-            return Err(Error::new(ErrorKind::Other, "Game not found!"));
+    /// Stores a game played outside of ChessVM as a finished game. Its moves
+    /// are replayed from `game`'s starting position and must all be legal.
+    /// `result` is only used when the final position does not decide the game
+    /// by itself.
+    pub async fn import_game(
+        &self,
+        game_id: u64,
+        mut game: GameState,
+        moves: &[SanPlus],
+        result: GameResult,
+        block: BlockInfo,
+    ) -> io::Result<()> {
+        if self.game_exists(game_id).await {
+            return Err(Error::other("game already exists"));
         }
 
-        // Game exists, we now remove
-        Ok(game_states.remove(&game_id).unwrap().game)
-    }
+        for (i, san) in moves.iter().enumerate() {
+            if game.outcome.is_some() {
+                return Err(Error::other(format!(
+                    "move {} is played after the game ended",
+                    i + 1
+                )));
+            }
+            let mv = san
+                .san
+                .to_move(&game.game)
+                .map_err(|e| Error::other(format!("move {} '{san}' is not legal: {e}", i + 1)))?;
+
+            game.history.push(MoveRecord {
+                ply: i + 1,
+                san: SanPlus::from_move(game.game.clone(), &mv).to_string(),
+                uci: mv.to_uci(game.variant.castling_mode()).to_string(),
+                block_id: block.id,
+                block_height: block.height,
+                block_timestamp: block.timestamp,
+                clock: None,
+            });
+            game.game.play_unchecked(&mv);
+            game.outcome = GameOutcome::from_position(&game.game);
+        }
+
+        match game.outcome {
+            Some(outcome) if outcome.result != result => {
+                return Err(Error::other(
+                    "declared result does not match the final position",
+                ))
+            }
+            Some(_) => {}
+            None => {
+                game.outcome = Some(GameOutcome {
+                    result,
+                    termination: Termination::Declared,
+                })
+            }
+        }
+        game.imported = true;
+
+        // Imported games are over, so they go straight to the archive
+        let mut archived_games = self.archived_games.write().await;
+        archived_games.insert(game_id, game.to_archive());
+
+        Ok(())
+    }
+
+    /// Asks the opponent for the last `plies` moves of a game to be undone,
+    /// replacing any pending request
+    pub async fn request_takeback(
+        &self,
+        player: Address,
+        game_id: u64,
+        plies: usize,
+    ) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        if player != game.white && player != game.black {
+            return Err(Error::other("only the players can request a takeback"));
+        }
+        if game.consultation.is_some() {
+            return Err(Error::other(
+                "the moves of consultation games are decided by votes",
+            ));
+        }
+        if game.outcome.is_some() {
+            return Err(Error::other("game is already over"));
+        }
+        if plies == 0 || plies > game.history.len() {
+            return Err(Error::other(format!(
+                "cannot take back {plies} plies, {} were played",
+                game.history.len()
+            )));
+        }
+
+        game.takeback = Some(TakebackRequest {
+            requester: player,
+            plies,
+        });
+
+        Ok(())
+    }
+
+    /// Accepts the opponent's takeback request, rewinding the position, side
+    /// to move and clocks to how they were before the undone moves. The clock
+    /// of the side to move restarts at `block_time`.
+    pub async fn accept_takeback(
+        &self,
+        player: Address,
+        game_id: u64,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        let request = match game.takeback {
+            Some(request) if game.outcome.is_none() && game.consultation.is_none() => request,
+            _ => return Err(Error::other("no takeback was requested")),
+        };
+        let opponent = if request.requester == game.white {
+            game.black
+        } else {
+            game.white
+        };
+        if player != opponent {
+            return Err(Error::other("only the opponent can accept a takeback"));
+        }
+
+        let plies = game.history.len() - request.plies;
+        let pos = game.replay(plies)?;
+        if let Some(mut clock) = game.history[plies].clock {
+            clock.last_update = block_time;
+            game.clock = Some(clock);
+        }
+        game.history.truncate(plies);
+        game.game = pos;
+        game.outcome = None;
+        game.takeback = None;
+        game.conditional_moves = ByColor::default();
+
+        Ok(())
+    }
+
+    /// Sets the replies `player` wants played automatically to the opponent's
+    /// next moves, replacing any previous ones. They can only be set while
+    /// the opponent is to move.
+    pub async fn set_conditional_moves(
+        &self,
+        player: Address,
+        game_id: u64,
+        moves: &ConditionalMoves,
+    ) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        if game.consultation.is_some() {
+            return Err(Error::other(
+                "the moves of consultation games are decided by votes",
+            ));
+        }
+        let opponent = game.game.turn();
+        if player != game.address_of(!opponent) || game.outcome.is_some() {
+            return Err(Error::other(
+                "conditional moves can only be set while the opponent is to move",
+            ));
+        }
+
+        let mut budget = MAX_CONDITIONAL_MOVES;
+        let moves = normalize_conditional_moves(
+            &game.game,
+            moves,
+            game.variant.castling_mode(),
+            &mut budget,
+        )?;
+        *game.conditional_moves.get_mut(!opponent) = moves;
+
+        Ok(())
+    }
+
+    /// Plays the conditional reply to the last move of a game, if its owner
+    /// set one up for that move. Returns `true` if a reply was played.
+    /// Conditional moves not matching the last move are cleared, and so are
+    /// those whose reply cannot be played, without failing the opponent's
+    /// move that triggered them.
+    pub async fn play_conditional_move(&self, game_id: u64, block: BlockInfo) -> io::Result<bool> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Ok(false),
+        };
+
+        let owner = game.game.turn();
+        let moves = std::mem::take(game.conditional_moves.get_mut(owner));
+        let line = match game.history.last() {
+            Some(last) if game.outcome.is_none() => moves.get(&last.uci).cloned(),
+            _ => None,
+        };
+        let line = match line {
+            Some(line) => line,
+            None => return Ok(false),
+        };
+
+        let player = game.address_of(owner);
+        let mv = match Uci::from_ascii(line.reply.as_bytes())
+            .ok()
+            .and_then(|uci| uci.to_move(&game.game).ok())
+        {
+            Some(mv) => mv,
+            None => return Ok(false),
+        };
+        let plies = game.history.len();
+        drop(game_states);
+
+        if let Err(e) = self.make_move(player, game_id, &mv, block).await {
+            log::info!("clearing conditional moves of game {game_id}: {e}");
+            return Ok(false);
+        }
+
+        // What to play next is kept for the opponent's following move, unless
+        // the reply could not be played, e.g. because the owner ran out of time
+        let mut game_states = self.game_states.write().await;
+        match game_states.get_mut(&game_id) {
+            Some(game) if game.history.len() > plies => {
+                *game.conditional_moves.get_mut(owner) = line.then;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Asks the opponent to freeze the clocks of a timed game
+    pub async fn request_pause(&self, player: Address, game_id: u64) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        if player != game.white && player != game.black {
+            return Err(Error::other("only the players can pause a game"));
+        }
+        if game.consultation.is_some() {
+            return Err(Error::other(
+                "a team owner cannot pause a consultation game alone",
+            ));
+        }
+        if game.clock.is_none() || game.outcome.is_some() {
+            return Err(Error::other("only timed games in progress can be paused"));
+        }
+        if game.is_paused() {
+            return Err(Error::other("game is already paused"));
+        }
+
+        game.pause = Some(Pause {
+            requested_by: player,
+            since: None,
+        });
+
+        Ok(())
+    }
+
+    /// Accepts the opponent's pause request, freezing both clocks at
+    /// `block_time`
+    pub async fn accept_pause(
+        &self,
+        player: Address,
+        game_id: u64,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        let requested_by = match game.pause {
+            Some(Pause {
+                requested_by,
+                since: None,
+            }) if game.outcome.is_none() => requested_by,
+            _ => return Err(Error::other("no pause was requested")),
+        };
+        if player != game.opponent_of(requested_by) {
+            return Err(Error::other("only the opponent can accept a pause"));
+        }
+
+        // The time thought so far is charged before the clocks stop
+        let turn = game.game.turn();
+        if let Some(clock) = game.clock.as_mut() {
+            if let Some(outcome) = clock.charge(turn, block_time, 0) {
+                game.outcome = Some(outcome);
+                game.pause = None;
+
+                return Ok(());
+            }
+        }
+        game.pause = Some(Pause {
+            requested_by,
+            since: Some(block_time),
+        });
+
+        Ok(())
+    }
+
+    /// Restarts the clocks of a paused game, on behalf of either player
+    pub async fn resume(&self, player: Address, game_id: u64, block_time: u64) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        if player != game.white && player != game.black {
+            return Err(Error::other("only the players can resume a game"));
+        }
+        if !game.is_paused() {
+            return Err(Error::other("game is not paused"));
+        }
+
+        if let Some(clock) = game.clock.as_mut() {
+            clock.last_update = block_time;
+        }
+        game.pause = None;
+
+        Ok(())
+    }
+
+    /// Ends the pauses that lasted longer than allowed by genesis as of
+    /// `block_time`, resuming or adjudicating the games
+    pub async fn expire_pauses(&self, block_time: u64) {
+        let mut game_states = self.game_states.write().await;
+
+        for game in game_states.values_mut() {
+            let since = match game.pause {
+                Some(Pause {
+                    since: Some(since), ..
+                }) => since,
+                _ => continue,
+            };
+            let deadline = since.saturating_add(self.genesis.max_pause);
+            if block_time < deadline {
+                continue;
+            }
+
+            // Clocks run again from the deadline, not from the block
+            if let Some(clock) = game.clock.as_mut() {
+                clock.last_update = deadline;
+            }
+            game.pause = None;
+            game.adjudicate(self.genesis.pause_expiry);
+        }
+    }
+
+    /// Ends the timed games whose side to move ran out of time as of
+    /// `block_time`, even if they never move again
+    pub async fn flag_games(&self, block_time: u64) {
+        let mut game_states = self.game_states.write().await;
+
+        for game in game_states.values_mut() {
+            if game.outcome.is_some() || game.is_paused() {
+                continue;
+            }
+            let turn = game.game.turn();
+            if let Some(outcome) = game.clock.as_mut().and_then(|c| c.flag(turn, block_time)) {
+                game.outcome = Some(outcome);
+                game.pause = None;
+            }
+        }
+    }
+
+    /// Returns `true` if a timed game is to be ended as of `block_time`
+    /// because the side to move ran out of time
+    pub async fn has_flagged_games(&self, block_time: u64) -> bool {
+        let game_states = self.game_states.read().await;
+
+        game_states.values().any(|g| {
+            g.outcome.is_none()
+                && !g.is_paused()
+                && g.clock
+                    .is_some_and(|c| c.is_out_of_time(g.game.turn(), block_time))
+        })
+    }
+
+    /// Moves the games that finished since the last call to the archive and
+    /// takes them into account, in game ID order, advancing the winners of
+    /// knockout games
+    pub async fn settle_games(&self, block_time: u64) {
+        let mut finished_ids: Vec<u64> = self
+            .game_states
+            .read()
+            .await
+            .iter()
+            .filter(|(_, g)| g.outcome.is_some())
+            .map(|(id, _)| *id)
+            .collect();
+        finished_ids.sort();
+
+        self.settle(&finished_ids, block_time).await;
+    }
+
+    /// Moves the finished games among `game_ids` to the archive and takes
+    /// them into account, in the given order
+    async fn settle(&self, game_ids: &[u64], block_time: u64) {
+        let mut game_states = self.game_states.write().await;
+        let mut archived_games = self.archived_games.write().await;
+        let mut finished: Vec<(u64, ArchivedGame)> = Vec::new();
+        for id in game_ids {
+            if game_states.get(id).is_some_and(|g| g.outcome.is_some()) {
+                let game = game_states.remove(id).unwrap().to_archive();
+                archived_games.insert(*id, game.clone());
+                finished.push((*id, game));
+            }
+        }
+        drop(archived_games);
+        drop(game_states);
+
+        for (game_id, game) in finished {
+            self.rate_game(&game).await;
+            self.record_stats(&game).await;
+            self.pay_out(game_id, &game).await;
+            if let (Some(tournament_id), Some(outcome)) = (game.tournament, game.outcome) {
+                // Called off knockout games are played again
+                let result = (!outcome.termination.is_void()).then_some(outcome.result);
+                if let Err(e) = self
+                    .advance_knockout(tournament_id, game_id, result, block_time)
+                    .await
+                {
+                    log::error!("could not advance knockout {tournament_id}: {e}");
+                }
+            }
+        }
+    }
+
+    /// Releases the stakes of a game that ended. Like for ratings, games
+    /// ending before any move is made or called off are treated as aborted,
+    /// and the stakes are refunded.
+    async fn pay_out(&self, game_id: u64, game: &ArchivedGame) {
+        let result = match game.outcome {
+            Some(outcome) if !game.history.is_empty() && !outcome.termination.is_void() => {
+                Some(outcome.result)
+            }
+            _ => None,
+        };
+
+        self.ledger.write().await.release(game_id, result);
+    }
+
+    /// Adds a finished game to the statistics of its players. Like for
+    /// ratings, unrated games and games ending before any move is made or
+    /// called off are left out.
+    async fn record_stats(&self, game: &ArchivedGame) {
+        let result = match game.outcome {
+            Some(outcome)
+                if !game.history.is_empty()
+                    && game.rated
+                    && game.consultation.is_none()
+                    && !outcome.termination.is_void() =>
+            {
+                outcome.result
+            }
+            _ => return,
+        };
+        let (white_score, black_score) = match result {
+            GameResult::WhiteWins => (Score::Win, Score::Loss),
+            GameResult::BlackWins => (Score::Loss, Score::Win),
+            GameResult::Draw => (Score::Draw, Score::Draw),
+        };
+
+        let mut player_stats = self.player_stats.write().await;
+        player_stats.entry(game.white).or_default().add_game(
+            game.black,
+            white_score,
+            game.history.len(),
+        );
+        player_stats.entry(game.black).or_default().add_game(
+            game.white,
+            black_score,
+            game.history.len(),
+        );
+    }
+
+    /// Returns the statistics of `player`, empty if they never finished a
+    /// game
+    pub async fn get_player_stats(&self, player: Address) -> PlayerStats {
+        let player_stats = self.player_stats.read().await;
+
+        player_stats.get(&player).cloned().unwrap_or_default()
+    }
+
+    /// Updates the ratings of the players of a finished game. Unrated games
+    /// and games ending before any move is made or called off are not rated.
+    async fn rate_game(&self, game: &ArchivedGame) {
+        let outcome = match game.outcome {
+            Some(outcome)
+                if !game.history.is_empty()
+                    && game.rated
+                    && !game.imported
+                    && game.consultation.is_none()
+                    && !outcome.termination.is_void() =>
+            {
+                outcome
+            }
+            _ => return,
+        };
+        let pool = RatingPool {
+            variant: game.variant,
+            category: TimeCategory::of(game.time_control),
+        };
+
+        let mut ratings = self.ratings.write().await;
+        let ratings = ratings.entry(pool).or_default();
+        let (white, black) = rating::rate(
+            ratings.get(&game.white).copied().unwrap_or_default(),
+            ratings.get(&game.black).copied().unwrap_or_default(),
+            outcome.result,
+        );
+        ratings.insert(game.white, white);
+        ratings.insert(game.black, black);
+    }
+
+    /// Returns the rating of `player` in `pool`, which is the initial rating
+    /// if they have not played in it
+    pub async fn get_rating(&self, player: Address, pool: RatingPool) -> Rating {
+        let ratings = self.ratings.read().await;
+
+        ratings
+            .get(&pool)
+            .and_then(|r| r.get(&player))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns every pool `player` has a rating in
+    pub async fn get_ratings(&self, player: Address) -> Vec<(RatingPool, Rating)> {
+        let ratings = self.ratings.read().await;
+
+        let mut found: Vec<(RatingPool, Rating)> = ratings
+            .iter()
+            .filter_map(|(pool, r)| r.get(&player).map(|rating| (*pool, *rating)))
+            .collect();
+        found.sort_by_key(|(pool, _)| *pool);
+
+        found
+    }
+
+    /// Returns up to `limit` players of a pool from rank `offset`, best
+    /// rated first, along with the number of rated players
+    pub async fn get_leaderboard(
+        &self,
+        pool: RatingPool,
+        offset: usize,
+        limit: usize,
+    ) -> (Vec<(Address, Rating)>, usize) {
+        let ratings = self.ratings.read().await;
+        let mut players: Vec<(Address, Rating)> = match ratings.get(&pool) {
+            Some(r) => r
+                .iter()
+                .map(|(player, rating)| (*player, *rating))
+                .collect(),
+            None => return (Vec::new(), 0),
+        };
+        players.sort_by(|(a, ra), (b, rb)| rb.rating.cmp(&ra.rating).then(a.cmp(b)));
+
+        let total = players.len();
+        (
+            players.into_iter().skip(offset).take(limit).collect(),
+            total,
+        )
+    }
+
+    /// Calls off a game in progress on behalf of `player`, moving it to the
+    /// archive. Players may abort a game before any move is made, unless it
+    /// is a consultation game, and the arbiter set in genesis may end any
+    /// game. Ending a finished game only archives it, keeping its result,
+    /// and archived games cannot be ended.
+    pub async fn end_game(&self, player: Address, game_id: u64, block_time: u64) -> io::Result<()> {
+        // Get write access to state
+        let mut game_states = self.game_states.write().await;
+
+        // If game not found
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None if self.is_archived(game_id).await => {
+                return Err(Error::other("game is already archived"))
+            }
+            None => return Err(Error::other("Game not found!")),
+        };
+        if game.outcome.is_some() {
+            if !self.may_end(player, game.white, game.black) {
+                return Err(Error::other(
+                    "only the players or the arbiter can end a game",
+                ));
+            }
+            drop(game_states);
+            self.settle(&[game_id], block_time).await;
+            return Ok(());
+        }
+
+        let termination = if self.genesis.arbiter == Some(player) {
+            Termination::Arbiter
+        } else if player != game.white && player != game.black {
+            return Err(Error::other(
+                "only the players or the arbiter can end a game in progress",
+            ));
+        } else if game.consultation.is_some() {
+            return Err(Error::other(
+                "a team owner cannot abort a consultation game alone",
+            ));
+        } else if !game.history.is_empty() {
+            return Err(Error::other(
+                "a game can only be aborted before any move is made",
+            ));
+        } else {
+            Termination::Aborted
+        };
+        game.outcome = Some(GameOutcome {
+            result: GameResult::Draw,
+            termination,
+        });
+        drop(game_states);
+
+        // Called off games are archived and their stakes refunded like any
+        // other finished game
+        self.settle(&[game_id], block_time).await;
+
+        Ok(())
+    }
+
+    /// Whether `player` may end a game between `white` and `black`
+    fn may_end(&self, player: Address, white: Address, black: Address) -> bool {
+        self.genesis.arbiter == Some(player) || player == white || player == black
+    }
 
     /// Getter for game board
-    pub async fn get_game(&self, game_id: u64) -> Option<Chess> {
+    pub async fn get_game(&self, game_id: u64) -> Option<VariantPosition> {
         // Get read access to state
         let game_states = self.game_states.read().await;
 
@@ -302,10 +1854,2547 @@ impl State {
         None
     }
 
-    /// Returns `true` if a game exists, `false` otherwise
+    /// Returns the full record of a game
+    pub async fn get_game_state(&self, game_id: u64) -> Option<GameState> {
+        let game_states = self.game_states.read().await;
+
+        game_states.get(&game_id).cloned()
+    }
+
+    /// Returns the record of a game, whether it is being played or archived
+    pub async fn get_game_record(&self, game_id: u64) -> Option<ArchivedGame> {
+        let game_states = self.game_states.read().await;
+        if let Some(game) = game_states.get(&game_id) {
+            return Some(game.to_archive());
+        }
+        drop(game_states);
+
+        let archived_games = self.archived_games.read().await;
+        archived_games.get(&game_id).cloned()
+    }
+
+    /// Returns `true` if a game has been archived
+    pub async fn is_archived(&self, game_id: u64) -> bool {
+        let archived_games = self.archived_games.read().await;
+
+        archived_games.contains_key(&game_id)
+    }
+
+    /// Returns up to `limit` moves of a game starting at index `offset`, along
+    /// with the total number of moves played, or `None` if the game does not
+    /// exist
+    pub async fn get_game_history(
+        &self,
+        game_id: u64,
+        offset: usize,
+        limit: usize,
+    ) -> Option<(Vec<MoveRecord>, usize)> {
+        let game = self.get_game_record(game_id).await?;
+
+        let moves = game
+            .history
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect();
+        Some((moves, game.history.len()))
+    }
+
+    /// Returns every game `player` takes part in, including archived ones,
+    /// ordered by game ID
+    pub async fn get_games_of_player(&self, player: Address) -> Vec<(u64, ArchivedGame)> {
+        let game_states = self.game_states.read().await;
+        let archived_games = self.archived_games.read().await;
+
+        let mut games: Vec<(u64, ArchivedGame)> = game_states
+            .iter()
+            .filter(|(_, g)| g.white == player || g.black == player)
+            .map(|(id, g)| (*id, g.to_archive()))
+            .chain(
+                archived_games
+                    .iter()
+                    .filter(|(_, g)| g.white == player || g.black == player)
+                    .map(|(id, g)| (*id, g.clone())),
+            )
+            .collect();
+        games.sort_by_key(|(id, _)| *id);
+
+        games
+    }
+
+    /// Returns `true` if a game exists, `false` otherwise. Archived games
+    /// exist too.
     pub async fn game_exists(&self, game_id: u64) -> bool {
         let game_states = self.game_states.read().await;
+        if game_states.contains_key(&game_id) {
+            return true;
+        }
+        drop(game_states);
+
+        self.is_archived(game_id).await
+    }
+
+    /// Returns the outcome of a game, or `None` if the game does not exist or
+    /// is still being played
+    pub async fn get_game_outcome(&self, game_id: u64) -> Option<GameOutcome> {
+        self.get_game_record(game_id).await.and_then(|g| g.outcome)
+    }
+
+    /// Offers the opponent of a finished game to play it again, replacing
+    /// any pending offer
+    pub async fn offer_rematch(&self, player: Address, game_id: u64) -> io::Result<()> {
+        let game = match self.get_game_record(game_id).await {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        if player != game.white && player != game.black {
+            return Err(Error::other("only the players can offer a rematch"));
+        }
+        if game.outcome.is_none() {
+            return Err(Error::other(
+                "a rematch can only be offered once the game is finished",
+            ));
+        }
+
+        let mut rematches = self.rematches.write().await;
+        if let Some(Rematch {
+            game_id: Some(_), ..
+        }) = rematches.get(&game_id)
+        {
+            return Err(Error::other("game was already rematched"));
+        }
+        rematches.insert(
+            game_id,
+            Rematch {
+                offered_by: player,
+                game_id: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Accepts the opponent's rematch offer, creating a game with colors
+    /// swapped and the same variant, starting position and time control
+    pub async fn accept_rematch(
+        &self,
+        player: Address,
+        game_id: u64,
+        block_time: u64,
+    ) -> io::Result<u64> {
+        let offered_by = match self.rematches.read().await.get(&game_id) {
+            Some(Rematch {
+                offered_by,
+                game_id: None,
+            }) => *offered_by,
+            _ => return Err(Error::other("no rematch was offered")),
+        };
+        let game = match self.get_game_record(game_id).await {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+
+        let opponent = if offered_by == game.white {
+            game.black
+        } else {
+            game.white
+        };
+        if player != opponent {
+            return Err(Error::other("only the opponent can accept a rematch"));
+        }
+
+        let rematch_id = calculate_rematch_id(game_id);
+        if self.game_exists(rematch_id).await {
+            return Err(Error::other("Game already exists!"));
+        }
 
-        game_states.contains_key(&game_id)
+        let mut rematch = GameState::new(
+            starting_position(game.variant, Some(&game.starting_fen), rematch_id)?,
+            game.variant,
+            game.black,
+            game.white,
+            game.time_control,
+            block_time,
+        );
+        rematch.rematch_of = Some(game_id);
+
+        let mut game_states = self.game_states.write().await;
+        game_states.insert(rematch_id, rematch);
+        drop(game_states);
+
+        let mut rematches = self.rematches.write().await;
+        rematches.insert(
+            game_id,
+            Rematch {
+                offered_by,
+                game_id: Some(rematch_id),
+            },
+        );
+
+        Ok(rematch_id)
+    }
+
+    /// Returns the rematch offered after a game, if any
+    pub async fn get_rematch(&self, game_id: u64) -> Option<Rematch> {
+        let rematches = self.rematches.read().await;
+
+        rematches.get(&game_id).copied()
+    }
+
+    /// Returns the IDs of the games linked to `game_id` by rematches, from
+    /// the first game of the series to the last
+    pub async fn get_rematch_chain(&self, game_id: u64) -> Vec<u64> {
+        let mut chain = vec![game_id];
+        while let Some(previous) = self
+            .get_game_record(chain[0])
+            .await
+            .and_then(|g| g.rematch_of)
+        {
+            chain.insert(0, previous);
+        }
+
+        let rematches = self.rematches.read().await;
+        while let Some(Rematch {
+            game_id: Some(next),
+            ..
+        }) = rematches.get(chain.last().unwrap())
+        {
+            chain.push(*next);
+        }
+
+        chain
+    }
+
+    /// Creates a tournament open for registration until its deadline
+    pub async fn create_tournament(&self, tournament: Tournament, nonce: u64) -> io::Result<u64> {
+        if tournament.rounds == 0 && tournament.format != TournamentFormat::Knockout {
+            return Err(Error::other("a tournament needs at least one round"));
+        }
+
+        let tournament_id = calculate_tournament_id(tournament.organizer, nonce);
+        let mut tournaments = self.tournaments.write().await;
+        if tournaments.contains_key(&tournament_id) {
+            return Err(Error::other("Tournament already exists!"));
+        }
+        tournaments.insert(tournament_id, tournament);
+
+        Ok(tournament_id)
+    }
+
+    /// Registers `player` for a tournament, before its deadline
+    pub async fn register_for_tournament(
+        &self,
+        player: Address,
+        tournament_id: u64,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut tournaments = self.tournaments.write().await;
+        let tournament = match tournaments.get_mut(&tournament_id) {
+            Some(t) => t,
+            None => return Err(Error::other("Tournament not found!")),
+        };
+
+        if block_time >= tournament.registration_deadline || tournament.started() {
+            return Err(Error::other("registration is closed"));
+        }
+        if tournament.players.contains(&player) {
+            return Err(Error::other("player is already registered"));
+        }
+        tournament.players.push(player);
+
+        Ok(())
     }
+
+    /// Withdraws `player` from a tournament. Before the first round, they are
+    /// simply unregistered; afterwards, they keep their results but are no
+    /// longer paired. In a knockout, they forfeit the game they are playing
+    /// and give their next matches away.
+    pub async fn withdraw_from_tournament(
+        &self,
+        player: Address,
+        tournament_id: u64,
+    ) -> io::Result<()> {
+        let mut tournaments = self.tournaments.write().await;
+        let tournament = match tournaments.get_mut(&tournament_id) {
+            Some(t) => t,
+            None => return Err(Error::other("Tournament not found!")),
+        };
+
+        if !tournament.players.contains(&player) || tournament.withdrawn.contains(&player) {
+            return Err(Error::other("player is not taking part in the tournament"));
+        }
+        if !tournament.started() {
+            tournament.players.retain(|p| *p != player);
+        } else {
+            tournament.withdrawn.push(player);
+        }
+        let open_game = tournament.open_bracket_game(player);
+        drop(tournaments);
+
+        // The opponent advances once the game is settled
+        if let Some(bracket_game) = open_game {
+            let mut game_states = self.game_states.write().await;
+            if let Some(game) = game_states.get_mut(&bracket_game.game_id) {
+                let winner = if bracket_game.white == player {
+                    Color::Black
+                } else {
+                    Color::White
+                };
+                game.outcome.get_or_insert(GameOutcome {
+                    result: GameResult::win_for(winner),
+                    termination: Termination::Forfeit,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pairs the next round of a tournament on behalf of its organizer and
+    /// creates its games, returning the round number. Players are seeded by
+    /// rating when the first round starts, and every game of the previous
+    /// round must be over. Knockouts are only started this way, their next
+    /// rounds following as matches are decided.
+    pub async fn start_round(
+        &self,
+        organizer: Address,
+        tournament_id: u64,
+        block_time: u64,
+    ) -> io::Result<usize> {
+        let mut tournament = match self.get_tournament(tournament_id).await {
+            Some(t) => t,
+            None => return Err(Error::other("Tournament not found!")),
+        };
+
+        if organizer != tournament.organizer {
+            return Err(Error::other("only the organizer can start a round"));
+        }
+        if block_time < tournament.registration_deadline {
+            return Err(Error::other("registration is still open"));
+        }
+        if tournament.format == TournamentFormat::Knockout && tournament.started() {
+            return Err(Error::other(
+                "knockout rounds start as the previous matches are decided",
+            ));
+        }
+        if tournament.players.len() - tournament.withdrawn.len() < 2 {
+            return Err(Error::other("a round needs at least two players"));
+        }
+        let round = tournament.pairings.len() + 1;
+        if tournament.format != TournamentFormat::Knockout
+            && (round > tournament.rounds || round > tournament.max_rounds())
+        {
+            return Err(Error::other("every round was already played"));
+        }
+        if let Some(previous) = tournament.pairings.last() {
+            for game_id in previous.iter().filter_map(|p| p.game_id) {
+                if self.get_game_outcome(game_id).await.is_none()
+                    && !self.is_archived(game_id).await
+                {
+                    return Err(Error::other(
+                        "every game of the previous round must be over",
+                    ));
+                }
+            }
+        }
+
+        if round == 1 {
+            let pool = RatingPool {
+                variant: tournament.variant,
+                category: TimeCategory::of(tournament.time_control),
+            };
+            let mut seeded = Vec::new();
+            for player in tournament.players.iter() {
+                seeded.push((*player, self.get_rating(*player, pool).await.rating));
+            }
+            // Ties keep the registration order
+            seeded.sort_by_key(|(_, rating)| std::cmp::Reverse(*rating));
+            tournament.players = seeded.into_iter().map(|(p, _)| p).collect();
+        }
+
+        if tournament.format == TournamentFormat::Knockout {
+            tournament.seed_bracket();
+            self.create_bracket_games(tournament_id, &mut tournament, block_time)
+                .await?;
+            let mut tournaments = self.tournaments.write().await;
+            tournaments.insert(tournament_id, tournament);
+
+            return Ok(round);
+        }
+
+        let results = self.get_tournament_results(&tournament).await;
+        let mut pairings = Vec::new();
+        for (board, (white, black)) in tournament.pair_next_round(&results).into_iter().enumerate()
+        {
+            let game_id =
+                black.map(|_| calculate_tournament_game_id(tournament_id, round, board + 1));
+            pairings.push(Pairing {
+                white,
+                black,
+                game_id,
+            });
+        }
+
+        let mut games = Vec::new();
+        for pairing in pairings.iter() {
+            let (game_id, black) = match (pairing.game_id, pairing.black) {
+                (Some(game_id), Some(black)) => (game_id, black),
+                _ => continue,
+            };
+            let mut game = GameState::new(
+                starting_position(tournament.variant, None, game_id)?,
+                tournament.variant,
+                pairing.white,
+                black,
+                tournament.time_control,
+                block_time,
+            );
+            game.tournament = Some(tournament_id);
+            games.push((game_id, game));
+        }
+        self.insert_new_games(games).await?;
+
+        tournament.pairings.push(pairings);
+        let mut tournaments = self.tournaments.write().await;
+        tournaments.insert(tournament_id, tournament);
+
+        Ok(round)
+    }
+
+    /// Advances the bracket of a knockout after one of its games finished,
+    /// creating the games of the matches that can be played next
+    async fn advance_knockout(
+        &self,
+        tournament_id: u64,
+        game_id: u64,
+        result: Option<GameResult>,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut tournament = match self.get_tournament(tournament_id).await {
+            Some(t) if t.format == TournamentFormat::Knockout => t,
+            _ => return Ok(()),
+        };
+        if !tournament.record_bracket_game(game_id, result) {
+            return Ok(());
+        }
+
+        // Game IDs are derived from the bracket, so creating them can only
+        // fail if the chain is corrupted; the result is then kept, but the
+        // bracket does not move forward
+        let recorded = tournament.clone();
+        let advanced = self
+            .create_bracket_games(tournament_id, &mut tournament, block_time)
+            .await;
+        let mut tournaments = self.tournaments.write().await;
+        match advanced {
+            Ok(()) => tournaments.insert(tournament_id, tournament),
+            Err(_) => tournaments.insert(tournament_id, recorded),
+        };
+
+        advanced
+    }
+
+    /// Moves the bracket of a knockout forward and creates the games of the
+    /// matches waiting for one, all of them or none
+    async fn create_bracket_games(
+        &self,
+        tournament_id: u64,
+        tournament: &mut Tournament,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut games = Vec::new();
+        for (round, index) in tournament.advance_bracket() {
+            let bracket_match = &tournament.bracket[round][index];
+            let (white, black) = match bracket_match.next_colors() {
+                Some(colors) => colors,
+                None => continue,
+            };
+            let game_id = calculate_bracket_game_id(
+                tournament_id,
+                round + 1,
+                index + 1,
+                bracket_match.games.len() + 1,
+            );
+
+            let mut game = GameState::new(
+                starting_position(tournament.variant, None, game_id)?,
+                tournament.variant,
+                white,
+                black,
+                tournament.time_control,
+                block_time,
+            );
+            game.tournament = Some(tournament_id);
+            games.push((game_id, game));
+
+            let armageddon = bracket_match.next_is_armageddon(tournament.tiebreak);
+            tournament.bracket[round][index].games.push(BracketGame {
+                game_id,
+                white,
+                black,
+                armageddon,
+                result: None,
+                void: false,
+            });
+        }
+
+        self.insert_new_games(games).await
+    }
+
+    /// Adds the games of a round at once, or none of them if any already
+    /// exists
+    async fn insert_new_games(&self, games: Vec<(u64, GameState)>) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        for (game_id, _) in games.iter() {
+            if game_states.contains_key(game_id) || self.is_archived(*game_id).await {
+                return Err(Error::other("Game already exists!"));
+            }
+        }
+        game_states.extend(games);
+
+        Ok(())
+    }
+
+    pub async fn get_tournament(&self, tournament_id: u64) -> Option<Tournament> {
+        let tournaments = self.tournaments.read().await;
+
+        tournaments.get(&tournament_id).cloned()
+    }
+
+    /// Returns the results of the decided games of a tournament. Called off
+    /// games are left out, so that neither player scores.
+    pub async fn get_tournament_results(
+        &self,
+        tournament: &Tournament,
+    ) -> HashMap<u64, GameResult> {
+        let mut results = HashMap::new();
+        for game_id in tournament
+            .pairings
+            .iter()
+            .flatten()
+            .filter_map(|p| p.game_id)
+        {
+            match self.get_game_outcome(game_id).await {
+                Some(outcome) if !outcome.termination.is_void() => {
+                    results.insert(game_id, outcome.result);
+                }
+                _ => {}
+            }
+        }
+
+        results
+    }
+
+    /// Returns the standings of a tournament, best first
+    pub async fn get_standings(&self, tournament_id: u64) -> io::Result<Vec<Standing>> {
+        let tournament = match self.get_tournament(tournament_id).await {
+            Some(t) => t,
+            None => return Err(Error::other("Tournament not found!")),
+        };
+        let results = self.get_tournament_results(&tournament).await;
+
+        Ok(tournament::standings(&tournament, &results))
+    }
+
+    /// Creates a simul open for registration until its host starts it
+    pub async fn create_simul(&self, simul: Simul, nonce: u64) -> io::Result<u64> {
+        if simul.boards == 0 {
+            return Err(Error::other("a simul needs at least one board"));
+        }
+
+        let simul_id = calculate_simul_id(simul.host, nonce);
+        let mut simuls = self.simuls.write().await;
+        if simuls.contains_key(&simul_id) {
+            return Err(Error::other("Simul already exists!"));
+        }
+        simuls.insert(simul_id, simul);
+
+        Ok(simul_id)
+    }
+
+    pub async fn register_for_simul(&self, player: Address, simul_id: u64) -> io::Result<()> {
+        let mut simuls = self.simuls.write().await;
+
+        match simuls.get_mut(&simul_id) {
+            Some(simul) => simul.register(player),
+            None => Err(Error::other("Simul not found!")),
+        }
+    }
+
+    pub async fn withdraw_from_simul(&self, player: Address, simul_id: u64) -> io::Result<()> {
+        let mut simuls = self.simuls.write().await;
+
+        match simuls.get_mut(&simul_id) {
+            Some(simul) => simul.withdraw(player),
+            None => Err(Error::other("Simul not found!")),
+        }
+    }
+
+    /// Closes the registration of a simul on behalf of its host and creates
+    /// a game on each board, the host playing white
+    pub async fn start_simul(
+        &self,
+        host: Address,
+        simul_id: u64,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut simuls = self.simuls.write().await;
+        let simul = match simuls.get_mut(&simul_id) {
+            Some(simul) => simul,
+            None => return Err(Error::other("Simul not found!")),
+        };
+
+        if host != simul.host {
+            return Err(Error::other("only the host can start a simul"));
+        }
+        if simul.started() {
+            return Err(Error::other("the simul already started"));
+        }
+        if simul.players.is_empty() {
+            return Err(Error::other("a simul needs at least one opponent"));
+        }
+
+        let mut games = Vec::new();
+        for (board, opponent) in simul.players.iter().enumerate() {
+            let game_id = calculate_simul_game_id(simul_id, board + 1);
+            if self.game_exists(game_id).await {
+                return Err(Error::other("Game already exists!"));
+            }
+            let mut game = GameState::new(
+                starting_position(simul.variant, None, game_id)?,
+                simul.variant,
+                simul.host,
+                *opponent,
+                simul.time_control,
+                block_time,
+            );
+            game.simul = Some(simul_id);
+            games.push((game_id, game));
+        }
+
+        simul.game_ids = games.iter().map(|(game_id, _)| *game_id).collect();
+        let mut game_states = self.game_states.write().await;
+        game_states.extend(games);
+
+        Ok(())
+    }
+
+    pub async fn get_simul(&self, simul_id: u64) -> Option<Simul> {
+        let simuls = self.simuls.read().await;
+
+        simuls.get(&simul_id).cloned()
+    }
+
+    /// Returns the boards of a simul in order, with whose turn it is on each
+    pub async fn get_simul_boards(&self, simul: &Simul) -> Vec<SimulBoard> {
+        let mut boards = Vec::new();
+        for (i, (game_id, opponent)) in simul.game_ids.iter().zip(&simul.players).enumerate() {
+            let record = match self.get_game_record(*game_id).await {
+                Some(record) => record,
+                None => continue,
+            };
+            let to_move = match self.get_game_state(*game_id).await {
+                Some(game) if game.outcome.is_none() => Some(game.address_of(game.game.turn())),
+                _ => None,
+            };
+            boards.push(SimulBoard {
+                board: i + 1,
+                opponent: *opponent,
+                game_id: *game_id,
+                to_move,
+                move_count: record.move_count,
+                outcome: record.outcome,
+            });
+        }
+
+        boards
+    }
+
+    pub async fn get_balance(&self, account: Address) -> u64 {
+        self.ledger.read().await.balance(account)
+    }
+
+    /// Charges a transaction fee to `payer`, sent to the treasury set in
+    /// genesis or burned
+    pub async fn pay_fee(&self, payer: Address, fee: u64) -> io::Result<()> {
+        if fee == 0 {
+            return Ok(());
+        }
+
+        self.ledger
+            .write()
+            .await
+            .pay_fee(payer, fee, self.genesis.fees.treasury)
+    }
+
+    /// Returns the funds `account` has locked in wagered games
+    pub async fn get_locked(&self, account: Address) -> u64 {
+        self.ledger.read().await.locked(account)
+    }
+
+    /// Returns the stakes locked for `game_id`, if it is a wagered game in
+    /// progress
+    pub async fn get_escrow(&self, game_id: u64) -> Option<Escrow> {
+        self.ledger.read().await.escrow(game_id)
+    }
+
+    /// Moves `amount` from `from` to `to`
+    pub async fn transfer(&self, from: Address, to: Address, amount: u64) -> io::Result<()> {
+        self.ledger.write().await.transfer(from, to, amount)
+    }
+
+    /// Posts a puzzle, holding its reward until it is solved or closed.
+    /// Puzzles are standard chess positions only.
+    pub async fn post_puzzle(&self, puzzle: Puzzle, nonce: u64) -> io::Result<u64> {
+        starting_position(GameVariant::Standard, Some(&puzzle.fen), 0)?;
+        if puzzle.deadline <= puzzle.created_at {
+            return Err(Error::other("the deadline must be in the future"));
+        }
+
+        let puzzle_id = calculate_puzzle_id(puzzle.poster, nonce);
+        let mut puzzles = self.puzzles.write().await;
+        if puzzles.contains_key(&puzzle_id) {
+            return Err(Error::other("Puzzle already exists!"));
+        }
+        self.ledger
+            .write()
+            .await
+            .debit(puzzle.poster, puzzle.reward)?;
+        puzzles.insert(puzzle_id, puzzle);
+
+        Ok(puzzle_id)
+    }
+
+    pub async fn commit_solution(
+        &self,
+        solver: Address,
+        puzzle_id: u64,
+        hash: B256,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut puzzles = self.puzzles.write().await;
+
+        match puzzles.get_mut(&puzzle_id) {
+            Some(puzzle) => puzzle.commit(solver, hash, block_time),
+            None => Err(Error::other("Puzzle not found!")),
+        }
+    }
+
+    /// Checks a solution revealed by `solver`, paying them the reward right
+    /// away if nobody committed before them
+    pub async fn reveal_solution(
+        &self,
+        solver: Address,
+        puzzle_id: u64,
+        moves: &[String],
+        salt: &str,
+        block_time: u64,
+    ) -> io::Result<()> {
+        let mut puzzles = self.puzzles.write().await;
+
+        let puzzle = match puzzles.get_mut(&puzzle_id) {
+            Some(puzzle) => puzzle,
+            None => return Err(Error::other("Puzzle not found!")),
+        };
+        let reveal_deadline = puzzle
+            .deadline
+            .saturating_add(self.genesis.puzzle_reveal_window);
+        if block_time >= reveal_deadline {
+            return Err(Error::other("the time to reveal solutions is over"));
+        }
+        puzzle.reveal(solver, moves, salt)?;
+
+        if let Some(winner) = puzzle.winner(false) {
+            self.close_puzzle(puzzle, Some(winner)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Closes the puzzles whose time to reveal solutions is over, paying
+    /// the earliest valid committer or refunding the poster, in puzzle ID
+    /// order. Puzzles whose reward cannot be paid stay open, to be closed by
+    /// a later block.
+    pub async fn close_puzzles(&self, block_time: u64) {
+        let mut puzzles = self.puzzles.write().await;
+        let mut over: Vec<(&u64, &mut Puzzle)> = puzzles
+            .iter_mut()
+            .filter(|(_, p)| {
+                !p.closed
+                    && block_time >= p.deadline.saturating_add(self.genesis.puzzle_reveal_window)
+            })
+            .collect();
+        over.sort_by_key(|(id, _)| **id);
+
+        for (puzzle_id, puzzle) in over {
+            let winner = puzzle.winner(true);
+            if let Err(e) = self.close_puzzle(puzzle, winner).await {
+                log::warn!("could not close puzzle {puzzle_id}: {e}");
+            }
+        }
+    }
+
+    /// Pays the reward of a puzzle to its winner, or back to its poster if
+    /// nobody solved it. The puzzle is only closed once the reward is paid.
+    async fn close_puzzle(&self, puzzle: &mut Puzzle, winner: Option<Address>) -> io::Result<()> {
+        let payee = winner.unwrap_or(puzzle.poster);
+        self.ledger.write().await.credit(payee, puzzle.reward)?;
+        puzzle.winner = winner;
+        puzzle.closed = true;
+
+        Ok(())
+    }
+
+    pub async fn get_puzzle(&self, puzzle_id: u64) -> Option<Puzzle> {
+        let puzzles = self.puzzles.read().await;
+
+        puzzles.get(&puzzle_id).cloned()
+    }
+
+    /// Creates a team owned by `owner`, its first member
+    pub async fn create_team(&self, owner: Address, nonce: u64) -> io::Result<u64> {
+        let team_id = calculate_team_id(owner, nonce);
+        let mut teams = self.teams.write().await;
+        if teams.contains_key(&team_id) {
+            return Err(Error::other("Team already exists!"));
+        }
+        teams.insert(team_id, Team::new(owner));
+
+        Ok(team_id)
+    }
+
+    pub async fn add_team_member(
+        &self,
+        owner: Address,
+        team_id: u64,
+        member: Address,
+    ) -> io::Result<()> {
+        let mut teams = self.teams.write().await;
+
+        match teams.get_mut(&team_id) {
+            Some(team) => team.add(owner, member),
+            None => Err(Error::other("Team not found!")),
+        }
+    }
+
+    pub async fn remove_team_member(
+        &self,
+        player: Address,
+        team_id: u64,
+        member: Address,
+    ) -> io::Result<()> {
+        let mut teams = self.teams.write().await;
+
+        match teams.get_mut(&team_id) {
+            Some(team) => team.remove(player, member),
+            None => Err(Error::other("Team not found!")),
+        }
+    }
+
+    pub async fn get_team(&self, team_id: u64) -> Option<Team> {
+        let teams = self.teams.read().await;
+
+        teams.get(&team_id).cloned()
+    }
+
+    /// Creates a game between two teams, played by the team owners' addresses
+    /// but moved by the votes of the members. `creator` must own one of the
+    /// teams.
+    pub async fn create_consultation_game(
+        &self,
+        creator: Address,
+        consultation: Consultation,
+        variant: GameVariant,
+        nonce: u64,
+        block_time: u64,
+    ) -> io::Result<u64> {
+        if consultation.voting_window == 0 {
+            return Err(Error::other("the voting window must be positive"));
+        }
+
+        let teams = self.teams.read().await;
+        let owner_of = |team_id| {
+            teams
+                .get(&team_id)
+                .map(|team: &Team| team.owner)
+                .ok_or_else(|| Error::other("Team not found!"))
+        };
+        let white = owner_of(consultation.white_team)?;
+        let black = owner_of(consultation.black_team)?;
+        drop(teams);
+        if white == black {
+            return Err(Error::other("the teams must have different owners"));
+        }
+        if creator != white && creator != black {
+            return Err(Error::other(
+                "only the owner of a team can create its games",
+            ));
+        }
+
+        let game_id = calculate_consultation_game_id(creator, nonce);
+        let mut game_states = self.game_states.write().await;
+        if game_states.contains_key(&game_id) || self.is_archived(game_id).await {
+            return Err(Error::other("Game already exists!"));
+        }
+
+        let position = starting_position(variant, None, game_id)?;
+        let mut game = GameState::new(position, variant, white, black, None, block_time);
+        game.consultation = Some(Consultation {
+            votes: BTreeMap::new(),
+            ..consultation
+        });
+        game_states.insert(game_id, game);
+
+        Ok(game_id)
+    }
+
+    /// Records the vote of `voter` for the next move of their team, playing
+    /// it right away once a majority of the team agrees, or if the voting
+    /// window is over
+    pub async fn vote_move(
+        &self,
+        voter: Address,
+        game_id: u64,
+        mv: &Move,
+        block: BlockInfo,
+    ) -> io::Result<()> {
+        let mut game_states = self.game_states.write().await;
+        let game = match game_states.get_mut(&game_id) {
+            Some(game) => game,
+            None => return Err(Error::other("Game not found!")),
+        };
+        if game.outcome.is_some() {
+            return Err(Error::other("the game is over"));
+        }
+        if game.is_paused() {
+            return Err(Error::other("game is paused"));
+        }
+        let turn = game.game.turn();
+        let voting_over = game
+            .voting_deadline()
+            .is_some_and(|deadline| block.timestamp >= deadline);
+        let consultation = match game.consultation.as_mut() {
+            Some(consultation) => consultation,
+            None => return Err(Error::other("not a consultation game")),
+        };
+        let team_id = consultation.team_of(turn);
+        let teams = self.teams.read().await;
+        let members = match teams.get(&team_id) {
+            Some(team) => &team.members,
+            None => return Err(Error::other("Team not found!")),
+        };
+        if !members.contains(&voter) {
+            return Err(Error::other("only members of the team to move can vote"));
+        }
+        if !game.game.is_legal(mv) {
+            return Err(Error::other("illegal move"));
+        }
+
+        let uci = mv.to_uci(game.variant.castling_mode()).to_string();
+        consultation.votes.insert(voter, uci);
+        let decided = consultation.decide(members, voting_over);
+        drop(teams);
+
+        let player = game.address_of(turn);
+        let mv = decided.and_then(|uci| {
+            Uci::from_ascii(uci.as_bytes())
+                .ok()
+                .and_then(|uci| uci.to_move(&game.game).ok())
+        });
+        drop(game_states);
+
+        match mv {
+            Some(mv) => self.play_move(player, game_id, &mv, block).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the moves to play in consultation games whose voting window
+    /// is over as of `block_time`, in game ID order
+    async fn voted_moves(&self, block_time: u64) -> Vec<(u64, Address, Move)> {
+        let game_states = self.game_states.read().await;
+        let teams = self.teams.read().await;
+
+        let mut moves = Vec::new();
+        for (game_id, game) in game_states.iter() {
+            let consultation = match &game.consultation {
+                Some(consultation) if game.outcome.is_none() && !game.is_paused() => consultation,
+                _ => continue,
+            };
+            if game
+                .voting_deadline()
+                .is_some_and(|deadline| block_time < deadline)
+            {
+                continue;
+            }
+            let turn = game.game.turn();
+            let mv = teams
+                .get(&consultation.team_of(turn))
+                .and_then(|team| consultation.decide(&team.members, true))
+                .and_then(|uci| Uci::from_ascii(uci.as_bytes()).ok())
+                .and_then(|uci| uci.to_move(&game.game).ok());
+            if let Some(mv) = mv {
+                moves.push((*game_id, game.address_of(turn), mv));
+            }
+        }
+        moves.sort_by_key(|(game_id, _, _)| *game_id);
+
+        moves
+    }
+
+    /// Returns the number of votes for each move of the side to move in a
+    /// consultation game
+    pub async fn get_vote_tally(&self, game_id: u64) -> BTreeMap<String, usize> {
+        let game_states = self.game_states.read().await;
+        let game = match game_states.get(&game_id) {
+            Some(game) => game,
+            None => return BTreeMap::new(),
+        };
+        let consultation = match &game.consultation {
+            Some(consultation) => consultation,
+            None => return BTreeMap::new(),
+        };
+        let team_id = consultation.team_of(game.game.turn());
+
+        match self.teams.read().await.get(&team_id) {
+            Some(team) => consultation.tally(&team.members),
+            None => BTreeMap::new(),
+        }
+    }
+
+    /// Returns whether a block built at `block_time` would play a voted move
+    pub async fn has_due_votes(&self, block_time: u64) -> bool {
+        !self.voted_moves(block_time).await.is_empty()
+    }
+
+    /// Plays the most voted move of the consultation games whose voting
+    /// window is over
+    pub async fn resolve_votes(&self, block: BlockInfo) {
+        for (game_id, player, mv) in self.voted_moves(block.timestamp).await {
+            if let Err(e) = self.play_move(player, game_id, &mv, block).await {
+                log::warn!("could not play the voted move of game {game_id}: {e}");
+            }
+        }
+    }
+
+    /// Registers a name for `player`
+    pub async fn register_name(&self, player: Address, name: &str) -> io::Result<()> {
+        self.names.write().await.register(player, name)
+    }
+
+    /// Gives the name owned by `player` to `to`
+    pub async fn transfer_name(&self, player: Address, name: &str, to: Address) -> io::Result<()> {
+        self.names.write().await.transfer(player, name, to)
+    }
+
+    /// Frees the name owned by `player`
+    pub async fn release_name(&self, player: Address, name: &str) -> io::Result<()> {
+        self.names.write().await.release(player, name)
+    }
+
+    /// Returns the address owning `name`
+    pub async fn resolve_name(&self, name: &str) -> Option<Address> {
+        self.names.read().await.resolve(name)
+    }
+
+    /// Returns the name owned by `player`
+    pub async fn reverse_resolve(&self, player: Address) -> Option<String> {
+        self.names
+            .read()
+            .await
+            .reverse_resolve(player)
+            .map(str::to_string)
+    }
+
+    /// Records the outcome of a transaction
+    pub async fn put_receipt(&self, receipt: Receipt) {
+        let mut receipts = self.receipts.write().await;
+
+        receipts.insert(receipt.tx_id, receipt);
+    }
+
+    /// Returns the receipt of a transaction, or `None` if it was not accepted
+    /// yet
+    pub async fn get_receipt(&self, tx_id: &ids::Id) -> Option<Receipt> {
+        let receipts = self.receipts.read().await;
+
+        receipts.get(tx_id).cloned()
+    }
+
+    /// Opens a challenge that can be accepted by `opponent`, or by anyone if
+    /// no opponent is given
+    pub async fn create_challenge(&self, challenge: Challenge, nonce: u64) -> io::Result<u64> {
+        if challenge.opponent == Some(challenge.challenger) {
+            return Err(Error::other("Cannot challenge yourself!"));
+        }
+
+        if self.get_balance(challenge.challenger).await < challenge.stake {
+            return Err(Error::other("insufficient balance to cover the stake"));
+        }
+
+        let challenge_id = calculate_challenge_id(challenge.challenger, nonce);
+
+        // The challenge ID becomes the game ID, so it must not be taken by
+        // either
+        if self.game_exists(challenge_id).await {
+            return Err(Error::other("Game already exists!"));
+        }
+
+        let mut challenges = self.challenges.write().await;
+        if challenges.contains_key(&challenge_id) {
+            return Err(Error::other("Challenge already exists!"));
+        }
+
+        challenges.insert(challenge_id, challenge);
+
+        Ok(challenge_id)
+    }
+
+    /// Accepts a challenge in `block`, creating a game with the challenge's
+    /// ID
+    pub async fn accept_challenge(
+        &self,
+        player: Address,
+        challenge_id: u64,
+        block: BlockInfo,
+    ) -> io::Result<u64> {
+        let mut challenges = self.challenges.write().await;
+
+        let challenge = match challenges.get(&challenge_id) {
+            Some(c) => c.clone(),
+            None => return Err(Error::other("Challenge not found!")),
+        };
+
+        if player == challenge.challenger {
+            return Err(Error::other("Cannot accept your own challenge!"));
+        }
+        if let Some(opponent) = challenge.opponent {
+            if player != opponent {
+                return Err(Error::other("Challenge is addressed to another player!"));
+            }
+        }
+
+        // Random colors are drawn by the acceptor, since the challenger picks
+        // the challenge ID
+        let challenger_is_white = match challenge.color {
+            ColorPreference::White => true,
+            ColorPreference::Black => false,
+            ColorPreference::Random => !draws_white(&block.id, player),
+        };
+        let (white, black) = if challenger_is_white {
+            (challenge.challenger, player)
+        } else {
+            (player, challenge.challenger)
+        };
+
+        let mut game = GameState::new(
+            starting_position(challenge.variant, None, challenge_id)?,
+            challenge.variant,
+            white,
+            black,
+            challenge.time_control,
+            block.timestamp,
+        );
+        if challenge.stake > 0 {
+            let mut ledger = self.ledger.write().await;
+            ledger.lock_stakes(
+                challenge_id,
+                Escrow {
+                    white,
+                    black,
+                    stake: challenge.stake,
+                },
+            )?;
+            game.stake = challenge.stake;
+        }
+
+        let mut game_states = self.game_states.write().await;
+        game_states.insert(challenge_id, game);
+        challenges.remove(&challenge_id);
+
+        Ok(challenge_id)
+    }
+
+    /// Withdraws a challenge; only its creator may do so
+    pub async fn cancel_challenge(&self, player: Address, challenge_id: u64) -> io::Result<()> {
+        let mut challenges = self.challenges.write().await;
+
+        match challenges.get(&challenge_id) {
+            Some(c) if c.challenger == player => {
+                challenges.remove(&challenge_id);
+                Ok(())
+            }
+            Some(_) => Err(Error::other("Only the challenger can cancel a challenge!")),
+            None => Err(Error::other("Challenge not found!")),
+        }
+    }
+
+    /// Adds a seek to the pool; a player can only have one seek at a time
+    pub async fn post_seek(&self, seek: Seek, nonce: u64) -> io::Result<u64> {
+        if let Some(range) = seek.rating_range {
+            if range.min > range.max {
+                return Err(Error::other("empty rating range"));
+            }
+        }
+
+        let seek_id = calculate_seek_id(seek.player, nonce);
+        if self.game_exists(seek_id).await {
+            return Err(Error::other("Game already exists!"));
+        }
+
+        let mut seeks = self.seeks.write().await;
+        if seeks.values().any(|s| s.player == seek.player) {
+            return Err(Error::other("player is already seeking a game"));
+        }
+        if seeks.contains_key(&seek_id) {
+            return Err(Error::other("Seek already exists!"));
+        }
+        seeks.insert(seek_id, seek);
+
+        Ok(seek_id)
+    }
+
+    /// Withdraws a seek; only its player may do so
+    pub async fn cancel_seek(&self, player: Address, seek_id: u64) -> io::Result<()> {
+        let mut seeks = self.seeks.write().await;
+
+        match seeks.get(&seek_id) {
+            Some(s) if s.player == player => {
+                seeks.remove(&seek_id);
+                Ok(())
+            }
+            Some(_) => Err(Error::other("only the player can cancel a seek")),
+            None => Err(Error::other("Seek not found!")),
+        }
+    }
+
+    /// Returns the seeks still waiting as of `block_time`, oldest first
+    pub async fn list_seeks(&self, block_time: u64) -> Vec<(u64, Seek)> {
+        let seeks = self.seeks.read().await;
+
+        let mut open: Vec<(u64, Seek)> = seeks
+            .iter()
+            .filter(|(_, s)| block_time < s.created_at.saturating_add(self.genesis.seek_ttl))
+            .map(|(id, s)| (*id, s.clone()))
+            .collect();
+        open.sort_by_key(|(id, s)| (s.created_at, *id));
+
+        open
+    }
+
+    /// Returns `true` if two seeks can be paired: same variant and time
+    /// control, and each player rated within the other's range
+    async fn seeks_match(&self, a: &Seek, b: &Seek) -> bool {
+        if a.player == b.player || a.variant != b.variant || a.time_control != b.time_control {
+            return false;
+        }
+        let pool = RatingPool {
+            variant: a.variant,
+            category: TimeCategory::of(a.time_control),
+        };
+
+        a.accepts(self.get_rating(b.player, pool).await.rating)
+            && b.accepts(self.get_rating(a.player, pool).await.rating)
+    }
+
+    /// Pairs the seeks waiting as of `block_time`, returning the IDs of the
+    /// seeks of each pair, the older first. Seeks are taken oldest first,
+    /// each paired with the oldest compatible seek left, so that every node
+    /// agrees on the pairs.
+    pub async fn pair_seeks(&self, block_time: u64) -> Vec<(u64, u64)> {
+        let open = self.list_seeks(block_time).await;
+
+        let mut paired = vec![false; open.len()];
+        let mut pairs = Vec::new();
+        for i in 0..open.len() {
+            if paired[i] {
+                continue;
+            }
+            for j in i + 1..open.len() {
+                if !paired[j] && self.seeks_match(&open[i].1, &open[j].1).await {
+                    paired[i] = true;
+                    paired[j] = true;
+                    pairs.push((open[i].0, open[j].0));
+                    break;
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// Creates the games of seeks paired by `block`, with the ID of the
+    /// older seek. Blocks are built on a pool that may have changed since,
+    /// so pairs that are not valid as of the block's time are skipped, e.g.
+    /// because a seek was cancelled, expired or is listed first while newer.
+    pub async fn start_seek_games(&self, pairs: &[(u64, u64)], block: BlockInfo) {
+        let block_time = block.timestamp;
+        let is_open = |s: &Seek| block_time < s.created_at.saturating_add(self.genesis.seek_ttl);
+        for (older, newer) in pairs.iter() {
+            let seeks = self.seeks.read().await;
+            let (a, b) = match (seeks.get(older), seeks.get(newer)) {
+                (Some(a), Some(b)) => (a.clone(), b.clone()),
+                _ => continue,
+            };
+            drop(seeks);
+            // Games are created with the ID of the older seek, so the order is
+            // checked like in `list_seeks`
+            if !is_open(&a) || !is_open(&b) || (a.created_at, *older) > (b.created_at, *newer) {
+                continue;
+            }
+            if !self.seeks_match(&a, &b).await || self.game_exists(*older).await {
+                continue;
+            }
+
+            // As for challenges, colors are drawn from the block, since the
+            // game ID is picked by the player of the older seek
+            let (white, black) = if draws_white(&block.id, a.player) {
+                (a.player, b.player)
+            } else {
+                (b.player, a.player)
+            };
+            let position = match starting_position(a.variant, None, *older) {
+                Ok(position) => position,
+                Err(_) => continue,
+            };
+            let mut game_states = self.game_states.write().await;
+            game_states.insert(
+                *older,
+                GameState::new(
+                    position,
+                    a.variant,
+                    white,
+                    black,
+                    a.time_control,
+                    block_time,
+                ),
+            );
+            drop(game_states);
+
+            let mut seeks = self.seeks.write().await;
+            seeks.remove(older);
+            seeks.remove(newer);
+        }
+    }
+
+    /// Removes the seeks that waited longer than allowed by genesis as of
+    /// `block_time`
+    pub async fn expire_seeks(&self, block_time: u64) {
+        let mut seeks = self.seeks.write().await;
+
+        seeks.retain(|_, s| block_time < s.created_at.saturating_add(self.genesis.seek_ttl));
+    }
+
+    /// Returns the challenges anyone can accept, along with those addressed to
+    /// `player`, ordered by challenge ID
+    pub async fn list_open_challenges(&self, player: Option<Address>) -> Vec<(u64, Challenge)> {
+        let challenges = self.challenges.read().await;
+
+        let mut open: Vec<(u64, Challenge)> = challenges
+            .iter()
+            .filter(|(_, c)| c.opponent.is_none() || (player.is_some() && c.opponent == player))
+            .map(|(id, c)| (*id, c.clone()))
+            .collect();
+        open.sort_by_key(|(id, _)| *id);
+
+        open
+    }
+}
+
+/// Returns a block at `timestamp` for the tests
+#[cfg(test)]
+fn block_at(timestamp: u64) -> BlockInfo {
+    BlockInfo {
+        id: ids::Id::empty(),
+        height: timestamp,
+        timestamp,
+    }
+}
+
+#[tokio::test]
+async fn test_challenges() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let carol = Address::repeat_byte(3);
+    let challenge = |opponent, color, time_control| Challenge {
+        challenger: alice,
+        opponent,
+        color,
+        time_control,
+        variant: GameVariant::Standard,
+        stake: 0,
+        created_at: 10,
+    };
+
+    // Targeted challenges can only be accepted by the opponent
+    let challenge_id = state
+        .create_challenge(challenge(Some(bob), ColorPreference::White, None), 0)
+        .await
+        .unwrap();
+    assert!(state.list_open_challenges(None).await.is_empty());
+    assert_eq!(state.list_open_challenges(Some(bob)).await.len(), 1);
+    assert!(state
+        .accept_challenge(carol, challenge_id, block_at(11))
+        .await
+        .is_err());
+
+    let game_id = state
+        .accept_challenge(bob, challenge_id, block_at(11))
+        .await
+        .unwrap();
+    assert_eq!(game_id, challenge_id);
+    assert!(state.game_exists(game_id).await);
+    assert!(state.list_open_challenges(Some(bob)).await.is_empty());
+
+    // Open challenges can be cancelled by their creator only
+    let open_id = state
+        .create_challenge(challenge(None, ColorPreference::Random, None), 1)
+        .await
+        .unwrap();
+    assert_eq!(state.list_open_challenges(None).await.len(), 1);
+    assert!(state.cancel_challenge(bob, open_id).await.is_err());
+    state.cancel_challenge(alice, open_id).await.unwrap();
+    assert!(state
+        .accept_challenge(bob, open_id, block_at(13))
+        .await
+        .is_err());
+
+    // Random colors are drawn from the block accepting the challenge, so the
+    // challenger cannot pick them through the challenge ID
+    let block_ids = (0..=u8::MAX).map(|b| ids::Id::from_slice(&[b]));
+    let mut block_ids = block_ids.partition::<Vec<_>, _>(|id| draws_white(id, bob));
+    for (nonce, id) in [(4, block_ids.0.remove(0)), (5, block_ids.1.remove(0))] {
+        let challenge_id = state
+            .create_challenge(challenge(None, ColorPreference::Random, None), nonce)
+            .await
+            .unwrap();
+        let block = BlockInfo { id, ..block_at(13) };
+        state
+            .accept_challenge(bob, challenge_id, block)
+            .await
+            .unwrap();
+        let game = state.get_game_state(challenge_id).await.unwrap();
+        let white = if nonce == 4 { bob } else { alice };
+        assert_eq!(game.white(), white);
+    }
+
+    // Running out of time loses the game
+    let time_control = TimeControl {
+        initial: 60,
+        increment: 0,
+    };
+    let timed_id = state
+        .create_challenge(
+            challenge(None, ColorPreference::White, Some(time_control)),
+            2,
+        )
+        .await
+        .unwrap();
+    state
+        .accept_challenge(bob, timed_id, block_at(100))
+        .await
+        .unwrap();
+    let mv = Move::Normal {
+        role: shakmaty::Role::Pawn,
+        from: shakmaty::Square::E2,
+        capture: None,
+        to: shakmaty::Square::E4,
+        promotion: None,
+    };
+    let block = BlockInfo {
+        id: ids::Id::empty(),
+        height: 1,
+        timestamp: 161,
+    };
+    state.make_move(alice, timed_id, &mv, block).await.unwrap();
+    let outcome = state.get_game_outcome(timed_id).await.unwrap();
+    assert_eq!(outcome.result, GameResult::BlackWins);
+    assert_eq!(outcome.termination, Termination::TimeForfeit);
+
+    // So does never moving again
+    let timed_id = state
+        .create_challenge(
+            challenge(None, ColorPreference::White, Some(time_control)),
+            3,
+        )
+        .await
+        .unwrap();
+    state
+        .accept_challenge(bob, timed_id, block_at(200))
+        .await
+        .unwrap();
+    let block = BlockInfo {
+        timestamp: 210,
+        ..block
+    };
+    state.make_move(alice, timed_id, &mv, block).await.unwrap();
+    assert!(!state.has_flagged_games(269).await);
+    state.flag_games(269).await;
+    assert_eq!(state.get_game_outcome(timed_id).await, None);
+    assert!(state.has_flagged_games(270).await);
+    state.flag_games(270).await;
+    let outcome = state.get_game_outcome(timed_id).await.unwrap();
+    assert_eq!(outcome.result, GameResult::WhiteWins);
+    assert_eq!(outcome.termination, Termination::TimeForfeit);
+}
+
+#[tokio::test]
+async fn test_end_game() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let carol = Address::repeat_byte(3);
+    let arbiter = Address::repeat_byte(4);
+    let state = State {
+        genesis: Arc::new(Genesis {
+            arbiter: Some(arbiter),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let block = BlockInfo {
+        id: ids::Id::empty(),
+        height: 1,
+        timestamp: 10,
+    };
+
+    // Players can abort a game before any move is made
+    let game_id = state
+        .create_new_game(alice, bob, GameVariant::Standard, None, 0, 10)
+        .await
+        .unwrap();
+    assert!(state.end_game(carol, game_id, 10).await.is_err());
+    state.end_game(bob, game_id, 10).await.unwrap();
+    assert!(state.get_game(game_id).await.is_none());
+
+    // Ended games stay queryable from the archive
+    let archived = state.get_game_record(game_id).await.unwrap();
+    assert!(state.is_archived(game_id).await);
+    assert_eq!(archived.outcome.unwrap().termination, Termination::Aborted);
+    assert_eq!(archived.move_count, 0);
+    // It cannot be ended again
+    assert!(state.end_game(bob, game_id, 10).await.is_err());
+    assert_eq!(state.get_game_record(game_id).await.unwrap(), archived);
+
+    // Games of the same players get their own IDs
+    let game_id = state
+        .create_new_game(alice, bob, GameVariant::Standard, None, 1, 10)
+        .await
+        .unwrap();
+    assert!(!state.is_archived(game_id).await);
+    assert!(state
+        .create_new_game(alice, bob, GameVariant::Standard, None, 1, 10)
+        .await
+        .is_err());
+
+    // Once moves are made, only the arbiter can end the game
+    let pos = state.get_game(game_id).await.unwrap();
+    let mv = pos.legal_moves()[0].clone();
+    // Moves out of turn are refused
+    assert!(state.make_move(bob, game_id, &mv, block).await.is_err());
+    state.make_move(alice, game_id, &mv, block).await.unwrap();
+    // So are illegal moves
+    assert!(state.make_move(bob, game_id, &mv, block).await.is_err());
+    assert!(state.end_game(alice, game_id, 10).await.is_err());
+    assert!(state.end_game(carol, game_id, 10).await.is_err());
+    state.end_game(arbiter, game_id, 10).await.unwrap();
+    let outcome = state.get_game_outcome(game_id).await.unwrap();
+    assert_eq!(outcome.termination, Termination::Arbiter);
+    assert!(state.is_archived(game_id).await);
+
+    // Finished games are archived once settled, or when ended, keeping
+    // their result
+    let game_id = state
+        .create_new_game(
+            alice,
+            bob,
+            GameVariant::Standard,
+            Some("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1"),
+            2,
+            10,
+        )
+        .await
+        .unwrap();
+    assert!(state.get_game_outcome(game_id).await.is_some());
+    let pos = state.get_game(game_id).await.unwrap();
+    assert!(pos.legal_moves().is_empty());
+    assert!(state.make_move(bob, game_id, &mv, block).await.is_err());
+    assert!(state.end_game(carol, game_id, 10).await.is_err());
+    assert!(!state.is_archived(game_id).await);
+    // Only the ended game is archived
+    let other_id = state
+        .create_new_game(
+            alice,
+            bob,
+            GameVariant::Standard,
+            Some("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1"),
+            3,
+            10,
+        )
+        .await
+        .unwrap();
+    state.end_game(bob, game_id, 10).await.unwrap();
+    assert!(state.is_archived(game_id).await);
+    assert!(!state.is_archived(other_id).await);
+    let outcome = state.get_game_outcome(game_id).await.unwrap();
+    assert_eq!(outcome.termination, Termination::Checkmate);
+    assert!(state.end_game(arbiter, game_id, 10).await.is_err());
+}
+
+#[tokio::test]
+async fn test_custom_fen() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+
+    // Games can start from a given position, with black to move
+    let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+    let game_id = state
+        .create_new_game(alice, bob, GameVariant::Standard, Some(fen), 0, 10)
+        .await
+        .unwrap();
+    let pos = state.get_game(game_id).await.unwrap();
+    assert_eq!(pos.turn(), shakmaty::Color::Black);
+    assert_eq!(
+        shakmaty::fen::Fen::from_position(pos, shakmaty::EnPassantMode::Always).to_string(),
+        fen
+    );
+
+    // Invalid or illegal positions are refused
+    assert!(state
+        .create_new_game(alice, bob, GameVariant::Standard, Some("not a fen"), 1, 10)
+        .await
+        .is_err());
+    assert!(state
+        .create_new_game(
+            bob,
+            alice,
+            GameVariant::Standard,
+            Some("8/8/8/8/8/8/8/8 w - - 0 1"),
+            0,
+            10
+        )
+        .await
+        .is_err());
+    assert!(!state.game_exists(calculate_game_id(bob, alice, 0)).await);
+
+    // Players cannot play themselves
+    assert!(state
+        .create_new_game(alice, alice, GameVariant::Standard, None, 0, 10)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_takeback() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let challenge = Challenge {
+        challenger: alice,
+        opponent: Some(bob),
+        color: ColorPreference::White,
+        time_control: Some(TimeControl {
+            initial: 60,
+            increment: 0,
+        }),
+        variant: GameVariant::Standard,
+        stake: 0,
+        created_at: 0,
+    };
+    let game_id = state.create_challenge(challenge, 0).await.unwrap();
+    state
+        .accept_challenge(bob, game_id, block_at(0))
+        .await
+        .unwrap();
+
+    let play = |player, uci: &str, timestamp| {
+        let state = state.clone();
+        let uci = uci.to_string();
+        async move {
+            let pos = state.get_game(game_id).await.unwrap();
+            let mv = Uci::from_ascii(uci.as_bytes())
+                .unwrap()
+                .to_move(&pos)
+                .unwrap();
+            let block = BlockInfo {
+                id: ids::Id::empty(),
+                height: timestamp,
+                timestamp,
+            };
+            state.make_move(player, game_id, &mv, block).await.unwrap();
+        }
+    };
+    play(alice, "e2e4", 5).await;
+    play(bob, "e7e5", 15).await;
+    play(alice, "g1f3", 20).await;
+
+    // Only the opponent of the requester can accept
+    assert!(state.request_takeback(alice, game_id, 4).await.is_err());
+    state.request_takeback(alice, game_id, 2).await.unwrap();
+    assert!(state.accept_takeback(alice, game_id, 30).await.is_err());
+    state.accept_takeback(bob, game_id, 30).await.unwrap();
+
+    let game = state.get_game_state(game_id).await.unwrap();
+    assert_eq!(game.history().len(), 1);
+    assert_eq!(game.game().turn(), Color::Black);
+    let clock = game.clock().unwrap();
+    assert_eq!((clock.white, clock.black, clock.last_update), (55, 60, 30));
+    assert!(game.takeback().is_none());
+
+    // A pending request expires on the next move
+    state.request_takeback(bob, game_id, 1).await.unwrap();
+    play(bob, "d7d5", 35).await;
+    assert!(state.accept_takeback(alice, game_id, 40).await.is_err());
+}
+
+#[tokio::test]
+async fn test_pause() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let state = State {
+        genesis: Arc::new(Genesis {
+            max_pause: 100,
+            pause_expiry: PauseExpiry::Material,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let new_game = |nonce| {
+        let state = state.clone();
+        async move {
+            let challenge = Challenge {
+                challenger: alice,
+                opponent: Some(bob),
+                color: ColorPreference::White,
+                time_control: Some(TimeControl {
+                    initial: 60,
+                    increment: 0,
+                }),
+                variant: GameVariant::Standard,
+                stake: 0,
+                created_at: 0,
+            };
+            let game_id = state.create_challenge(challenge, nonce).await.unwrap();
+            state
+                .accept_challenge(bob, game_id, block_at(0))
+                .await
+                .unwrap();
+            game_id
+        }
+    };
+
+    // Clocks are frozen once the opponent accepts
+    let game_id = new_game(0).await;
+    assert!(state.accept_pause(bob, game_id, 5).await.is_err());
+    state.request_pause(alice, game_id).await.unwrap();
+    assert!(state.accept_pause(alice, game_id, 5).await.is_err());
+    state.accept_pause(bob, game_id, 10).await.unwrap();
+    let pos = state.get_game(game_id).await.unwrap();
+    let mv = pos.legal_moves()[0].clone();
+    let block = BlockInfo {
+        id: ids::Id::empty(),
+        height: 1,
+        timestamp: 50,
+    };
+    assert!(state.make_move(alice, game_id, &mv, block).await.is_err());
+
+    state.resume(bob, game_id, 50).await.unwrap();
+    state.make_move(alice, game_id, &mv, block).await.unwrap();
+    let clock = state
+        .get_game_state(game_id)
+        .await
+        .unwrap()
+        .clock()
+        .unwrap();
+    assert_eq!(clock.white, 50);
+
+    // Pauses lasting too long are adjudicated following genesis
+    let game_id = new_game(1).await;
+    state.request_pause(bob, game_id).await.unwrap();
+    state.accept_pause(alice, game_id, 10).await.unwrap();
+    state.expire_pauses(109).await;
+    assert!(state.get_game_outcome(game_id).await.is_none());
+    state.expire_pauses(110).await;
+    let outcome = state.get_game_outcome(game_id).await.unwrap();
+    assert_eq!(outcome.result, GameResult::Draw);
+    assert_eq!(outcome.termination, Termination::Adjudication);
+
+    // Material only decides games of standard chess
+    let fen = "4k3/8/8/8/8/8/8/QQ2K3 w - - 0 1";
+    for (variant, result) in [
+        (GameVariant::Standard, GameResult::WhiteWins),
+        (GameVariant::Antichess, GameResult::Draw),
+    ] {
+        let game_id = state
+            .create_new_game(alice, bob, variant, Some(fen), 0, 0)
+            .await
+            .unwrap();
+        let mut game_states = state.game_states.write().await;
+        let game = game_states.get_mut(&game_id).unwrap();
+        game.adjudicate(PauseExpiry::Material);
+        assert_eq!(game.outcome.unwrap().result, result);
+        game_states.remove(&game_id);
+    }
+}
+
+#[tokio::test]
+async fn test_rematch() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let time_control = TimeControl {
+        initial: 300,
+        increment: 2,
+    };
+    let challenge = Challenge {
+        challenger: alice,
+        opponent: Some(bob),
+        color: ColorPreference::White,
+        time_control: Some(time_control),
+        variant: GameVariant::Atomic,
+        stake: 0,
+        created_at: 0,
+    };
+    let game_id = state.create_challenge(challenge, 0).await.unwrap();
+    state
+        .accept_challenge(bob, game_id, block_at(0))
+        .await
+        .unwrap();
+
+    // Rematches can only be offered once the game is finished
+    assert!(state.offer_rematch(alice, game_id).await.is_err());
+    state
+        .game_states
+        .write()
+        .await
+        .get_mut(&game_id)
+        .unwrap()
+        .adjudicate(PauseExpiry::Draw);
+    state.settle_games(10).await;
+
+    state.offer_rematch(alice, game_id).await.unwrap();
+    assert!(state.accept_rematch(alice, game_id, 50).await.is_err());
+    let rematch_id = state.accept_rematch(bob, game_id, 50).await.unwrap();
+
+    let rematch = state.get_game_state(rematch_id).await.unwrap();
+    assert_eq!((rematch.white(), rematch.black()), (bob, alice));
+    assert_eq!(rematch.variant(), GameVariant::Atomic);
+    assert_eq!(rematch.time_control(), Some(time_control));
+    assert_eq!(rematch.rematch_of(), Some(game_id));
+    assert!(state.offer_rematch(bob, game_id).await.is_err());
+
+    assert_eq!(
+        state.get_rematch_chain(rematch_id).await,
+        vec![game_id, rematch_id]
+    );
+    assert_eq!(
+        state.get_rematch_chain(game_id).await,
+        vec![game_id, rematch_id]
+    );
+}
+
+#[tokio::test]
+async fn test_ratings() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let block = BlockInfo {
+        id: ids::Id::empty(),
+        height: 1,
+        timestamp: 10,
+    };
+
+    let challenge = Challenge {
+        challenger: alice,
+        opponent: Some(bob),
+        color: ColorPreference::White,
+        time_control: None,
+        variant: GameVariant::Standard,
+        stake: 0,
+        created_at: 0,
+    };
+    // Games created without the consent of black are not rated, games from
+    // accepted challenges are
+    let casual_id = state
+        .create_new_game(alice, bob, GameVariant::Standard, None, 0, 0)
+        .await
+        .unwrap();
+    let challenge_id = state.create_challenge(challenge, 0).await.unwrap();
+    let game_id = state
+        .accept_challenge(bob, challenge_id, block_at(0))
+        .await
+        .unwrap();
+    for id in [casual_id, game_id] {
+        // Fool's mate
+        for (player, uci) in [
+            (alice, "f2f3"),
+            (bob, "e7e5"),
+            (alice, "g2g4"),
+            (bob, "d8h4"),
+        ] {
+            let pos = state.get_game(id).await.unwrap();
+            let mv = Uci::from_ascii(uci.as_bytes())
+                .unwrap()
+                .to_move(&pos)
+                .unwrap();
+            state.make_move(player, id, &mv, block).await.unwrap();
+        }
+        state.settle_games(10).await;
+        // Settling twice does not rate the game twice
+        state.settle_games(10).await;
+    }
+    assert!(!state.get_game_record(casual_id).await.unwrap().rated);
+
+    let pool = RatingPool {
+        variant: GameVariant::Standard,
+        category: TimeCategory::Correspondence,
+    };
+    assert_eq!(state.get_rating(bob, pool).await.rating, 1520);
+    assert_eq!(state.get_rating(alice, pool).await.games, 1);
+    assert_eq!(state.get_ratings(alice).await.len(), 1);
+
+    let (leaders, total) = state.get_leaderboard(pool, 0, 1).await;
+    assert_eq!(total, 2);
+    assert_eq!(leaders[0].0, bob);
+}
+
+#[tokio::test]
+async fn test_player_stats() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let block = BlockInfo {
+        id: ids::Id::empty(),
+        height: 1,
+        timestamp: 10,
+    };
+
+    let challenge = Challenge {
+        challenger: alice,
+        opponent: Some(bob),
+        color: ColorPreference::White,
+        time_control: None,
+        variant: GameVariant::Standard,
+        stake: 0,
+        created_at: 0,
+    };
+    let challenge_id = state.create_challenge(challenge, 0).await.unwrap();
+    let game_id = state
+        .accept_challenge(bob, challenge_id, block_at(0))
+        .await
+        .unwrap();
+
+    // Fool's mate
+    for (player, uci) in [
+        (alice, "f2f3"),
+        (bob, "e7e5"),
+        (alice, "g2g4"),
+        (bob, "d8h4"),
+    ] {
+        let pos = state.get_game(game_id).await.unwrap();
+        let mv = Uci::from_ascii(uci.as_bytes())
+            .unwrap()
+            .to_move(&pos)
+            .unwrap();
+        state.make_move(player, game_id, &mv, block).await.unwrap();
+    }
+    state.settle_games(10).await;
+    // Settling twice does not count the game twice
+    state.settle_games(10).await;
+
+    let stats = state.get_player_stats(alice).await;
+    assert_eq!((stats.record.played, stats.record.lost), (1, 1));
+    assert_eq!(stats.head_to_head[&bob].lost, 1);
+    assert_eq!(stats.average_length(), Some(4.0));
+    let json = serde_json::to_string(&stats).unwrap();
+    assert_eq!(serde_json::from_str::<PlayerStats>(&json).unwrap(), stats);
+    assert_eq!(
+        stats.streak,
+        Some(stats::Streak {
+            score: Score::Loss,
+            length: 1
+        })
+    );
+    let stats = state.get_player_stats(bob).await;
+    assert_eq!((stats.record.played, stats.record.won), (1, 1));
+    assert_eq!(stats.head_to_head[&alice].won, 1);
+}
+
+#[tokio::test]
+async fn test_tournament() {
+    let state = State::default();
+    let players: Vec<Address> = (1..=4).map(Address::repeat_byte).collect();
+    let organizer = players[0];
+    let tournament = Tournament {
+        organizer,
+        format: tournament::TournamentFormat::RoundRobin,
+        rounds: 3,
+        variant: GameVariant::Standard,
+        time_control: None,
+        registration_deadline: 100,
+        players: Vec::new(),
+        withdrawn: Vec::new(),
+        pairings: Vec::new(),
+        tiebreak: tournament::Tiebreak::default(),
+        bracket: Vec::new(),
+        created_at: 0,
+    };
+    let tournament_id = state
+        .create_tournament(tournament.clone(), 0)
+        .await
+        .unwrap();
+    // Rounds need players
+    assert!(state
+        .start_round(organizer, tournament_id, 100)
+        .await
+        .is_err());
+
+    for player in players.iter() {
+        state
+            .register_for_tournament(*player, tournament_id, 10)
+            .await
+            .unwrap();
+    }
+    assert!(state
+        .register_for_tournament(players[0], tournament_id, 10)
+        .await
+        .is_err());
+    assert!(state
+        .register_for_tournament(Address::repeat_byte(5), tournament_id, 100)
+        .await
+        .is_err());
+
+    // Only the organizer starts rounds, once registration is closed
+    assert!(state
+        .start_round(players[1], tournament_id, 100)
+        .await
+        .is_err());
+    assert!(state
+        .start_round(organizer, tournament_id, 50)
+        .await
+        .is_err());
+    assert_eq!(
+        state
+            .start_round(organizer, tournament_id, 100)
+            .await
+            .unwrap(),
+        1
+    );
+
+    // Equally rated players are seeded in registration order
+    let round = &state.get_tournament(tournament_id).await.unwrap().pairings[0];
+    assert_eq!(
+        round.iter().map(|p| (p.white, p.black)).collect::<Vec<_>>(),
+        vec![
+            (players[0], Some(players[3])),
+            (players[1], Some(players[2]))
+        ]
+    );
+    let game_ids: Vec<u64> = round.iter().filter_map(|p| p.game_id).collect();
+    let game = state.get_game_state(game_ids[0]).await.unwrap();
+    assert_eq!(game.tournament(), Some(tournament_id));
+
+    // The next round waits for every game to be over
+    assert!(state
+        .start_round(organizer, tournament_id, 110)
+        .await
+        .is_err());
+    // A called off game counts as over, but neither player scores
+    let mut game_states = state.game_states.write().await;
+    game_states.get_mut(&game_ids[0]).unwrap().outcome = Some(GameOutcome {
+        result: GameResult::WhiteWins,
+        termination: Termination::Checkmate,
+    });
+    game_states.get_mut(&game_ids[1]).unwrap().outcome = Some(GameOutcome {
+        result: GameResult::Draw,
+        termination: Termination::Arbiter,
+    });
+    drop(game_states);
+    assert_eq!(
+        state
+            .start_round(organizer, tournament_id, 110)
+            .await
+            .unwrap(),
+        2
+    );
+
+    let standings = state.get_standings(tournament_id).await.unwrap();
+    assert_eq!(standings[0].player, players[0]);
+    assert_eq!(standings[0].points, 1.0);
+    assert_eq!(standings.iter().map(|s| s.points).sum::<f64>(), 1.0);
+
+    // Knockouts advance winners as soon as their games are settled
+    let knockout = Tournament {
+        format: TournamentFormat::Knockout,
+        ..tournament
+    };
+    let knockout_id = state.create_tournament(knockout, 1).await.unwrap();
+    for player in players[..3].iter() {
+        state
+            .register_for_tournament(*player, knockout_id, 10)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        state
+            .start_round(organizer, knockout_id, 100)
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(state
+        .start_round(organizer, knockout_id, 100)
+        .await
+        .is_err());
+
+    let bracket = state.get_tournament(knockout_id).await.unwrap().bracket;
+    assert_eq!(bracket[0][0].winner, Some(players[0]));
+    let semi_final = bracket[0][1].games[0].game_id;
+    state
+        .game_states
+        .write()
+        .await
+        .get_mut(&semi_final)
+        .unwrap()
+        .outcome = Some(GameOutcome {
+        result: GameResult::BlackWins,
+        termination: Termination::Checkmate,
+    });
+    state.settle_games(120).await;
+
+    let bracket = state.get_tournament(knockout_id).await.unwrap().bracket;
+    assert_eq!(bracket[0][1].winner, Some(players[2]));
+    let final_game = bracket[1][0].games[0];
+    assert_eq!(
+        (final_game.white, final_game.black),
+        (players[0], players[2])
+    );
+    let game = state.get_game_state(final_game.game_id).await.unwrap();
+    assert_eq!(game.created_at(), 120);
+    assert_eq!(game.tournament(), Some(knockout_id));
+
+    // Withdrawing forfeits the game being played, sending the opponent
+    // through
+    state
+        .withdraw_from_tournament(players[0], knockout_id)
+        .await
+        .unwrap();
+    state.settle_games(130).await;
+    let outcome = state.get_game_outcome(final_game.game_id).await.unwrap();
+    assert_eq!(
+        (outcome.result, outcome.termination),
+        (GameResult::BlackWins, Termination::Forfeit)
+    );
+    let tournament = state.get_tournament(knockout_id).await.unwrap();
+    assert_eq!(tournament.bracket_winner(), Some(players[2]));
+}
+
+#[tokio::test]
+async fn test_seeks() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let carol = Address::repeat_byte(3);
+    let blitz = Some(TimeControl {
+        initial: 180,
+        increment: 2,
+    });
+    let seek = |player, created_at, rating_range| Seek {
+        player,
+        time_control: blitz,
+        variant: GameVariant::Standard,
+        rating_range,
+        created_at,
+    };
+
+    let alice_seek = state.post_seek(seek(alice, 10, None), 0).await.unwrap();
+    assert!(state.post_seek(seek(alice, 10, None), 1).await.is_err());
+    // Carol only wants strong opponents, so she is left waiting
+    let strong = Some(RatingRange {
+        min: 1800,
+        max: 3000,
+    });
+    let carol_seek = state.post_seek(seek(carol, 11, strong), 0).await.unwrap();
+    let bob_seek = state.post_seek(seek(bob, 12, None), 0).await.unwrap();
+
+    let pairs = state.pair_seeks(20).await;
+    assert_eq!(pairs, vec![(alice_seek, bob_seek)]);
+    // Pairs listing the newer seek first, or seeks that can't be paired, are
+    // skipped when the block is accepted
+    state
+        .start_seek_games(
+            &[(bob_seek, alice_seek), (alice_seek, carol_seek)],
+            block_at(20),
+        )
+        .await;
+    assert_eq!(state.list_seeks(20).await.len(), 3);
+    state.start_seek_games(&pairs, block_at(20)).await;
+    let game = state.get_game_state(alice_seek).await.unwrap();
+    assert_eq!(game.time_control(), blitz);
+    assert!([game.white(), game.black()].contains(&bob));
+    // Colors are drawn from the block for the player of the older seek
+    let white = if draws_white(&ids::Id::empty(), alice) {
+        alice
+    } else {
+        bob
+    };
+    assert_eq!(game.white(), white);
+    assert_eq!(
+        state
+            .list_seeks(20)
+            .await
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>(),
+        vec![carol_seek]
+    );
+
+    // Seeks expire after the TTL set in genesis
+    let expiry = 11 + state.genesis.seek_ttl;
+    assert_eq!(state.list_seeks(expiry - 1).await.len(), 1);
+    state.expire_seeks(expiry).await;
+    assert!(state.list_seeks(0).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_wagers() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let state = State {
+        ledger: Arc::new(RwLock::new(Ledger::from_allocations(
+            &std::collections::BTreeMap::from([(alice, 100), (bob, 100)]),
+        ))),
+        ..Default::default()
+    };
+    let balances = || async { (state.get_balance(alice).await, state.get_balance(bob).await) };
+
+    // Games created directly are never wagered, since only the stakes of
+    // challenges are agreed to by both players
+    let game_id = state
+        .create_new_game(alice, bob, GameVariant::Standard, None, 0, 0)
+        .await
+        .unwrap();
+    assert_eq!(state.get_game_state(game_id).await.unwrap().stake(), 0);
+    assert_eq!(balances().await, (100, 100));
+
+    // Both stakes are locked when the challenge is accepted
+    let challenge = Challenge {
+        challenger: alice,
+        opponent: Some(bob),
+        color: ColorPreference::White,
+        time_control: None,
+        variant: GameVariant::Standard,
+        stake: 40,
+        created_at: 0,
+    };
+    let game_id = state.create_challenge(challenge, 1).await.unwrap();
+    state
+        .accept_challenge(bob, game_id, block_at(0))
+        .await
+        .unwrap();
+    assert_eq!(balances().await, (60, 60));
+    assert_eq!(state.get_locked(alice).await, 40);
+
+    // The winner takes both stakes once the game is settled
+    for (player, uci) in [
+        (alice, "f2f3"),
+        (bob, "e7e5"),
+        (alice, "g2g4"),
+        (bob, "d8h4"),
+    ] {
+        let pos = state.get_game(game_id).await.unwrap();
+        let mv = Uci::from_ascii(uci.as_bytes())
+            .unwrap()
+            .to_move(&pos)
+            .unwrap();
+        let block = BlockInfo {
+            id: ids::Id::empty(),
+            height: 1,
+            timestamp: 1,
+        };
+        state.make_move(player, game_id, &mv, block).await.unwrap();
+    }
+    state.settle_games(1).await;
+    assert_eq!(balances().await, (60, 140));
+    assert_eq!(state.get_escrow(game_id).await, None);
+    assert!(state.is_archived(game_id).await);
+
+    // Accepting a wagered challenge locks the stakes, and aborting refunds
+    // them
+    let challenge = Challenge {
+        challenger: alice,
+        opponent: Some(bob),
+        color: ColorPreference::White,
+        time_control: None,
+        variant: GameVariant::Standard,
+        stake: 61,
+        created_at: 0,
+    };
+    assert!(state.create_challenge(challenge.clone(), 0).await.is_err());
+    let challenge = Challenge {
+        stake: 50,
+        ..challenge
+    };
+    let game_id = state.create_challenge(challenge, 0).await.unwrap();
+    state
+        .accept_challenge(bob, game_id, block_at(0))
+        .await
+        .unwrap();
+    assert_eq!(balances().await, (10, 90));
+    assert_eq!(state.get_game_state(game_id).await.unwrap().stake(), 50);
+    state.end_game(bob, game_id, 0).await.unwrap();
+    assert_eq!(balances().await, (60, 140));
+    assert_eq!(state.get_game_record(game_id).await.unwrap().stake, 50);
+}
+
+#[tokio::test]
+async fn test_puzzles() {
+    let poster = Address::repeat_byte(1);
+    let alice = Address::repeat_byte(2);
+    let state = State {
+        ledger: Arc::new(RwLock::new(Ledger::from_allocations(
+            &std::collections::BTreeMap::from([(poster, 100)]),
+        ))),
+        ..Default::default()
+    };
+    let solution = vec!["a1a8".to_string()];
+    let puzzle = Puzzle {
+        poster,
+        fen: "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1".to_string(),
+        solution_hash: puzzle::solution_hash(&solution),
+        reward: 30,
+        deadline: 100,
+        commitments: Vec::new(),
+        winner: None,
+        closed: false,
+        created_at: 0,
+    };
+
+    // The reward is held until the puzzle is solved
+    assert!(state
+        .post_puzzle(
+            Puzzle {
+                reward: 101,
+                ..puzzle.clone()
+            },
+            0
+        )
+        .await
+        .is_err());
+    let solved = state.post_puzzle(puzzle.clone(), 0).await.unwrap();
+    let unsolved = state.post_puzzle(puzzle, 1).await.unwrap();
+    assert_eq!(state.get_balance(poster).await, 40);
+
+    let hash = puzzle::commitment_hash(alice, &solution, "salt");
+    state
+        .commit_solution(alice, solved, hash, 10)
+        .await
+        .unwrap();
+    state
+        .commit_solution(alice, unsolved, B256::ZERO, 10)
+        .await
+        .unwrap();
+    // As the earliest committer, Alice is paid as soon as she reveals
+    state
+        .reveal_solution(alice, solved, &solution, "salt", 20)
+        .await
+        .unwrap();
+    assert_eq!(state.get_balance(alice).await, 30);
+    assert_eq!(state.get_puzzle(solved).await.unwrap().winner, Some(alice));
+
+    // Unsolved puzzles are refunded once reveals are over
+    let reveals_over = 100 + state.genesis.puzzle_reveal_window;
+    state.close_puzzles(reveals_over - 1).await;
+    assert_eq!(state.get_balance(poster).await, 40);
+    // A reward that cannot be paid keeps the puzzle open until it can
+    let full = u64::MAX - 40;
+    state.ledger.write().await.credit(poster, full).unwrap();
+    state.close_puzzles(reveals_over).await;
+    assert!(!state.get_puzzle(unsolved).await.unwrap().closed);
+    state.ledger.write().await.debit(poster, full).unwrap();
+    state.close_puzzles(reveals_over).await;
+    assert_eq!(state.get_balance(poster).await, 70);
+    assert!(state.get_puzzle(unsolved).await.unwrap().closed);
+}
+
+#[tokio::test]
+async fn test_consultation_games() {
+    let state = State::default();
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let carol = Address::repeat_byte(3);
+    let dave = Address::repeat_byte(4);
+    let white_team = state.create_team(alice, 0).await.unwrap();
+    state.add_team_member(alice, white_team, bob).await.unwrap();
+    state
+        .add_team_member(alice, white_team, carol)
+        .await
+        .unwrap();
+    let black_team = state.create_team(dave, 0).await.unwrap();
+
+    let consultation = Consultation {
+        white_team,
+        black_team,
+        voting_window: 60,
+        votes: BTreeMap::new(),
+    };
+    // Only the owner of one of the teams can start their game
+    assert!(state
+        .create_consultation_game(bob, consultation.clone(), GameVariant::Standard, 0, 0)
+        .await
+        .is_err());
+    let game_id = state
+        .create_consultation_game(alice, consultation, GameVariant::Standard, 0, 0)
+        .await
+        .unwrap();
+    // Nor abort it before any move is made
+    assert!(state.end_game(alice, game_id, 0).await.is_err());
+    assert!(state.end_game(dave, game_id, 0).await.is_err());
+
+    let vote = |voter, uci: &str, timestamp| {
+        let state = state.clone();
+        let uci = uci.to_string();
+        async move {
+            let pos = state.get_game(game_id).await.unwrap();
+            let mv = Uci::from_ascii(uci.as_bytes())
+                .unwrap()
+                .to_move(&pos)
+                .unwrap();
+            let block = BlockInfo {
+                id: ids::Id::empty(),
+                height: timestamp,
+                timestamp,
+            };
+            state.vote_move(voter, game_id, &mv, block).await
+        }
+    };
+    let moves = |state: State| async move {
+        let game = state.get_game_state(game_id).await.unwrap();
+        game.history
+            .iter()
+            .map(|m| m.uci.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // Moves are decided by votes, by members of the side to move only
+    let pos = state.get_game(game_id).await.unwrap();
+    let e4 = Uci::from_ascii(b"e2e4").unwrap().to_move(&pos).unwrap();
+    let block = BlockInfo {
+        id: ids::Id::empty(),
+        height: 1,
+        timestamp: 1,
+    };
+    assert!(state.make_move(alice, game_id, &e4, block).await.is_err());
+    assert!(vote(dave, "e2e4", 1).await.is_err());
+
+    // A majority plays the move right away
+    vote(alice, "d2d4", 1).await.unwrap();
+    vote(bob, "e2e4", 2).await.unwrap();
+    assert_eq!(
+        state.get_vote_tally(game_id).await,
+        BTreeMap::from([("d2d4".to_string(), 1), ("e2e4".to_string(), 1)])
+    );
+    vote(carol, "e2e4", 3).await.unwrap();
+    assert_eq!(moves(state.clone()).await, ["e2e4"]);
+    assert!(state.get_vote_tally(game_id).await.is_empty());
+
+    // Team owners cannot take moves back or pause the game on their own
+    assert!(state.request_takeback(alice, game_id, 1).await.is_err());
+    assert!(state.request_pause(dave, game_id).await.is_err());
+
+    // Otherwise the most voted move is played once the window is over
+    state.add_team_member(dave, black_team, bob).await.unwrap();
+    state
+        .add_team_member(dave, black_team, carol)
+        .await
+        .unwrap();
+    vote(dave, "e7e5", 10).await.unwrap();
+    vote(bob, "c7c5", 20).await.unwrap();
+    let block = BlockInfo {
+        id: ids::Id::empty(),
+        height: 62,
+        timestamp: 62,
+    };
+    assert!(!state.has_due_votes(62).await);
+    state.resolve_votes(block).await;
+    assert_eq!(moves(state.clone()).await, ["e2e4"]);
+    assert!(state.has_due_votes(63).await);
+    state
+        .resolve_votes(BlockInfo {
+            timestamp: 63,
+            ..block
+        })
+        .await;
+    // Ties go to the move coming first in UCI notation
+    assert_eq!(moves(state.clone()).await, ["e2e4", "c7c5"]);
+}
+
+#[tokio::test]
+async fn test_simuls() {
+    let state = State::default();
+    let host = Address::repeat_byte(1);
+    let alice = Address::repeat_byte(2);
+    let bob = Address::repeat_byte(3);
+    let simul = Simul {
+        host,
+        boards: 3,
+        variant: GameVariant::Standard,
+        time_control: None,
+        players: Vec::new(),
+        game_ids: Vec::new(),
+        created_at: 0,
+    };
+    let simul_id = state.create_simul(simul, 0).await.unwrap();
+    state.register_for_simul(alice, simul_id).await.unwrap();
+    state.register_for_simul(bob, simul_id).await.unwrap();
+
+    assert!(state.start_simul(alice, simul_id, 1).await.is_err());
+    state.start_simul(host, simul_id, 1).await.unwrap();
+    assert!(state.start_simul(host, simul_id, 2).await.is_err());
+    let simul = state.get_simul(simul_id).await.unwrap();
+    assert_eq!(simul.game_ids.len(), 2);
+    let game = state.get_game_record(simul.game_ids[0]).await.unwrap();
+    assert_eq!(
+        (game.white, game.black, game.simul),
+        (host, alice, Some(simul_id))
+    );
+
+    let play = |player, game_id, uci: &str| {
+        let state = state.clone();
+        let uci = uci.to_string();
+        async move {
+            let pos = state.get_game(game_id).await.unwrap();
+            let mv = Uci::from_ascii(uci.as_bytes())
+                .unwrap()
+                .to_move(&pos)
+                .unwrap();
+            let block = BlockInfo {
+                id: ids::Id::empty(),
+                height: 2,
+                timestamp: 2,
+            };
+            state.make_move(player, game_id, &mv, block).await.unwrap();
+        }
+    };
+    // The first board is aborted, and the host is mated on the second
+    state.end_game(alice, simul.game_ids[0], 2).await.unwrap();
+    play(host, simul.game_ids[1], "f2f3").await;
+    play(bob, simul.game_ids[1], "e7e5").await;
+    play(host, simul.game_ids[1], "g2g4").await;
+    play(bob, simul.game_ids[1], "d8h4").await;
+    state.settle_games(2).await;
+
+    let boards = state.get_simul_boards(&simul).await;
+    assert_eq!(boards[0].to_move, None);
+    assert_eq!(
+        boards[0].outcome.map(|o| o.termination),
+        Some(Termination::Aborted)
+    );
+    assert_eq!(boards[1].to_move, None);
+    let score = simul::score(&boards);
+    assert_eq!((score.losses, score.ongoing, score.void), (1, 0, 1));
+    assert_eq!((score.host_points, score.opponent_points), (0.0, 1.0));
 }
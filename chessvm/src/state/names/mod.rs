@@ -0,0 +1,130 @@
+//! Human readable names of addresses.
+
+use std::{
+    collections::HashMap,
+    io::{self, Error},
+};
+
+use alloy_primitives::Address;
+
+pub const MIN_NAME_LENGTH: usize = 3;
+pub const MAX_NAME_LENGTH: usize = 20;
+
+/// Checks that a name is 3 to 20 lowercase ASCII letters, digits or
+/// underscores, starting with a letter
+pub fn validate_name(name: &str) -> io::Result<()> {
+    if name.len() < MIN_NAME_LENGTH || name.len() > MAX_NAME_LENGTH {
+        return Err(Error::other(format!(
+            "names must be {MIN_NAME_LENGTH} to {MAX_NAME_LENGTH} characters long"
+        )));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return Err(Error::other("names must start with a lowercase letter"));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(Error::other(
+            "names can only contain lowercase letters, digits and underscores",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Names and the addresses owning them; an address owns at most one name
+#[derive(Clone, Debug, Default)]
+pub struct NameRegistry {
+    owners: HashMap<String, Address>,
+    names: HashMap<Address, String>,
+}
+
+impl NameRegistry {
+    pub fn register(&mut self, owner: Address, name: &str) -> io::Result<()> {
+        validate_name(name)?;
+        if self.owners.contains_key(name) {
+            return Err(Error::other(format!("name '{name}' is already taken")));
+        }
+        if let Some(current) = self.names.get(&owner) {
+            return Err(Error::other(format!(
+                "{owner} already owns the name '{current}'"
+            )));
+        }
+
+        self.owners.insert(name.to_string(), owner);
+        self.names.insert(owner, name.to_string());
+
+        Ok(())
+    }
+
+    /// Gives a name owned by `owner` to `to`
+    pub fn transfer(&mut self, owner: Address, name: &str, to: Address) -> io::Result<()> {
+        self.check_owner(owner, name)?;
+        if let Some(current) = self.names.get(&to) {
+            return Err(Error::other(format!(
+                "{to} already owns the name '{current}'"
+            )));
+        }
+
+        self.names.remove(&owner);
+        self.owners.insert(name.to_string(), to);
+        self.names.insert(to, name.to_string());
+
+        Ok(())
+    }
+
+    /// Frees a name owned by `owner`, so that anyone can register it
+    pub fn release(&mut self, owner: Address, name: &str) -> io::Result<()> {
+        self.check_owner(owner, name)?;
+
+        self.owners.remove(name);
+        self.names.remove(&owner);
+
+        Ok(())
+    }
+
+    /// Returns the address owning `name`
+    pub fn resolve(&self, name: &str) -> Option<Address> {
+        self.owners.get(name).copied()
+    }
+
+    /// Returns the name owned by `owner`
+    pub fn reverse_resolve(&self, owner: Address) -> Option<&str> {
+        self.names.get(&owner).map(String::as_str)
+    }
+
+    fn check_owner(&self, owner: Address, name: &str) -> io::Result<()> {
+        match self.owners.get(name) {
+            Some(o) if *o == owner => Ok(()),
+            Some(_) => Err(Error::other(format!(
+                "name '{name}' is owned by another address"
+            ))),
+            None => Err(Error::other(format!("name '{name}' is not registered"))),
+        }
+    }
+}
+
+#[test]
+fn test_name_registry() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let mut registry = NameRegistry::default();
+
+    assert!(registry.register(alice, "Alice").is_err());
+    assert!(registry.register(alice, "al").is_err());
+    assert!(registry.register(alice, "1alice").is_err());
+    registry.register(alice, "alice_1").unwrap();
+    assert!(registry.register(bob, "alice_1").is_err());
+    assert!(registry.register(alice, "alice_2").is_err());
+
+    assert!(registry.transfer(bob, "alice_1", bob).is_err());
+    registry.transfer(alice, "alice_1", bob).unwrap();
+    assert_eq!(registry.resolve("alice_1"), Some(bob));
+    assert_eq!(registry.reverse_resolve(bob), Some("alice_1"));
+    assert_eq!(registry.reverse_resolve(alice), None);
+
+    registry.release(bob, "alice_1").unwrap();
+    assert_eq!(registry.resolve("alice_1"), None);
+    registry.register(alice, "alice_1").unwrap();
+}
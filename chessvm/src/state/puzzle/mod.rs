@@ -0,0 +1,199 @@
+//! Puzzle bounties solved by commit and reveal.
+//!
+//! Solvers first commit to a hash binding their address to the solution, and
+//! reveal the moves later. The reward goes to the earliest commitment that is
+//! revealed to be valid, so copying a solution seen in the mempool does not
+//! pay.
+
+use std::io::{self, Error};
+
+use alloy_primitives::{keccak256, Address, B256};
+use serde::{Deserialize, Serialize};
+use shakmaty::{uci::Uci, Position};
+
+use super::variant::{starting_position, GameVariant};
+
+/// Returns the hash of a solution, given as moves in UCI notation
+pub fn solution_hash(moves: &[String]) -> B256 {
+    keccak256(moves.join(" "))
+}
+
+/// Returns the hash `solver` commits to before revealing a solution. `salt`
+/// keeps others from guessing the solution from the commitment.
+pub fn commitment_hash(solver: Address, moves: &[String], salt: &str) -> B256 {
+    let mut bytes = solver.to_vec();
+    bytes.extend_from_slice(moves.join(" ").as_bytes());
+    bytes.extend_from_slice(salt.as_bytes());
+
+    keccak256(bytes)
+}
+
+/// Checks that `moves`, in UCI notation, can be played in a row from `fen`,
+/// a standard chess position
+pub fn check_line(fen: &str, moves: &[String]) -> io::Result<()> {
+    if moves.is_empty() {
+        return Err(Error::other("the solution has no moves"));
+    }
+
+    let mut pos = starting_position(GameVariant::Standard, Some(fen), 0)?;
+    for (ply, uci) in moves.iter().enumerate() {
+        let mv = Uci::from_ascii(uci.as_bytes())
+            .ok()
+            .and_then(|uci| uci.to_move(&pos).ok())
+            .ok_or_else(|| Error::other(format!("move {} '{uci}' is illegal", ply + 1)))?;
+        pos.play_unchecked(&mv);
+    }
+
+    Ok(())
+}
+
+/// A solver's commitment to a solution
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Commitment {
+    pub solver: Address,
+    pub hash: B256,
+    /// Whether the solver revealed a valid solution
+    pub solved: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub poster: Address,
+    /// Position to solve, in FEN; puzzles are standard chess only
+    pub fen: String,
+    /// Hash of the solution, see [`solution_hash`]
+    pub solution_hash: B256,
+    /// Amount paid to the solver, held by the chain until then
+    pub reward: u64,
+    /// Block timestamp after which no solution can be committed
+    pub deadline: u64,
+    /// Commitments in the order they were included
+    pub commitments: Vec<Commitment>,
+    /// Who was paid the reward, once awarded
+    pub winner: Option<Address>,
+    /// Whether the reward was paid out or refunded
+    pub closed: bool,
+    /// Block timestamp of the puzzle's creation
+    pub created_at: u64,
+}
+
+impl Puzzle {
+    pub fn commit(&mut self, solver: Address, hash: B256, block_time: u64) -> io::Result<()> {
+        if self.closed || block_time >= self.deadline {
+            return Err(Error::other("the puzzle no longer accepts solutions"));
+        }
+        if solver == self.poster {
+            return Err(Error::other("cannot solve your own puzzle"));
+        }
+        if self.commitments.iter().any(|c| c.solver == solver) {
+            return Err(Error::other("already committed to a solution"));
+        }
+
+        self.commitments.push(Commitment {
+            solver,
+            hash,
+            solved: false,
+        });
+
+        Ok(())
+    }
+
+    /// Checks the solution revealed by `solver` against their commitment and
+    /// the puzzle, marking their commitment as solved
+    pub fn reveal(&mut self, solver: Address, moves: &[String], salt: &str) -> io::Result<()> {
+        if self.closed {
+            return Err(Error::other("the puzzle is closed"));
+        }
+        let commitment = self
+            .commitments
+            .iter_mut()
+            .find(|c| c.solver == solver)
+            .ok_or_else(|| Error::other("no commitment to reveal"))?;
+        if commitment.solved {
+            return Err(Error::other("solution already revealed"));
+        }
+        if commitment_hash(solver, moves, salt) != commitment.hash {
+            return Err(Error::other("the solution does not match the commitment"));
+        }
+        check_line(&self.fen, moves)?;
+        if solution_hash(moves) != self.solution_hash {
+            return Err(Error::other("wrong solution"));
+        }
+
+        commitment.solved = true;
+
+        Ok(())
+    }
+
+    /// Returns who deserves the reward: the earliest commitment if it was
+    /// solved, or once reveals are over, the earliest solved one
+    pub fn winner(&self, reveals_over: bool) -> Option<Address> {
+        if reveals_over {
+            return self.commitments.iter().find(|c| c.solved).map(|c| c.solver);
+        }
+
+        self.commitments
+            .first()
+            .filter(|c| c.solved)
+            .map(|c| c.solver)
+    }
+}
+
+#[test]
+fn test_puzzle() {
+    let poster = Address::repeat_byte(1);
+    let alice = Address::repeat_byte(2);
+    let bob = Address::repeat_byte(3);
+    // Back rank mate
+    let solution = vec!["a1a8".to_string()];
+    let wrong = vec!["a1a7".to_string()];
+    let mut puzzle = Puzzle {
+        poster,
+        fen: "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1".to_string(),
+        solution_hash: solution_hash(&solution),
+        reward: 10,
+        deadline: 100,
+        commitments: Vec::new(),
+        winner: None,
+        closed: false,
+        created_at: 0,
+    };
+
+    assert!(check_line(&puzzle.fen, &["a1a9".to_string()]).is_err());
+    assert!(puzzle
+        .commit(poster, commitment_hash(poster, &solution, "x"), 1)
+        .is_err());
+    puzzle
+        .commit(alice, commitment_hash(alice, &wrong, "salt"), 1)
+        .unwrap();
+    // Bob copies Alice's commitment, which is bound to her address
+    puzzle
+        .commit(bob, commitment_hash(alice, &wrong, "salt"), 2)
+        .unwrap();
+    assert!(puzzle
+        .commit(bob, commitment_hash(bob, &solution, "pepper"), 3)
+        .is_err());
+    assert!(puzzle.commit(Address::ZERO, B256::ZERO, 100).is_err());
+
+    assert!(puzzle.reveal(alice, &solution, "salt").is_err());
+    assert!(puzzle.reveal(alice, &wrong, "salt").is_err());
+    assert!(puzzle.reveal(bob, &wrong, "salt").is_err());
+    assert_eq!(puzzle.winner(true), None);
+
+    let mut puzzle = Puzzle {
+        commitments: Vec::new(),
+        ..puzzle
+    };
+    puzzle
+        .commit(alice, commitment_hash(alice, &solution, "salt"), 1)
+        .unwrap();
+    puzzle
+        .commit(bob, commitment_hash(bob, &solution, "pepper"), 2)
+        .unwrap();
+    // Bob reveals first, but Alice committed earlier
+    puzzle.reveal(bob, &solution, "pepper").unwrap();
+    assert_eq!(puzzle.winner(false), None);
+    assert_eq!(puzzle.winner(true), Some(bob));
+    puzzle.reveal(alice, &solution, "salt").unwrap();
+    assert_eq!(puzzle.winner(false), Some(alice));
+}
@@ -0,0 +1,198 @@
+//! Elo ratings of players, kept separately per variant and time control
+//! category.
+//!
+//! Elo was chosen over Glicko-2 on purpose: it only needs integer
+//! arithmetic, so every node computes the same ratings, and Glicko-2's
+//! rating deviation would have to decay with time between rating periods,
+//! which a chain updating ratings game by game does not have. Provisional
+//! ratings moving faster over the first games stand in for a high
+//! deviation.
+
+use std::{
+    fmt,
+    io::{self, Error, ErrorKind},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{clock::TimeControl, variant::GameVariant, GameResult};
+
+/// Rating of a player before their first game in a pool
+pub const INITIAL_RATING: u32 = 1500;
+
+/// Lowest rating a player can fall to
+pub const RATING_FLOOR: u32 = 100;
+
+/// Number of games during which a rating moves faster
+pub const PROVISIONAL_GAMES: u32 = 30;
+
+/// Rating differences above this are counted as this much
+const MAX_RATING_DIFFERENCE: u32 = 400;
+
+/// Upper bound of the rating difference giving the higher rated player an
+/// expected score of 50%, 51%, and so on, from the FIDE rating regulations.
+/// Differences above the last bound give 92%.
+const EXPECTED_SCORE_BOUNDS: [u32; 42] = [
+    3, 10, 17, 25, 32, 39, 46, 53, 61, 68, 76, 83, 91, 98, 106, 113, 121, 129, 137, 145, 153, 162,
+    170, 179, 188, 197, 206, 215, 225, 235, 245, 256, 267, 278, 290, 302, 315, 328, 344, 357, 374,
+    391,
+];
+
+/// Speed of a game, derived from its time control
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TimeCategory {
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+    /// Games without a clock
+    Correspondence,
+}
+
+impl TimeCategory {
+    /// Classifies a time control by its estimated duration, the initial time
+    /// plus 40 increments
+    pub fn of(time_control: Option<TimeControl>) -> Self {
+        let tc = match time_control {
+            Some(tc) => tc,
+            None => return TimeCategory::Correspondence,
+        };
+        match tc.initial.saturating_add(tc.increment.saturating_mul(40)) {
+            0..=179 => TimeCategory::Bullet,
+            180..=479 => TimeCategory::Blitz,
+            480..=1499 => TimeCategory::Rapid,
+            _ => TimeCategory::Classical,
+        }
+    }
+}
+
+impl fmt::Display for TimeCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl FromStr for TimeCategory {
+    type Err = Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "bullet" => Ok(TimeCategory::Bullet),
+            "blitz" => Ok(TimeCategory::Blitz),
+            "rapid" => Ok(TimeCategory::Rapid),
+            "classical" => Ok(TimeCategory::Classical),
+            "correspondence" | "untimed" => Ok(TimeCategory::Correspondence),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown time control category '{s}'"),
+            )),
+        }
+    }
+}
+
+/// Games that are rated against each other
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RatingPool {
+    pub variant: GameVariant,
+    pub category: TimeCategory,
+}
+
+/// A player's rating in a pool
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rating {
+    pub rating: u32,
+    /// Number of rated games played in the pool
+    pub games: u32,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            rating: INITIAL_RATING,
+            games: 0,
+        }
+    }
+}
+
+impl Rating {
+    /// How much a single game can move the rating
+    fn k_factor(&self) -> i64 {
+        if self.games < PROVISIONAL_GAMES {
+            40
+        } else if self.rating >= 2400 {
+            10
+        } else {
+            20
+        }
+    }
+
+    /// Returns the rating after a game against `opponent` where `score` was
+    /// made, in percent
+    fn after_game(self, opponent: Rating, score: i64) -> Self {
+        let change = self.k_factor() * (score - expected_score(self.rating, opponent.rating));
+        // Rounds half away from zero
+        let change = (change + change.signum() * 50) / 100;
+
+        Self {
+            rating: (self.rating as i64 + change).max(RATING_FLOOR as i64) as u32,
+            games: self.games + 1,
+        }
+    }
+}
+
+/// Returns the score, in percent, a player rated `rating` is expected to make
+/// against a player rated `opponent`
+fn expected_score(rating: u32, opponent: u32) -> i64 {
+    let difference = rating.abs_diff(opponent).min(MAX_RATING_DIFFERENCE);
+    let higher = EXPECTED_SCORE_BOUNDS
+        .iter()
+        .position(|bound| difference <= *bound)
+        .unwrap_or(EXPECTED_SCORE_BOUNDS.len()) as i64
+        + 50;
+
+    if rating >= opponent {
+        higher
+    } else {
+        100 - higher
+    }
+}
+
+/// Returns the ratings of white and black after a game ending in `result`
+pub fn rate(white: Rating, black: Rating, result: GameResult) -> (Rating, Rating) {
+    let white_score = match result {
+        GameResult::WhiteWins => 100,
+        GameResult::BlackWins => 0,
+        GameResult::Draw => 50,
+    };
+
+    (
+        white.after_game(black, white_score),
+        black.after_game(white, 100 - white_score),
+    )
+}
+
+#[test]
+fn test_rate() {
+    let new = Rating::default();
+    let (white, black) = rate(new, new, GameResult::WhiteWins);
+    assert_eq!((white.rating, black.rating), (1520, 1480));
+    assert_eq!((white.games, black.games), (1, 1));
+
+    // A draw against a stronger player gains rating
+    let strong = Rating {
+        rating: 2000,
+        games: 100,
+    };
+    let (white, black) = rate(new, strong, GameResult::Draw);
+    assert_eq!((white.rating, black.rating), (1517, 1992));
+
+    assert_eq!(
+        TimeCategory::of(Some(TimeControl {
+            initial: 180,
+            increment: 2
+        })),
+        TimeCategory::Blitz
+    );
+    assert_eq!(TimeCategory::of(None), TimeCategory::Correspondence);
+}
@@ -0,0 +1,180 @@
+//! Simultaneous exhibitions, where a host plays many opponents at once.
+
+use std::io::{self, Error};
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use super::{clock::TimeControl, variant::GameVariant, GameOutcome, GameResult};
+
+/// A host playing white against every registered opponent, one board each
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Simul {
+    pub host: Address,
+    /// Maximum number of opponents
+    pub boards: usize,
+    pub variant: GameVariant,
+    pub time_control: Option<TimeControl>,
+    /// Opponents in registration order, which is the order of the boards
+    pub players: Vec<Address>,
+    /// Games of the boards, in board order, once the simul started
+    pub game_ids: Vec<u64>,
+    /// Block timestamp of the simul's creation
+    pub created_at: u64,
+}
+
+impl Simul {
+    pub fn started(&self) -> bool {
+        !self.game_ids.is_empty()
+    }
+
+    /// Takes a board for `player`, while the simul has not started
+    pub fn register(&mut self, player: Address) -> io::Result<()> {
+        if self.started() {
+            return Err(Error::other("registration is closed"));
+        }
+        if player == self.host {
+            return Err(Error::other("the host cannot play against themself"));
+        }
+        if self.players.contains(&player) {
+            return Err(Error::other("player is already registered"));
+        }
+        if self.players.len() >= self.boards {
+            return Err(Error::other("every board is taken"));
+        }
+        self.players.push(player);
+
+        Ok(())
+    }
+
+    /// Gives the board of `player` back, while the simul has not started
+    pub fn withdraw(&mut self, player: Address) -> io::Result<()> {
+        if self.started() {
+            return Err(Error::other("the games of the simul already started"));
+        }
+        if !self.players.contains(&player) {
+            return Err(Error::other("player is not registered"));
+        }
+        self.players.retain(|p| *p != player);
+
+        Ok(())
+    }
+}
+
+/// A board of a simul, as it stands
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SimulBoard {
+    /// Numbered from 1
+    pub board: usize,
+    pub opponent: Address,
+    pub game_id: u64,
+    /// Player whose turn it is, until the game is over
+    pub to_move: Option<Address>,
+    pub move_count: usize,
+    /// Boards aborted or ended by the arbiter are recorded as draws, with the
+    /// `Aborted` or `Arbiter` termination
+    pub outcome: Option<GameOutcome>,
+}
+
+/// Results of the host across the boards of a simul
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct SimulScore {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// Boards still being played
+    pub ongoing: usize,
+    /// Boards aborted or ended by the arbiter, which count for no one
+    #[serde(default)]
+    pub void: usize,
+    /// Points of the host, a draw being worth half a point
+    pub host_points: f64,
+    /// Points of the opponents together
+    pub opponent_points: f64,
+}
+
+/// Adds up the results of the boards, where the host plays white
+pub fn score(boards: &[SimulBoard]) -> SimulScore {
+    let mut score = SimulScore::default();
+    for board in boards {
+        match board.outcome {
+            Some(outcome) if outcome.termination.is_void() => score.void += 1,
+            Some(outcome) => match outcome.result {
+                GameResult::WhiteWins => score.wins += 1,
+                GameResult::Draw => score.draws += 1,
+                GameResult::BlackWins => score.losses += 1,
+            },
+            None => score.ongoing += 1,
+        }
+    }
+    score.host_points = score.wins as f64 + score.draws as f64 / 2.0;
+    score.opponent_points = score.losses as f64 + score.draws as f64 / 2.0;
+
+    score
+}
+
+#[test]
+fn test_simul() {
+    use super::Termination;
+
+    let host = Address::repeat_byte(1);
+    let alice = Address::repeat_byte(2);
+    let bob = Address::repeat_byte(3);
+    let carol = Address::repeat_byte(4);
+    let mut simul = Simul {
+        host,
+        boards: 2,
+        variant: GameVariant::Standard,
+        time_control: None,
+        players: Vec::new(),
+        game_ids: Vec::new(),
+        created_at: 0,
+    };
+
+    assert!(simul.register(host).is_err());
+    simul.register(alice).unwrap();
+    assert!(simul.register(alice).is_err());
+    simul.register(bob).unwrap();
+    assert!(simul.register(carol).is_err());
+    simul.withdraw(bob).unwrap();
+    simul.register(carol).unwrap();
+    assert_eq!(simul.players, [alice, carol]);
+
+    simul.game_ids = vec![1, 2];
+    assert!(simul.register(bob).is_err());
+    assert!(simul.withdraw(alice).is_err());
+
+    let ended = |result: Option<GameResult>, termination| SimulBoard {
+        board: 1,
+        opponent: alice,
+        game_id: 1,
+        to_move: None,
+        move_count: 0,
+        outcome: result.map(|result| GameOutcome {
+            result,
+            termination,
+        }),
+    };
+    let board = |result| ended(result, Termination::Checkmate);
+    // Aborted boards are recorded as draws, but are not worth half a point
+    let score = score(&[
+        board(Some(GameResult::WhiteWins)),
+        board(Some(GameResult::Draw)),
+        board(Some(GameResult::BlackWins)),
+        board(None),
+        ended(Some(GameResult::Draw), Termination::Aborted),
+        ended(Some(GameResult::Draw), Termination::Arbiter),
+    ]);
+    assert_eq!(
+        (
+            score.wins,
+            score.draws,
+            score.losses,
+            score.ongoing,
+            score.void
+        ),
+        (1, 1, 1, 1, 2)
+    );
+    assert_eq!(score.host_points, 1.5);
+    assert_eq!(score.opponent_points, 1.5);
+}
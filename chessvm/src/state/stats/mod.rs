@@ -0,0 +1,77 @@
+//! Statistics of players, aggregated over their finished games.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// What a player made of a game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// Number of games played and how they ended
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Record {
+    pub played: u32,
+    pub won: u32,
+    pub lost: u32,
+    pub drawn: u32,
+}
+
+impl Record {
+    fn add(&mut self, score: Score) {
+        self.played += 1;
+        match score {
+            Score::Win => self.won += 1,
+            Score::Loss => self.lost += 1,
+            Score::Draw => self.drawn += 1,
+        }
+    }
+}
+
+/// Games in a row ending the same way, up to the last one
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub score: Score,
+    pub length: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlayerStats {
+    #[serde(flatten)]
+    pub record: Record,
+    pub streak: Option<Streak>,
+    /// Half-moves played over all games
+    pub total_plies: u64,
+    /// Record against each opponent
+    pub head_to_head: BTreeMap<Address, Record>,
+}
+
+impl PlayerStats {
+    /// Takes a game of `plies` half-moves against `opponent` into account
+    pub fn add_game(&mut self, opponent: Address, score: Score, plies: usize) {
+        self.record.add(score);
+        self.head_to_head.entry(opponent).or_default().add(score);
+        self.total_plies += plies as u64;
+        self.streak = match self.streak {
+            Some(streak) if streak.score == score => Some(Streak {
+                score,
+                length: streak.length + 1,
+            }),
+            _ => Some(Streak { score, length: 1 }),
+        };
+    }
+
+    /// Average number of half-moves of the games played, if any
+    pub fn average_length(&self) -> Option<f64> {
+        if self.record.played == 0 {
+            return None;
+        }
+
+        Some(self.total_plies as f64 / self.record.played as f64)
+    }
+}
@@ -0,0 +1,151 @@
+//! Teams playing consultation games, where members vote on each move.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Error},
+};
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use shakmaty::Color;
+
+/// A group of players voting together on the moves of a side
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Team {
+    /// Manages the members; always a member
+    pub owner: Address,
+    pub members: BTreeSet<Address>,
+}
+
+impl Team {
+    pub fn new(owner: Address) -> Self {
+        Self {
+            owner,
+            members: BTreeSet::from([owner]),
+        }
+    }
+
+    /// Adds `member` on behalf of `player`, who must own the team
+    pub fn add(&mut self, player: Address, member: Address) -> io::Result<()> {
+        if player != self.owner {
+            return Err(Error::other("only the owner can add members to a team"));
+        }
+        if !self.members.insert(member) {
+            return Err(Error::other(format!("{member} is already a member")));
+        }
+
+        Ok(())
+    }
+
+    /// Removes `member` on behalf of `player`: the owner can remove anyone but
+    /// themself, and members can leave
+    pub fn remove(&mut self, player: Address, member: Address) -> io::Result<()> {
+        if player != self.owner && player != member {
+            return Err(Error::other("only the owner can remove other members"));
+        }
+        if member == self.owner {
+            return Err(Error::other("the owner cannot leave the team"));
+        }
+        if !self.members.remove(&member) {
+            return Err(Error::other(format!("{member} is not a member")));
+        }
+
+        Ok(())
+    }
+}
+
+/// State of a game between two teams
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Consultation {
+    pub white_team: u64,
+    pub black_team: u64,
+    /// How long the side to move votes before the most voted move is played,
+    /// in seconds from the start of its turn
+    pub voting_window: u64,
+    /// Move in UCI notation voted by each member of the side to move
+    pub votes: BTreeMap<Address, String>,
+}
+
+impl Consultation {
+    /// Returns the ID of the team playing `color`
+    pub fn team_of(&self, color: Color) -> u64 {
+        match color {
+            Color::White => self.white_team,
+            Color::Black => self.black_team,
+        }
+    }
+
+    /// Returns the number of votes for each move, counting only the votes of
+    /// `members`
+    pub fn tally(&self, members: &BTreeSet<Address>) -> BTreeMap<String, usize> {
+        let mut tally = BTreeMap::new();
+        for (voter, mv) in &self.votes {
+            if members.contains(voter) {
+                *tally.entry(mv.clone()).or_default() += 1;
+            }
+        }
+
+        tally
+    }
+
+    /// Returns the move to play: one voted by a majority of `members`, or the
+    /// most voted one if voting is over. Ties go to the move coming first in
+    /// UCI notation.
+    pub fn decide(&self, members: &BTreeSet<Address>, voting_over: bool) -> Option<String> {
+        let (mv, votes) = self
+            .tally(members)
+            .into_iter()
+            // The last maximum of a reversed iteration is the first one
+            .rev()
+            .max_by_key(|(_, votes)| *votes)?;
+
+        if voting_over || votes * 2 > members.len() {
+            Some(mv)
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_consultation() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let carol = Address::repeat_byte(3);
+    let dave = Address::repeat_byte(4);
+
+    let mut team = Team::new(alice);
+    assert!(team.add(bob, carol).is_err());
+    team.add(alice, bob).unwrap();
+    team.add(alice, carol).unwrap();
+    team.add(alice, dave).unwrap();
+    assert!(team.remove(bob, carol).is_err());
+    assert!(team.remove(alice, alice).is_err());
+    team.remove(dave, dave).unwrap();
+
+    let mut consultation = Consultation {
+        white_team: 1,
+        black_team: 2,
+        voting_window: 60,
+        votes: BTreeMap::new(),
+    };
+    consultation.votes.insert(alice, "g1f3".to_string());
+    consultation.votes.insert(bob, "e2e4".to_string());
+    // Dave left the team, so his vote does not count
+    consultation.votes.insert(dave, "g1f3".to_string());
+    assert_eq!(consultation.decide(&team.members, false), None);
+    assert_eq!(
+        consultation.decide(&team.members, true),
+        Some("e2e4".to_string())
+    );
+
+    consultation.votes.insert(carol, "g1f3".to_string());
+    assert_eq!(
+        consultation.tally(&team.members),
+        BTreeMap::from([("e2e4".to_string(), 1), ("g1f3".to_string(), 2)])
+    );
+    assert_eq!(
+        consultation.decide(&team.members, false),
+        Some("g1f3".to_string())
+    );
+}
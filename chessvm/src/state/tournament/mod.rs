@@ -0,0 +1,851 @@
+//! Tournaments whose rounds are paired on chain.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    io::{self, Error, ErrorKind},
+    str::FromStr,
+};
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use shakmaty::Color;
+
+use super::{clock::TimeControl, variant::GameVariant, GameResult};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentFormat {
+    /// Everyone plays everyone once, following the Berger tables
+    RoundRobin,
+    /// Players with similar scores are paired against each other, following
+    /// the Dutch system
+    Swiss,
+    /// Single elimination bracket, the winner of each match advancing to the
+    /// next round
+    Knockout,
+}
+
+impl fmt::Display for TournamentFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl FromStr for TournamentFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        match s.to_ascii_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "roundrobin" => Ok(TournamentFormat::RoundRobin),
+            "swiss" => Ok(TournamentFormat::Swiss),
+            "knockout" => Ok(TournamentFormat::Knockout),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown tournament format '{s}'"),
+            )),
+        }
+    }
+}
+
+/// How a drawn knockout match is decided
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tiebreak {
+    /// One more game with colors reversed, where black advances on a draw
+    #[default]
+    Armageddon,
+    /// Games with colors reversed until one is decisive
+    Replay,
+    /// The higher seed advances
+    HigherSeed,
+}
+
+/// A board of a round. A player without an opponent gets a bye, worth a win.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pairing {
+    pub white: Address,
+    pub black: Option<Address>,
+    /// Game played on the board, unless it is a bye
+    pub game_id: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    pub organizer: Address,
+    pub format: TournamentFormat,
+    /// Number of rounds to play; knockouts play as many as their bracket
+    /// needs
+    pub rounds: usize,
+    pub variant: GameVariant,
+    pub time_control: Option<TimeControl>,
+    /// Block timestamp from which players can no longer register, and the
+    /// first round can start
+    pub registration_deadline: u64,
+    /// Registered players, ordered by seed once the first round started
+    pub players: Vec<Address>,
+    /// Players who left after the first round started; they are not paired
+    /// anymore
+    pub withdrawn: Vec<Address>,
+    /// Pairings of every round started so far
+    pub pairings: Vec<Vec<Pairing>>,
+    /// How drawn knockout matches are decided
+    #[serde(default)]
+    pub tiebreak: Tiebreak,
+    /// Matches of every round of a knockout, from the first round to the
+    /// final, once it started
+    #[serde(default)]
+    pub bracket: Vec<Vec<BracketMatch>>,
+    /// Block timestamp of the tournament's creation
+    pub created_at: u64,
+}
+
+/// A game of a knockout match
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BracketGame {
+    pub game_id: u64,
+    pub white: Address,
+    pub black: Address,
+    /// Whether black advances on a draw
+    pub armageddon: bool,
+    pub result: Option<GameResult>,
+    /// Whether the game was called off, in which case it is played again
+    /// with the same colors
+    #[serde(default)]
+    pub void: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BracketMatch {
+    /// Higher seed first. A player is `None` until the previous round
+    /// decides them, or in the first round if the other player has a bye.
+    pub players: [Option<Address>; 2],
+    /// Games played so far, tiebreaks after the first one
+    pub games: Vec<BracketGame>,
+    pub winner: Option<Address>,
+}
+
+impl BracketMatch {
+    /// Returns white and black of the next game of the match: the higher
+    /// seed has white first, then colors are reversed after every game
+    pub fn next_colors(&self) -> Option<(Address, Address)> {
+        match (self.games.last(), self.players) {
+            (Some(last), _) if last.void => Some((last.white, last.black)),
+            (Some(last), _) => Some((last.black, last.white)),
+            (None, [Some(higher), Some(lower)]) => Some((higher, lower)),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the next game of the match is an Armageddon game,
+    /// which only tiebreak games following `tiebreak` are
+    pub fn next_is_armageddon(&self, tiebreak: Tiebreak) -> bool {
+        match self.games.last() {
+            Some(last) if last.void => last.armageddon,
+            Some(_) => tiebreak == Tiebreak::Armageddon,
+            None => false,
+        }
+    }
+}
+
+/// Standing of a player in a tournament
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub player: Address,
+    pub points: f64,
+    /// Sum of the points of the opponents played
+    pub buchholz: f64,
+    /// Sum of the points of the opponents beaten, plus half of those of the
+    /// opponents drawn
+    pub sonneborn_berger: f64,
+    pub withdrawn: bool,
+}
+
+impl Tournament {
+    /// Returns the number of rounds the pairing system allows, which for
+    /// round-robins is one round per opponent
+    pub fn max_rounds(&self) -> usize {
+        match self.format {
+            TournamentFormat::RoundRobin => {
+                let n = self.players.len();
+                (n + n % 2).saturating_sub(1)
+            }
+            TournamentFormat::Swiss => self.rounds,
+            TournamentFormat::Knockout => {
+                self.players.len().next_power_of_two().trailing_zeros() as usize
+            }
+        }
+    }
+
+    /// Returns `true` once the first round was started
+    pub fn started(&self) -> bool {
+        !self.pairings.is_empty() || !self.bracket.is_empty()
+    }
+
+    /// Returns the points of every player, in half-points, given the results
+    /// of the finished games
+    pub fn half_points(&self, results: &HashMap<u64, GameResult>) -> HashMap<Address, u32> {
+        let mut points: HashMap<Address, u32> = self.players.iter().map(|p| (*p, 0)).collect();
+        for pairing in self.pairings.iter().flatten() {
+            let (white, black) = pairing_half_points(pairing, results);
+            *points.entry(pairing.white).or_default() += white;
+            if let Some(black_player) = pairing.black {
+                *points.entry(black_player).or_default() += black;
+            }
+        }
+
+        points
+    }
+
+    /// Pairs the next round. Round-robins follow the Berger tables over the
+    /// seeded players, giving byes to the opponents of withdrawn players.
+    /// Swiss rounds are paired with the Dutch system.
+    pub fn pair_next_round(
+        &self,
+        results: &HashMap<u64, GameResult>,
+    ) -> Vec<(Address, Option<Address>)> {
+        let round = self.pairings.len() + 1;
+        match self.format {
+            TournamentFormat::RoundRobin => berger_round(self.players.len(), round)
+                .into_iter()
+                .filter_map(|(white, black)| {
+                    let white = self.active(self.players[white]);
+                    let black = black.and_then(|b| self.active(self.players[b]));
+                    match (white, black) {
+                        (Some(white), black) => Some((white, black)),
+                        (None, Some(black)) => Some((black, None)),
+                        (None, None) => None,
+                    }
+                })
+                .collect(),
+            TournamentFormat::Swiss => self.pair_swiss_round(round, results),
+            // Knockout rounds follow from the bracket instead
+            TournamentFormat::Knockout => Vec::new(),
+        }
+    }
+
+    /// Builds the bracket of a knockout from the seeded players, the top
+    /// seeds getting byes when the number of players is not a power of two
+    pub fn seed_bracket(&mut self) {
+        let size = self.players.len().next_power_of_two();
+        let seeds = bracket_seeds(size);
+        let mut rounds = Vec::new();
+        let mut matches: Vec<BracketMatch> = seeds
+            .chunks(2)
+            .map(|pair| BracketMatch {
+                players: [
+                    self.players.get(pair[0]).copied(),
+                    self.players.get(pair[1]).copied(),
+                ],
+                games: Vec::new(),
+                winner: None,
+            })
+            .collect();
+        while !matches.is_empty() {
+            let next = matches.len() / 2;
+            rounds.push(matches);
+            matches = (0..next)
+                .map(|_| BracketMatch {
+                    players: [None, None],
+                    games: Vec::new(),
+                    winner: None,
+                })
+                .collect();
+        }
+
+        self.bracket = rounds;
+    }
+
+    /// Records the result of a knockout game, deciding its match unless a
+    /// tiebreak is needed, or no result if the game was called off so that
+    /// it is played again. Returns `false` if the game is not in the bracket.
+    pub fn record_bracket_game(&mut self, game_id: u64, result: Option<GameResult>) -> bool {
+        let tiebreak = self.tiebreak;
+        for bracket_match in self.bracket.iter_mut().flatten() {
+            let game = match bracket_match
+                .games
+                .iter_mut()
+                .find(|g| g.game_id == game_id)
+            {
+                Some(game) => game,
+                None => continue,
+            };
+            let result = match result {
+                Some(result) => result,
+                None => {
+                    game.void = true;
+                    return true;
+                }
+            };
+            game.result = Some(result);
+
+            bracket_match.winner = match result {
+                GameResult::WhiteWins => Some(game.white),
+                GameResult::BlackWins => Some(game.black),
+                GameResult::Draw if game.armageddon => Some(game.black),
+                GameResult::Draw => match tiebreak {
+                    Tiebreak::HigherSeed => bracket_match.players[0],
+                    Tiebreak::Armageddon | Tiebreak::Replay => None,
+                },
+            };
+            return true;
+        }
+
+        false
+    }
+
+    /// Moves the winners of decided matches to the next round, and returns
+    /// the round and index of the matches waiting for a game to be created.
+    /// A player who withdrew gives their matches away to their opponents.
+    pub fn advance_bracket(&mut self) -> Vec<(usize, usize)> {
+        let withdrawn = &self.withdrawn;
+        let mut waiting = Vec::new();
+        for round in 0..self.bracket.len() {
+            for index in 0..self.bracket[round].len() {
+                if round > 0 && self.bracket[round][index].games.is_empty() {
+                    let previous = &self.bracket[round - 1];
+                    self.bracket[round][index].players =
+                        [previous[2 * index].winner, previous[2 * index + 1].winner];
+                }
+
+                let bracket_match = &mut self.bracket[round][index];
+                if bracket_match.winner.is_some() {
+                    continue;
+                }
+                // A drawn game calls for a tiebreak game, and a called off one
+                // for the same game again
+                let ready = bracket_match
+                    .games
+                    .last()
+                    .is_none_or(|g| g.result.is_some() || g.void);
+                match bracket_match.players {
+                    [Some(higher), Some(lower)] if ready => {
+                        if withdrawn.contains(&higher) {
+                            bracket_match.winner = Some(lower);
+                        } else if withdrawn.contains(&lower) {
+                            bracket_match.winner = Some(higher);
+                        } else {
+                            waiting.push((round, index));
+                        }
+                    }
+                    [Some(player), None] | [None, Some(player)] if round == 0 => {
+                        bracket_match.winner = Some(player);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        waiting
+    }
+
+    /// Returns the game `player` is playing in a knockout, if their match is
+    /// being played
+    pub fn open_bracket_game(&self, player: Address) -> Option<BracketGame> {
+        self.bracket
+            .iter()
+            .flatten()
+            .filter(|m| m.winner.is_none())
+            .filter_map(|m| m.games.last())
+            .find(|g| g.result.is_none() && !g.void && (g.white == player || g.black == player))
+            .copied()
+    }
+
+    /// Returns the winner of a knockout, once the final is decided
+    pub fn bracket_winner(&self) -> Option<Address> {
+        self.bracket.last()?.first()?.winner
+    }
+
+    /// Returns `player` if they did not withdraw
+    fn active(&self, player: Address) -> Option<Address> {
+        (!self.withdrawn.contains(&player)).then_some(player)
+    }
+
+    /// Returns the colors each player had, in order, and who they played
+    fn histories(&self) -> HashMap<Address, (Vec<Color>, HashSet<Address>)> {
+        let mut histories: HashMap<Address, (Vec<Color>, HashSet<Address>)> = HashMap::new();
+        for pairing in self.pairings.iter().flatten() {
+            if let Some(black) = pairing.black {
+                let white = histories.entry(pairing.white).or_default();
+                white.0.push(Color::White);
+                white.1.insert(black);
+                let black_history = histories.entry(black).or_default();
+                black_history.0.push(Color::Black);
+                black_history.1.insert(pairing.white);
+            }
+        }
+
+        histories
+    }
+
+    fn pair_swiss_round(
+        &self,
+        round: usize,
+        results: &HashMap<u64, GameResult>,
+    ) -> Vec<(Address, Option<Address>)> {
+        let points = self.half_points(results);
+        let histories = self.histories();
+        let had_bye: HashSet<Address> = self
+            .pairings
+            .iter()
+            .flatten()
+            .filter(|p| p.black.is_none())
+            .map(|p| p.white)
+            .collect();
+
+        // Players are ranked by score, then by seed
+        let mut ranked: Vec<Address> = self
+            .players
+            .iter()
+            .copied()
+            .filter(|p| !self.withdrawn.contains(p))
+            .collect();
+        ranked.sort_by_key(|p| std::cmp::Reverse(points[p]));
+
+        // The lowest ranked player who did not have a bye yet gets it
+        let bye = (ranked.len() % 2 == 1).then(|| {
+            let bye = ranked
+                .iter()
+                .rposition(|p| !had_bye.contains(p))
+                .unwrap_or(ranked.len() - 1);
+            ranked.remove(bye)
+        });
+
+        let played = |a: &Address, b: &Address| {
+            histories
+                .get(a)
+                .is_some_and(|(_, opponents)| opponents.contains(b))
+        };
+        let mut games = Vec::new();
+        let mut floaters: Vec<Address> = Vec::new();
+        let mut start = 0;
+        while start < ranked.len() {
+            let score = points[&ranked[start]];
+            let end = ranked[start..]
+                .iter()
+                .position(|p| points[p] != score)
+                .map_or(ranked.len(), |i| start + i);
+            let last = end == ranked.len();
+
+            // Players who could not be paired in the score group above join
+            // this one
+            let mut group: Vec<Address> = std::mem::take(&mut floaters);
+            group.extend_from_slice(&ranked[start..end]);
+            loop {
+                if let Some((pairs, left)) = pair_score_group(&group, &played) {
+                    games.extend(pairs);
+                    floaters.splice(0..0, left);
+                    break;
+                }
+                if last {
+                    // Nobody is left to float to, so rematches are allowed
+                    for pair in group.chunks(2) {
+                        games.push((pair[0], pair[1]));
+                    }
+                    break;
+                }
+                // The lowest ranked player floats down to the next group
+                floaters.insert(0, group.pop().unwrap());
+            }
+            start = end;
+        }
+
+        games
+            .into_iter()
+            .enumerate()
+            .map(|(board, (higher, lower))| {
+                let (white, black) = allocate_colors(higher, lower, round + board, &histories);
+                (white, Some(black))
+            })
+            .chain(bye.map(|bye| (bye, None)))
+            .collect()
+    }
+}
+
+/// Returns the seeds, numbered from 0, of the first round of a bracket of
+/// `size` players in match order, so that the top seeds can only meet in the
+/// last rounds
+pub fn bracket_seeds(size: usize) -> Vec<usize> {
+    let mut seeds = vec![0];
+    while seeds.len() < size {
+        let len = seeds.len();
+        seeds = seeds
+            .into_iter()
+            .flat_map(|seed| [seed, 2 * len - 1 - seed])
+            .collect();
+    }
+
+    seeds
+}
+
+/// Games of a score group, and the players left to float down
+type GroupPairing = (Vec<(Address, Address)>, Vec<Address>);
+
+/// Pairs a score group following the Dutch system: its upper half plays its
+/// lower half, trying the lower half's orders one after the other until no
+/// players meet twice. Returns the games, higher ranked player first, and the
+/// players left to float down, or `None` if the upper half cannot be paired.
+fn pair_score_group(
+    group: &[Address],
+    played: &impl Fn(&Address, &Address) -> bool,
+) -> Option<GroupPairing> {
+    fn assign(
+        upper: &[Address],
+        lower: &mut Vec<Address>,
+        played: &impl Fn(&Address, &Address) -> bool,
+        games: &mut Vec<(Address, Address)>,
+    ) -> bool {
+        let player = match upper.first() {
+            Some(player) => *player,
+            None => return true,
+        };
+        for i in 0..lower.len() {
+            if played(&player, &lower[i]) {
+                continue;
+            }
+            let opponent = lower.remove(i);
+            games.push((player, opponent));
+            if assign(&upper[1..], lower, played, games) {
+                return true;
+            }
+            games.pop();
+            lower.insert(i, opponent);
+        }
+
+        false
+    }
+
+    let (upper, lower) = group.split_at(group.len() / 2);
+    let mut lower = lower.to_vec();
+    let mut games = Vec::new();
+
+    assign(upper, &mut lower, played, &mut games).then_some((games, lower))
+}
+
+/// Gives white to the player who had it less often, then to the player who
+/// had black last, and otherwise alternates over rounds and boards, starting
+/// with the higher ranked player of the first board of the first round
+fn allocate_colors(
+    higher: Address,
+    lower: Address,
+    alternation: usize,
+    histories: &HashMap<Address, (Vec<Color>, HashSet<Address>)>,
+) -> (Address, Address) {
+    let colors = |p: &Address| histories.get(p).map(|(c, _)| c.as_slice()).unwrap_or(&[]);
+    let balance = |p: &Address| {
+        colors(p)
+            .iter()
+            .map(|c| if *c == Color::White { 1 } else { -1 })
+            .sum::<i32>()
+    };
+
+    let higher_white = match balance(&higher).cmp(&balance(&lower)) {
+        std::cmp::Ordering::Less => true,
+        std::cmp::Ordering::Greater => false,
+        std::cmp::Ordering::Equal => match (colors(&higher).last(), colors(&lower).last()) {
+            (Some(Color::Black), Some(Color::White)) => true,
+            (Some(Color::White), Some(Color::Black)) => false,
+            _ => alternation % 2 == 1,
+        },
+    };
+
+    if higher_white {
+        (higher, lower)
+    } else {
+        (lower, higher)
+    }
+}
+
+/// Returns the boards of a round of a round-robin between `n` players, as
+/// indices of white and black, following the Berger tables. Rounds are
+/// numbered from 1, and with an odd number of players, the player without an
+/// opponent gets a bye.
+pub fn berger_round(n: usize, round: usize) -> Vec<(usize, Option<usize>)> {
+    // Players are numbered from 1, and the last player of an even number of
+    // players stays in place while the others rotate
+    let m = n + n % 2;
+    let rotating = m - 1;
+    let player = |k: usize| (k <= n).then_some(k - 1);
+
+    let mut boards = Vec::new();
+    for k in 1..=rotating {
+        // Players k and l meet in round r when k + l = r + 1, modulo the
+        // number of rotating players
+        let l = match ((round + 1) % rotating + rotating - k % rotating) % rotating {
+            0 => rotating,
+            l => l,
+        };
+        if l < k {
+            continue;
+        }
+
+        let (white, black) = if l == k {
+            // The fixed player has white in even rounds
+            if round % 2 == 1 {
+                (k, m)
+            } else {
+                (m, k)
+            }
+        } else if (l - k) % 2 == 1 {
+            (k, l)
+        } else {
+            (l, k)
+        };
+        match (player(white), player(black)) {
+            (Some(white), black) => boards.push((white, black)),
+            (None, Some(black)) => boards.push((black, None)),
+            (None, None) => {}
+        }
+    }
+
+    boards
+}
+
+/// Returns the half-points of white and black on a board
+fn pairing_half_points(pairing: &Pairing, results: &HashMap<u64, GameResult>) -> (u32, u32) {
+    let game_id = match pairing.game_id {
+        Some(game_id) => game_id,
+        None => return (2, 0),
+    };
+    match results.get(&game_id) {
+        Some(GameResult::WhiteWins) => (2, 0),
+        Some(GameResult::BlackWins) => (0, 2),
+        Some(GameResult::Draw) => (1, 1),
+        None => (0, 0),
+    }
+}
+
+/// Ranks the players of a tournament by points, then Buchholz, then
+/// Sonneborn-Berger, then seed
+pub fn standings(tournament: &Tournament, results: &HashMap<u64, GameResult>) -> Vec<Standing> {
+    let points = tournament.half_points(results);
+
+    let mut standings: Vec<(usize, Standing)> = tournament
+        .players
+        .iter()
+        .enumerate()
+        .map(|(seed, player)| {
+            let mut buchholz = 0;
+            let mut sonneborn_berger = 0;
+            for pairing in tournament.pairings.iter().flatten() {
+                let (own, opponent) = match pairing.black {
+                    Some(black) if pairing.white == *player => {
+                        (pairing_half_points(pairing, results).0, black)
+                    }
+                    Some(black) if black == *player => {
+                        (pairing_half_points(pairing, results).1, pairing.white)
+                    }
+                    _ => continue,
+                };
+                if !pairing.game_id.is_some_and(|id| results.contains_key(&id)) {
+                    continue;
+                }
+                buchholz += points[&opponent];
+                // Counted in quarter-points
+                sonneborn_berger += own * points[&opponent];
+            }
+
+            (
+                seed,
+                Standing {
+                    player: *player,
+                    points: points[player] as f64 / 2.0,
+                    buchholz: buchholz as f64 / 2.0,
+                    sonneborn_berger: sonneborn_berger as f64 / 4.0,
+                    withdrawn: tournament.withdrawn.contains(player),
+                },
+            )
+        })
+        .collect();
+
+    standings.sort_by(|(seed_a, a), (seed_b, b)| {
+        b.points
+            .total_cmp(&a.points)
+            .then(b.buchholz.total_cmp(&a.buchholz))
+            .then(b.sonneborn_berger.total_cmp(&a.sonneborn_berger))
+            .then(seed_a.cmp(seed_b))
+    });
+
+    standings.into_iter().map(|(_, s)| s).collect()
+}
+
+#[test]
+fn test_berger_round() {
+    // Rounds of the Berger table for 6 players, numbered from 0
+    assert_eq!(
+        berger_round(6, 1),
+        vec![(0, Some(5)), (1, Some(4)), (2, Some(3))]
+    );
+    assert_eq!(
+        berger_round(6, 2),
+        vec![(0, Some(1)), (4, Some(2)), (5, Some(3))]
+    );
+    assert_eq!(
+        berger_round(6, 5),
+        vec![(4, Some(0)), (3, Some(1)), (2, Some(5))]
+    );
+
+    // Everyone meets everyone once, the odd player out getting a bye
+    let mut met = HashSet::new();
+    for round in 1..=5 {
+        let boards = berger_round(5, round);
+        assert_eq!(boards.iter().filter(|(_, b)| b.is_none()).count(), 1);
+        for (white, black) in boards.into_iter() {
+            if let Some(black) = black {
+                assert!(met.insert((white.min(black), white.max(black))));
+            }
+        }
+    }
+    assert_eq!(met.len(), 10);
+}
+
+#[test]
+fn test_swiss_pairing() {
+    let players: Vec<Address> = (1..=5).map(Address::repeat_byte).collect();
+    let mut tournament = Tournament {
+        organizer: players[0],
+        format: TournamentFormat::Swiss,
+        rounds: 3,
+        variant: GameVariant::Standard,
+        time_control: None,
+        registration_deadline: 0,
+        players: players.clone(),
+        withdrawn: Vec::new(),
+        pairings: Vec::new(),
+        tiebreak: Tiebreak::default(),
+        bracket: Vec::new(),
+        created_at: 0,
+    };
+    let mut results = HashMap::new();
+
+    // Top half plays bottom half, and the lowest ranked player gets the bye
+    let round = tournament.pair_next_round(&results);
+    assert_eq!(
+        round,
+        vec![
+            (players[0], Some(players[2])),
+            (players[3], Some(players[1])),
+            (players[4], None),
+        ]
+    );
+    let pairing = |(white, black): (Address, Option<Address>), game_id| Pairing {
+        white,
+        black,
+        game_id: black.map(|_| game_id),
+    };
+    tournament.pairings.push(
+        round
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| pairing(p, i as u64))
+            .collect(),
+    );
+    results.insert(0, GameResult::WhiteWins);
+    results.insert(1, GameResult::WhiteWins);
+
+    // Winners meet, the player who had black gets white, and the bye goes to
+    // someone else
+    let round = tournament.pair_next_round(&results);
+    assert_eq!(
+        round,
+        vec![
+            (players[3], Some(players[0])),
+            (players[1], Some(players[4])),
+            (players[2], None),
+        ]
+    );
+
+    let standings = standings(&tournament, &results);
+    let ranking: Vec<Address> = standings.iter().map(|s| s.player).collect();
+    assert_eq!(
+        ranking,
+        vec![players[0], players[3], players[4], players[1], players[2]]
+    );
+    assert_eq!(standings[0].points, 1.0);
+}
+
+#[test]
+fn test_bracket() {
+    assert_eq!(bracket_seeds(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+
+    let players: Vec<Address> = (1..=3).map(Address::repeat_byte).collect();
+    let mut tournament = Tournament {
+        organizer: players[0],
+        format: TournamentFormat::Knockout,
+        rounds: 0,
+        variant: GameVariant::Standard,
+        time_control: None,
+        registration_deadline: 0,
+        players: players.clone(),
+        withdrawn: Vec::new(),
+        pairings: Vec::new(),
+        tiebreak: Tiebreak::Armageddon,
+        bracket: Vec::new(),
+        created_at: 0,
+    };
+    tournament.seed_bracket();
+    assert_eq!(tournament.max_rounds(), 2);
+
+    // The top seed gets a bye, the others play
+    assert_eq!(tournament.advance_bracket(), vec![(0, 1)]);
+    assert_eq!(tournament.bracket[0][0].winner, Some(players[0]));
+    let play = |tournament: &mut Tournament, round: usize, index: usize, game_id| {
+        let bracket_match = &mut tournament.bracket[round][index];
+        let (white, black) = bracket_match.next_colors().unwrap();
+        let armageddon = bracket_match.next_is_armageddon(Tiebreak::Armageddon);
+        bracket_match.games.push(BracketGame {
+            game_id,
+            white,
+            black,
+            armageddon,
+            result: None,
+            void: false,
+        });
+        (white, black)
+    };
+    assert_eq!(play(&mut tournament, 0, 1, 1), (players[1], players[2]));
+
+    // A draw calls for an Armageddon game with colors reversed, where a draw
+    // sends black through
+    assert!(tournament.record_bracket_game(1, Some(GameResult::Draw)));
+    assert_eq!(tournament.advance_bracket(), vec![(0, 1)]);
+    assert_eq!(play(&mut tournament, 0, 1, 2), (players[2], players[1]));
+    // Called off games are played again with the same colors
+    assert!(tournament.record_bracket_game(2, None));
+    assert_eq!(tournament.bracket[0][1].winner, None);
+    assert_eq!(tournament.advance_bracket(), vec![(0, 1)]);
+    assert_eq!(play(&mut tournament, 0, 1, 5), (players[2], players[1]));
+    assert!(tournament.bracket[0][1].games[2].armageddon);
+    assert!(tournament.record_bracket_game(5, Some(GameResult::Draw)));
+    assert_eq!(tournament.advance_bracket(), vec![(1, 0)]);
+    assert_eq!(
+        tournament.bracket[1][0].players,
+        [Some(players[0]), Some(players[1])]
+    );
+
+    play(&mut tournament, 1, 0, 3);
+    assert!(!tournament.record_bracket_game(4, Some(GameResult::WhiteWins)));
+    assert!(tournament.record_bracket_game(3, Some(GameResult::BlackWins)));
+    assert!(tournament.advance_bracket().is_empty());
+    assert_eq!(tournament.bracket_winner(), Some(players[1]));
+
+    // A player who withdrew gives their next match away
+    let players: Vec<Address> = (1..=4).map(Address::repeat_byte).collect();
+    let mut tournament = Tournament {
+        players: players.clone(),
+        bracket: Vec::new(),
+        ..tournament
+    };
+    tournament.seed_bracket();
+    assert_eq!(tournament.advance_bracket(), vec![(0, 0), (0, 1)]);
+    play(&mut tournament, 0, 0, 1);
+    play(&mut tournament, 0, 1, 2);
+    assert_eq!(
+        tournament.open_bracket_game(players[0]).map(|g| g.game_id),
+        Some(1)
+    );
+    tournament.withdrawn.push(players[1]);
+    assert!(tournament.record_bracket_game(1, Some(GameResult::WhiteWins)));
+    assert!(tournament.record_bracket_game(2, Some(GameResult::WhiteWins)));
+    assert_eq!(tournament.open_bracket_game(players[0]), None);
+    assert!(tournament.advance_bracket().is_empty());
+    assert_eq!(tournament.bracket_winner(), Some(players[0]));
+}
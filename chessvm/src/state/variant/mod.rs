@@ -0,0 +1,204 @@
+//! Chess variants supported by ChessVM.
+
+use std::{
+    fmt,
+    io::{self, Error, ErrorKind},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use shakmaty::{
+    fen::Fen,
+    variant::{Variant, VariantPosition},
+    CastlingMode,
+};
+
+/// The rules a game is played under
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default,
+)]
+pub enum GameVariant {
+    #[default]
+    Standard,
+    Chess960,
+    Atomic,
+    Crazyhouse,
+    KingOfTheHill,
+    ThreeCheck,
+    Antichess,
+    Horde,
+    RacingKings,
+}
+
+impl GameVariant {
+    /// The shakmaty variant implementing the rules; Chess960 only differs
+    /// from standard chess in its castling mode
+    fn rules(self) -> Variant {
+        match self {
+            GameVariant::Standard | GameVariant::Chess960 => Variant::Chess,
+            GameVariant::Atomic => Variant::Atomic,
+            GameVariant::Crazyhouse => Variant::Crazyhouse,
+            GameVariant::KingOfTheHill => Variant::KingOfTheHill,
+            GameVariant::ThreeCheck => Variant::ThreeCheck,
+            GameVariant::Antichess => Variant::Antichess,
+            GameVariant::Horde => Variant::Horde,
+            GameVariant::RacingKings => Variant::RacingKings,
+        }
+    }
+
+    pub fn castling_mode(self) -> CastlingMode {
+        match self {
+            GameVariant::Chess960 => CastlingMode::Chess960,
+            _ => CastlingMode::Standard,
+        }
+    }
+
+    /// Human readable name, as used in PGN `Variant` tags
+    pub fn name(self) -> &'static str {
+        match self {
+            GameVariant::Standard => "Standard",
+            GameVariant::Chess960 => "Chess960",
+            GameVariant::Atomic => "Atomic",
+            GameVariant::Crazyhouse => "Crazyhouse",
+            GameVariant::KingOfTheHill => "King of the Hill",
+            GameVariant::ThreeCheck => "Three-check",
+            GameVariant::Antichess => "Antichess",
+            GameVariant::Horde => "Horde",
+            GameVariant::RacingKings => "Racing Kings",
+        }
+    }
+}
+
+impl fmt::Display for GameVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for GameVariant {
+    type Err = Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        match normalized.as_str() {
+            "standard" | "chess" => Ok(GameVariant::Standard),
+            "chess960" | "fischerrandom" => Ok(GameVariant::Chess960),
+            "atomic" => Ok(GameVariant::Atomic),
+            "crazyhouse" => Ok(GameVariant::Crazyhouse),
+            "kingofthehill" | "koth" => Ok(GameVariant::KingOfTheHill),
+            "threecheck" | "3check" => Ok(GameVariant::ThreeCheck),
+            "antichess" => Ok(GameVariant::Antichess),
+            "horde" => Ok(GameVariant::Horde),
+            "racingkings" => Ok(GameVariant::RacingKings),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown variant '{s}'"),
+            )),
+        }
+    }
+}
+
+/// Returns the back rank of Chess960 starting position `n` (0 to 959),
+/// following Scharnagl's numbering
+fn chess960_back_rank(n: u64) -> [char; 8] {
+    let mut rank = [' '; 8];
+
+    // Bishops go on opposite colored squares
+    let (n, light) = (n / 4, n % 4);
+    rank[(2 * light + 1) as usize] = 'B';
+    let (n, dark) = (n / 4, n % 4);
+    rank[(2 * dark) as usize] = 'B';
+
+    let place_on_empty = |rank: &mut [char; 8], nth: usize, piece: char| {
+        let square = (0..8).filter(|i| rank[*i] == ' ').nth(nth).unwrap();
+        rank[square] = piece;
+    };
+
+    let (n, queen) = (n / 6, n % 6);
+    place_on_empty(&mut rank, queen as usize, 'Q');
+
+    // Knight placements on the five remaining squares
+    const KNIGHTS: [(usize, usize); 10] = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (2, 3),
+        (2, 4),
+        (3, 4),
+    ];
+    let (first, second) = KNIGHTS[n as usize];
+    // Place the second knight first so the index of the first one is unaffected
+    place_on_empty(&mut rank, second, 'N');
+    place_on_empty(&mut rank, first, 'N');
+
+    // The king always goes between the rooks
+    place_on_empty(&mut rank, 0, 'R');
+    place_on_empty(&mut rank, 0, 'K');
+    place_on_empty(&mut rank, 0, 'R');
+
+    rank
+}
+
+/// Returns the FEN of a Chess960 starting position picked by `seed`
+pub fn chess960_fen(seed: u64) -> String {
+    let white: String = chess960_back_rank(seed % 960).iter().collect();
+    let black = white.to_ascii_lowercase();
+
+    format!("{black}/pppppppp/8/8/8/8/PPPPPPPP/{white} w KQkq - 0 1")
+}
+
+/// Returns the position a game of `variant` starts from: `fen` if given, the
+/// variant's initial position otherwise. Chess960 games without a FEN start
+/// from a position picked by `seed`.
+/// # Errors
+/// Fails if the FEN is malformed or is not a legal position of the variant
+pub fn starting_position(
+    variant: GameVariant,
+    fen: Option<&str>,
+    seed: u64,
+) -> io::Result<VariantPosition> {
+    let fen = match (fen, variant) {
+        (Some(fen), _) => fen.to_string(),
+        (None, GameVariant::Chess960) => chess960_fen(seed),
+        (None, _) => return Ok(VariantPosition::new(variant.rules())),
+    };
+
+    let setup = Fen::from_ascii(fen.as_bytes())
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("invalid FEN: {e}")))?
+        .into_setup();
+
+    VariantPosition::from_setup(variant.rules(), setup, variant.castling_mode()).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("FEN is not a legal {variant} position: {e}"),
+        )
+    })
+}
+
+#[test]
+fn test_chess960() {
+    // Position 518 is the standard starting position
+    assert_eq!(
+        chess960_fen(518),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+    );
+
+    let mut back_ranks = std::collections::HashSet::new();
+    for n in 0..960 {
+        starting_position(GameVariant::Chess960, None, n).unwrap();
+        back_ranks.insert(chess960_back_rank(n));
+    }
+    assert_eq!(back_ranks.len(), 960);
+
+    assert!(starting_position(GameVariant::Horde, None, 0).is_ok());
+    assert!(starting_position(GameVariant::Standard, Some("not a fen"), 0).is_err());
+}
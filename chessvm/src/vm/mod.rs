@@ -1,9 +1,10 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io::{self, Error, ErrorKind},
     time::Duration,
 };
 
+use alloy_primitives::Address;
 use avalanche_types::{
     choices, ids,
     subnet::{
@@ -29,7 +30,7 @@ use avalanche_types::{
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use semver::Version;
-use std::{collections::VecDeque, sync::Arc};
+use std::{cmp::Reverse, collections::VecDeque, sync::Arc};
 use tokio::sync::{mpsc::Sender, RwLock};
 
 use crate::{
@@ -38,12 +39,15 @@ use crate::{
         static_handlers::{StaticHandler, StaticService},
     },
     block::{tx, Block},
-    genesis::Genesis,
-    state,
+    genesis::{FeeSchedule, Genesis},
+    state::{self, ledger::Ledger},
 };
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Most transactions included in a block
+const MAX_BLOCK_TXS: usize = 100;
+
 pub struct VmState {
     pub ctx: Option<Context<ValidatorStateClient>>,
     pub version: Version,
@@ -82,6 +86,8 @@ pub struct Vm<A> {
     /// A queue of data that have not been put into a block and proposed yet.
     /// Mempool is not persistent, so just keep in memory via Vm.
     pub mempool: Arc<RwLock<VecDeque<tx::Transaction>>>,
+    /// IDs of the transactions in the mempool, to refuse duplicates
+    pub mempool_ids: Arc<RwLock<HashSet<ids::Id>>>,
 }
 
 impl<A> Default for Vm<A>
@@ -103,6 +109,7 @@ where
             vm_state: Arc::new(RwLock::new(VmState::default())),
             app_sender: None,
             mempool: Arc::new(RwLock::new(VecDeque::with_capacity(100))),
+            mempool_ids: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 
@@ -160,11 +167,44 @@ where
         }
     }
 
-    /// Allows for a transaction to be added to this VM mempool
+    /// Allows for a transaction to be added to this VM mempool. Transactions
+    /// have to be signed by their sender, who has to be able to pay the fee,
+    /// and can only be included once.
     pub async fn submit_tx(&self, tx: tx::Transaction) -> io::Result<()> {
         log::info!("vm::submit_tx called!");
 
+        tx.verify_signature()?;
+        let tx_id = tx.compute_id()?;
+        let vm_state = self.vm_state.read().await;
+        if let Some(state) = &vm_state.state {
+            if state.get_receipt(&tx_id).await.is_some() {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("transaction {tx_id} was already accepted"),
+                ));
+            }
+            // Accounts that cannot pay would fill the mempool for free
+            let fee = tx.fee(&state.genesis.fees);
+            let balance = state
+                .state_at(&vm_state.preferred)
+                .await?
+                .get_balance(tx.sender)
+                .await;
+            if fee > balance {
+                return Err(Error::other(format!(
+                    "sender cannot pay the fee of {fee} with a balance of {balance}"
+                )));
+            }
+        }
+        drop(vm_state);
+
         let mut vm_mempool = self.mempool.write().await;
+        if !self.mempool_ids.write().await.insert(tx_id) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("transaction {tx_id} is already pending"),
+            ));
+        }
         vm_mempool.push_back(tx);
 
         log::info!("transaction added to mempool!");
@@ -216,13 +256,29 @@ where
         vm_state.version = version;
 
         let genesis = Genesis::from_slice(genesis_bytes)?;
-        vm_state.genesis = genesis;
+        vm_state.genesis = genesis.clone();
 
         let current = db_manager.current().await?;
         let state = state::State {
             db: Arc::new(RwLock::new(current.db)),
             verified_blocks: Arc::new(RwLock::new(HashMap::new())),
             game_states: Arc::new(RwLock::new(HashMap::new())),
+            archived_games: Arc::new(RwLock::new(HashMap::new())),
+            challenges: Arc::new(RwLock::new(HashMap::new())),
+            rematches: Arc::new(RwLock::new(HashMap::new())),
+            ratings: Arc::new(RwLock::new(HashMap::new())),
+            player_stats: Arc::new(RwLock::new(HashMap::new())),
+            names: Arc::new(RwLock::new(state::names::NameRegistry::default())),
+            tournaments: Arc::new(RwLock::new(HashMap::new())),
+            simuls: Arc::new(RwLock::new(HashMap::new())),
+            seeks: Arc::new(RwLock::new(HashMap::new())),
+            ledger: Arc::new(RwLock::new(state::ledger::Ledger::from_allocations(
+                &genesis.allocations,
+            ))),
+            puzzles: Arc::new(RwLock::new(HashMap::new())),
+            teams: Arc::new(RwLock::new(HashMap::new())),
+            receipts: Arc::new(RwLock::new(HashMap::new())),
+            genesis: Arc::new(genesis),
         };
         vm_state.state = Some(state.clone());
 
@@ -232,6 +288,9 @@ where
 
         let has_last_accepted = state.has_last_accepted_block().await?;
         if has_last_accepted {
+            // Only blocks are persisted, so the rest of the state is rebuilt
+            // from them
+            state.replay_accepted_blocks().await?;
             let last_accepted_blk_id = state.get_last_accepted_block_id().await?;
             vm_state.preferred = last_accepted_blk_id;
             log::info!("initialized Vm with last accepted block {last_accepted_blk_id}");
@@ -242,6 +301,7 @@ where
                 0,
                 vm_state.genesis.data.clone(),
                 Vec::new(),
+                Vec::new(),
                 choices::status::Status::default(),
             )?;
             genesis_block.set_state(state.clone());
@@ -322,30 +382,46 @@ where
         let mut mempool = self.mempool.write().await;
 
         log::info!("build_block called for {} mempool", mempool.len());
-        if mempool.is_empty() {
-            log::info!("Mempool is empty, returning Err!");
-            return Err(Error::new(ErrorKind::Other, "no pending block"));
-        }
 
         let vm_state = self.vm_state.read().await;
         if let Some(state) = &vm_state.state {
-            self.notify_block_ready().await;
-
-            let prnt_blk = state.get_block(&vm_state.preferred).await?;
-            // log::info!("Got parent block!");
             let unix_now = Utc::now()
                 .timestamp()
                 .try_into()
                 .expect("timestamp to convert from i64 to u64");
 
-            let first_tx = mempool.pop_front().unwrap();
+            // The new block follows the preferred one, so fees, seeks, votes
+            // and clocks are checked against the state as of that block. A
+            // block is worth building for seeks to pair, voted moves to play
+            // or games lost on time even without transactions.
+            let prnt_state = state.state_at(&vm_state.preferred).await?;
+            let txs = {
+                let ledger = prnt_state.ledger.read().await;
+                let mut mempool_ids = self.mempool_ids.write().await;
+                next_transactions(&mut mempool, &mut mempool_ids, &ledger, &state.genesis.fees)
+            };
+            let seek_pairs = prnt_state.pair_seeks(unix_now).await;
+            if txs.is_empty()
+                && seek_pairs.is_empty()
+                && !prnt_state.has_due_votes(unix_now).await
+                && !prnt_state.has_flagged_games(unix_now).await
+            {
+                log::info!("Mempool is empty, returning Err!");
+                return Err(Error::other("no pending block"));
+            }
+
+            self.notify_block_ready().await;
+
+            let prnt_blk = state.get_block(&vm_state.preferred).await?;
+            // log::info!("Got parent block!");
 
             let mut block = Block::try_new(
                 prnt_blk.id(),
                 prnt_blk.height() + 1,
                 unix_now,
                 String::from("Random Block Data"),
-                vec![first_tx],
+                txs,
+                seek_pairs,
                 choices::status::Status::Processing,
             )?;
             block.set_state(state.clone());
@@ -366,6 +442,27 @@ where
     async fn set_preference(&self, id: ids::Id) -> io::Result<()> {
         let mut vm_state = self.vm_state.write().await;
         vm_state.preferred = id;
+        let state = vm_state.state.clone();
+        drop(vm_state);
+
+        // Seeks posted by the preferred block may be paired by the next one,
+        // and votes cast in it or clocks running since may be due
+        if let Some(state) = state {
+            let prnt_state = match state.state_at(&id).await {
+                Ok(prnt_state) => prnt_state,
+                Err(e) => {
+                    log::warn!("cannot get the state as of the preferred block: {e}");
+                    return Ok(());
+                }
+            };
+            let unix_now = Utc::now().timestamp().try_into().unwrap_or_default();
+            if !prnt_state.pair_seeks(unix_now).await.is_empty()
+                || prnt_state.has_due_votes(unix_now).await
+                || prnt_state.has_flagged_games(unix_now).await
+            {
+                self.notify_block_ready().await;
+            }
+        }
 
         Ok(())
     }
@@ -574,3 +671,96 @@ where
         Err(Error::new(ErrorKind::NotFound, "state manager not found"))
     }
 }
+
+/// Takes the transactions of the next block out of the mempool, highest tip
+/// first and the oldest one among equal tips. Transactions whose sender
+/// cannot pay their fee on top of the ones already taken are dropped, so
+/// that unfunded transactions do not pile up in the mempool.
+fn next_transactions(
+    mempool: &mut VecDeque<tx::Transaction>,
+    mempool_ids: &mut HashSet<ids::Id>,
+    ledger: &Ledger,
+    fees: &FeeSchedule,
+) -> Vec<tx::Transaction> {
+    // Sorting is stable, so equal tips keep the mempool's order
+    let mut by_tip: Vec<usize> = (0..mempool.len()).collect();
+    by_tip.sort_by_key(|i| Reverse(mempool[*i].tip));
+
+    let mut spent: HashMap<Address, u64> = HashMap::new();
+    let mut taken = Vec::new();
+    let mut removed = HashSet::new();
+    for i in by_tip {
+        if taken.len() == MAX_BLOCK_TXS {
+            break;
+        }
+        let tx = &mempool[i];
+        let fee = tx.fee(fees);
+        let spent = spent.entry(tx.sender).or_default();
+        match spent.checked_add(fee) {
+            Some(total) if total <= ledger.balance(tx.sender) => {
+                *spent = total;
+                taken.push(i);
+            }
+            _ => log::info!("dropping transaction whose sender cannot pay the fee of {fee}"),
+        }
+        removed.insert(i);
+    }
+
+    let txs = taken.iter().map(|i| mempool[*i].clone()).collect();
+    let mut i = 0;
+    mempool.retain(|tx| {
+        let keep = !removed.contains(&i);
+        if !keep {
+            if let Ok(tx_id) = tx.compute_id() {
+                mempool_ids.remove(&tx_id);
+            }
+        }
+        i += 1;
+        keep
+    });
+
+    txs
+}
+
+#[test]
+fn test_next_transactions() {
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let ledger =
+        Ledger::from_allocations(&std::collections::BTreeMap::from([(alice, 25), (bob, 5)]));
+    let fees = FeeSchedule {
+        base_fee: 10,
+        ..Default::default()
+    };
+    let transfer = |sender, tip| tx::Transaction {
+        action: tx::ActionType::Transfer {
+            to: Address::ZERO,
+            amount: 0,
+            block_id: ids::Id::empty(),
+        },
+        bytes: Vec::new(),
+        id: ids::Id::empty(),
+        size: 0,
+        sender,
+        tip,
+        nonce: 0,
+        signature: String::new(),
+    };
+
+    // Highest tip first, and Alice can only pay for two of her transactions
+    // while Bob cannot pay for his
+    let mut mempool = VecDeque::from([
+        transfer(alice, 0),
+        transfer(bob, 0),
+        transfer(alice, 5),
+        transfer(alice, 0),
+    ]);
+    let mut mempool_ids: HashSet<_> = mempool.iter().map(|tx| tx.compute_id().unwrap()).collect();
+    let txs = next_transactions(&mut mempool, &mut mempool_ids, &ledger, &fees);
+    let taken: Vec<_> = txs.iter().map(|tx| (tx.sender, tx.tip)).collect();
+    assert_eq!(taken, vec![(alice, 5), (alice, 0)]);
+
+    // The others are dropped rather than left to pile up
+    assert!(mempool.is_empty());
+    assert!(mempool_ids.is_empty());
+}
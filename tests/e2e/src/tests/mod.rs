@@ -6,9 +6,11 @@ use std::{
     time::{Duration, Instant},
 };
 
-use alloy_primitives::Address;
 use avalanche_network_runner_sdk::{BlockchainSpec, Client, GlobalConfig, StartRequest};
-use avalanche_types::{ids, jsonrpc::client::info as avalanche_sdk_info, subnet};
+use avalanche_types::{
+    ids, jsonrpc::client::info as avalanche_sdk_info, key::secp256k1::private_key, subnet,
+};
+use chessvm::{client::address_of, state::variant::GameVariant};
 
 const AVALANCHEGO_VERSION: &str = "v1.10.9";
 
@@ -85,6 +87,7 @@ async fn e2e() {
     // write some random genesis file
     let genesis = chessvm::genesis::Genesis {
         data: random_manager::secure_string(10),
+        ..Default::default()
     };
     let genesis_file_path = random_manager::tmp_path(10, None).unwrap();
     genesis.sync(&genesis_file_path).unwrap();
@@ -250,13 +253,20 @@ async fn e2e() {
     // let height0 = resp.result.unwrap().block.height();
 
     log::info!("testing create_game method");
-    let white = Address::ZERO;
-    let addr_str = "0x66f9664f97F2b50F62D13eA064982f936dE76657";
-    let black = Address::parse_checksummed(addr_str, None).unwrap();
-
-    let resp = chessvm::client::create_game(&ep, &chain_url_path, white, black)
-        .await
-        .unwrap();
+    // Transactions are signed by the players sending them
+    let white = private_key::Key::from_bytes(&[1; 32]).unwrap();
+    let black = address_of(&private_key::Key::from_bytes(&[2; 32]).unwrap());
+
+    let resp = chessvm::client::create_game(
+        &ep,
+        &chain_url_path,
+        &white,
+        black,
+        GameVariant::Standard,
+        None,
+    )
+    .await
+    .unwrap();
 
     let game_id = resp.result.unwrap().game_id;
 
@@ -280,7 +290,7 @@ async fn e2e() {
         to: String::from("e4"),
         promotion: None,
     };
-    let resp_3 = chessvm::client::make_move(&ep, &chain_url_path, white, game_id, mv)
+    let resp_3 = chessvm::client::make_move(&ep, &chain_url_path, &white, game_id, mv)
         .await
         .unwrap();
     log::info!("resp3 is {:?}", resp_3);
@@ -297,12 +307,18 @@ async fn e2e() {
 
     // Now testing a chess game with an ending
     // Create new player
-    let brown =
-        Address::parse_checksummed("0x7f610402ccc4CC1BEbcE9699819200f5f28ED6e3", None).unwrap();
+    let brown = private_key::Key::from_bytes(&[3; 32]).unwrap();
     // Create new game
-    let resp5 = chessvm::client::create_game(&ep, &chain_url_path, white, brown)
-        .await
-        .unwrap();
+    let resp5 = chessvm::client::create_game(
+        &ep,
+        &chain_url_path,
+        &white,
+        address_of(&brown),
+        GameVariant::Standard,
+        None,
+    )
+    .await
+    .unwrap();
     let game_id_2 = resp5.result.unwrap().game_id;
     log::info!("Created a new game with game_id {}", game_id_2);
 
@@ -316,7 +332,7 @@ async fn e2e() {
         to: String::from("f3"),
         promotion: None,
     };
-    let resp6 = chessvm::client::make_move(&ep, &chain_url_path, white, game_id_2, mv)
+    let resp6 = chessvm::client::make_move(&ep, &chain_url_path, &white, game_id_2, mv)
         .await
         .unwrap();
 
@@ -329,7 +345,7 @@ async fn e2e() {
         to: String::from("e6"),
         promotion: None,
     };
-    let resp7 = chessvm::client::make_move(&ep, &chain_url_path, brown, game_id_2, mv)
+    let resp7 = chessvm::client::make_move(&ep, &chain_url_path, &brown, game_id_2, mv)
         .await
         .unwrap();
 
@@ -342,7 +358,7 @@ async fn e2e() {
         to: String::from("g4"),
         promotion: None,
     };
-    let resp8 = chessvm::client::make_move(&ep, &chain_url_path, white, game_id_2, mv)
+    let resp8 = chessvm::client::make_move(&ep, &chain_url_path, &white, game_id_2, mv)
         .await
         .unwrap();
 
@@ -355,7 +371,7 @@ async fn e2e() {
         to: String::from("h4"),
         promotion: None,
     };
-    let resp9 = chessvm::client::make_move(&ep, &chain_url_path, brown, game_id_2, mv)
+    let resp9 = chessvm::client::make_move(&ep, &chain_url_path, &brown, game_id_2, mv)
         .await
         .unwrap();
 
@@ -443,6 +459,7 @@ async fn start_network() {
     // write some random genesis file
     let genesis = chessvm::genesis::Genesis {
         data: random_manager::secure_string(10),
+        ..Default::default()
     };
     let genesis_file_path = random_manager::tmp_path(10, None).unwrap();
     genesis.sync(&genesis_file_path).unwrap();